@@ -1,18 +1,40 @@
 #![allow(dead_code)]
 
 use std::str::FromStr;
-use criterion::{criterion_group, Criterion, criterion_main};
+use criterion::{BatchSize, Criterion, criterion_main};
 use mime_more::Mime;
 
 fn bench_from_str(c: &mut Criterion) {
     c.bench_function("ModuleType::from_known_str", |b| {
         b.iter(|| {
-            use std::str::FromStr;
             Mime::from_str("application/json").unwrap();
         })
     });
 }
 
+fn bench_inner_mime(c: &mut Criterion) {
+    let mime = Mime::from_str("text/plain; charset=utf-8").unwrap();
+    c.bench_function("Mime::as_mime", |b| {
+        b.iter(|| {
+            std::hint::black_box(mime.as_mime());
+        })
+    });
+
+    c.bench_function("Mime::to_mime", |b| {
+        b.iter(|| {
+            std::hint::black_box(mime.to_mime());
+        })
+    });
+
+    c.bench_function("Mime::into_mime", |b| {
+        b.iter_batched(
+            || mime.clone(),
+            |mime| std::hint::black_box(mime.into_mime()),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
 #[cfg(feature = "extension")]
 fn bench_from_ext(c: &mut Criterion) {
     c.bench_function("ModuleType::from_ext", |b| {
@@ -60,7 +82,7 @@ fn bench_is_texture(c: &mut Criterion) {
         b.iter(|| {
             let mime = Mime::from_str("image/png").unwrap();
 
-            mime_more::texture::is_texture_mime(&mime.mime);
+            mime_more::texture::is_texture_mime(mime.as_mime());
         })
     });
 
@@ -89,7 +111,7 @@ fn bench_isnt_texture(c: &mut Criterion) {
         b.iter(|| {
             let mime = Mime::from_str("text/plain").unwrap();
 
-            mime_more::texture::is_texture_mime(&mime.mime);
+            mime_more::texture::is_texture_mime(mime.as_mime());
         })
     });
 
@@ -116,7 +138,7 @@ fn bench_large_file_isnt_texture(c: &mut Criterion) {
         b.iter(|| {
             let mime = Mime::from_str("text/plain").unwrap();
 
-            mime_more::texture::is_texture_mime(&mime.mime);
+            mime_more::texture::is_texture_mime(mime.as_mime());
         })
     });
 
@@ -129,7 +151,7 @@ fn bench_large_file_isnt_texture(c: &mut Criterion) {
     });
 }
 
-#[cfg(feature = "dataurl")]
+#[cfg(all(feature = "dataurl", feature = "std"))]
 fn bench_dataurl(c: &mut Criterion) {
     c.bench_function("Dataurl::from_path", |b| {
         b.iter(|| {
@@ -140,6 +162,37 @@ fn bench_dataurl(c: &mut Criterion) {
     });
 }
 
+#[cfg(feature = "dataurl")]
+fn bench_encode_large(c: &mut Criterion) {
+    use mime_more::dataurl::Dataurl;
+
+    let data: Vec<u8> = (0..1024 * 1024).map(|i| (i * 7 % 251) as u8).collect();
+    let dataurl = Dataurl::new(Mime::APPLICATION_OCTET_STREAM, data).unwrap();
+    c.bench_function("Dataurl::to_string (1 MB)", |b| {
+        b.iter(|| dataurl.to_string())
+    });
+    c.bench_function("Dataurl as Display (1 MB)", |b| {
+        b.iter(|| ToString::to_string(&dataurl))
+    });
+}
+
+#[cfg(feature = "dataurl")]
+fn bench_parse_large(c: &mut Criterion) {
+    use mime_more::dataurl::{Dataurl, DataurlRef};
+
+    let data: Vec<u8> = (0..1024 * 1024).map(|i| (i * 7 % 251) as u8).collect();
+    let url = Dataurl::new(Mime::APPLICATION_OCTET_STREAM, data).unwrap().to_string();
+    c.bench_function("DataurlRef::parse (1 MB)", |b| {
+        b.iter(|| DataurlRef::parse(&url).unwrap().mime)
+    });
+    c.bench_function("DataurlRef::decode (1 MB)", |b| {
+        b.iter(|| DataurlRef::parse(&url).unwrap().decode().unwrap().len())
+    });
+    c.bench_function("Dataurl::from_str (1 MB)", |b| {
+        b.iter(|| Dataurl::from_str(&url).unwrap().mime)
+    });
+}
+
 #[cfg(feature = "dataurl")]
 fn decode_texture(c: &mut Criterion) {
     c.bench_function("Dataurl::from_str", |b| {
@@ -150,10 +203,31 @@ fn decode_texture(c: &mut Criterion) {
     });
 }
 
-criterion_group!(
-    benches, bench_from_str, bench_from_ext, bench_from_ext_light,
-    bench_from_content, bench_is_texture, bench_isnt_texture,
-    bench_large_file_isnt_texture, bench_dataurl, decode_texture
-);
+/// Equivalent to `criterion_group!`, but only registers the benchmarks whose features are enabled.
+fn benches() {
+    let mut c = Criterion::default().configure_from_args();
+    bench_from_str(&mut c);
+    bench_inner_mime(&mut c);
+    #[cfg(feature = "extension")]
+    bench_from_ext(&mut c);
+    #[cfg(feature = "extension-light")]
+    bench_from_ext_light(&mut c);
+    #[cfg(feature = "magic")]
+    bench_from_content(&mut c);
+    #[cfg(feature = "texture")]
+    {
+        bench_is_texture(&mut c);
+        bench_isnt_texture(&mut c);
+        bench_large_file_isnt_texture(&mut c);
+    }
+    #[cfg(all(feature = "dataurl", feature = "std"))]
+    bench_dataurl(&mut c);
+    #[cfg(feature = "dataurl")]
+    {
+        bench_encode_large(&mut c);
+        bench_parse_large(&mut c);
+        decode_texture(&mut c);
+    }
+}
 
 criterion_main!(benches);
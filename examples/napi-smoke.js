@@ -0,0 +1,23 @@
+// Smoke test for the `napi` feature. Build the addon and run it from the repository root:
+//
+//     cargo rustc --lib --features napi --crate-type cdylib
+//     cp target/debug/libmime_more.so target/debug/mime_more.node # `.dylib` on macOS, `mime_more.dll` on Windows
+//     node examples/napi-smoke.js
+"use strict";
+
+const assert = require("node:assert");
+const path = require("node:path");
+
+const addon = process.argv[2] ?? path.join(__dirname, "..", "target", "debug", "mime_more.node");
+const { fromExt, fromPath, fromContent, toDataurl } = require(path.resolve(addon));
+
+assert.strictEqual(fromExt("png"), "image/png");
+assert.strictEqual(fromPath("assets/logo.svg"), "image/svg+xml");
+assert.strictEqual(fromContent(Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00])), "image/png");
+assert.match(toDataurl("tests/fixtures/pixel.png"), /^data:image\/png;base64,iVBORw0KGgo/);
+
+assert.throws(() => fromExt("unknown"), { code: "UNKNOWN_TYPE" });
+assert.throws(() => fromContent(Buffer.alloc(0)), { code: "UNKNOWN_TYPE" });
+assert.throws(() => toDataurl("does/not/exist.png"), { code: "IO_ERROR" });
+
+console.log("napi smoke test passed");
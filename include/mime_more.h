@@ -0,0 +1,37 @@
+#ifndef MIME_MORE_H
+#define MIME_MORE_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// Guesses the MIME type from the extension (without the leading dot).
+//
+// # Safety
+//
+// `ext` must be null or point to a valid NUL-terminated string.
+char *mime_more_from_ext(const char *ext);
+
+// Infers the MIME type from the magic number of `len` bytes at `data`.
+//
+// # Safety
+//
+// `data` must be null or point to at least `len` readable bytes.
+char *mime_more_from_content(const uint8_t *data, size_t len);
+
+// Encodes `len` bytes at `data` as a data URL. When `mime` is null, the MIME type is inferred from the content.
+//
+// # Safety
+//
+// `mime` must be null or point to a valid NUL-terminated string, and `data` must be null or point to at least `len` readable bytes.
+char *mime_more_dataurl_encode(const char *mime, const uint8_t *data, size_t len);
+
+// Releases a string returned by any `mime_more_*` function. Passing null is a no-op.
+//
+// # Safety
+//
+// `s` must be null or a pointer returned by this library that hasn't been freed yet.
+void mime_more_string_free(char *s);
+
+#endif  /* MIME_MORE_H */
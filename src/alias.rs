@@ -0,0 +1,79 @@
+//! The alias table behind `Mime::canonicalize`, which maps legacy and non-standard spellings to the registered (or most common) one, e.g. `image/jpg` to `image/jpeg`.
+
+use phf::{phf_map, Map};
+
+/// Maps an alias essence to its canonical essence. No canonical essence is itself an alias, so canonicalization is idempotent.
+pub static ALIASES: Map<&'static str, &'static str> = phf_map! {
+    // Text
+    "text/xml" => "application/xml",
+    "text/json" => "application/json",
+    "text/x-json" => "application/json",
+    "application/x-javascript" => "text/javascript",
+    "application/javascript" => "text/javascript",
+    "application/ecmascript" => "text/javascript",
+    "text/ecmascript" => "text/javascript",
+    "text/x-javascript" => "text/javascript",
+    "text/x-markdown" => "text/markdown",
+    "text/x-yaml" => "application/yaml",
+    "text/yaml" => "application/yaml",
+    "application/x-yaml" => "application/yaml",
+    "text/x-toml" => "application/toml",
+    "text/x-csv" => "text/csv",
+    "application/csv" => "text/csv",
+    // Images
+    "image/jpg" => "image/jpeg",
+    "image/pjpeg" => "image/jpeg",
+    "image/x-png" => "image/png",
+    "image/x-ms-bmp" => "image/bmp",
+    "image/x-bmp" => "image/bmp",
+    // Fonts
+    "font/x-woff" => "font/woff",
+    "application/font-woff" => "font/woff",
+    "application/font-woff2" => "font/woff2",
+    "application/x-font-ttf" => "font/ttf",
+    "application/x-font-truetype" => "font/ttf",
+    "application/x-font-otf" => "font/otf",
+    "application/x-font-opentype" => "font/otf",
+    // Audios
+    "audio/x-wav" => "audio/wav",
+    "audio/wave" => "audio/wav",
+    "audio/vnd.wave" => "audio/wav",
+    "audio/mp3" => "audio/mpeg",
+    "audio/x-mp3" => "audio/mpeg",
+    "audio/mpeg3" => "audio/mpeg",
+    "audio/x-mpeg" => "audio/mpeg",
+    "audio/x-flac" => "audio/flac",
+    "audio/x-aac" => "audio/aac",
+    "audio/mid" => "audio/midi",
+    "audio/x-midi" => "audio/midi",
+    // Other
+    "application/x-pdf" => "application/pdf",
+    "application/x-gzip" => "application/gzip",
+    "application/x-zip-compressed" => "application/zip",
+};
+
+/// Returns the canonical essence for an alias, or `None` if `essence` (lowercase, without parameters) is not an alias.
+pub fn canonical_essence(essence: &str) -> Option<&'static str> {
+    ALIASES.get(essence).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mime;
+    use core::str::FromStr;
+
+    #[test]
+    fn table_is_consistent() {
+        for (alias, canonical) in ALIASES.entries() {
+            assert_eq!(Mime::from_str(alias).unwrap().essence(), *alias);
+            assert_eq!(Mime::from_str(canonical).unwrap().essence(), *canonical);
+            assert_ne!(alias, canonical);
+            assert_eq!(
+                canonical_essence(canonical),
+                None,
+                "{canonical} is an alias"
+            );
+        }
+    }
+}
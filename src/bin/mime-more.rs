@@ -0,0 +1,147 @@
+//! The `mime-more` command line tool, built with the `cli` feature.
+//!
+//! ```text
+//! mime-more guess <path>                   Guesses the MIME type of a file, and which source decided it.
+//! mime-more sniff <path | ->               Infers the MIME type from the content only.
+//! mime-more dataurl encode <path>          Prints the file as a data URL.
+//! mime-more dataurl decode <url | path>    Writes the decoded data URL to stdout.
+//! ```
+//!
+//! Exit codes: `0` on success, `1` on IO errors, `2` on usage errors, `3` when the type is unknown, and `4` when a data URL is invalid.
+
+use mime_more::{texture, Dataurl, Mime, MimeError};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+const USAGE: &str = "Usage:
+    mime-more guess <path>
+    mime-more sniff <path | ->
+    mime-more dataurl encode <path>
+    mime-more dataurl decode <url | path>";
+
+/// Why the command failed, which decides the exit code.
+enum Failure {
+    Io(std::io::Error),
+    Usage,
+    Unknown,
+    InvalidDataurl(MimeError),
+}
+
+impl Failure {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Self::Io(_) => ExitCode::from(1),
+            Self::Usage => ExitCode::from(2),
+            Self::Unknown => ExitCode::from(3),
+            Self::InvalidDataurl(_) => ExitCode::from(4),
+        }
+    }
+}
+
+impl From<std::io::Error> for Failure {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+fn read_input(input: &str) -> std::io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    if input == "-" {
+        std::io::stdin().read_to_end(&mut data)?;
+    } else {
+        std::fs::File::open(input)?.read_to_end(&mut data)?;
+    }
+    Ok(data)
+}
+
+/// Infers the MIME type from the content, naming the source that decided it.
+fn sniff(data: &[u8]) -> Option<(Mime, &'static str)> {
+    if let Ok(inferred) = Mime::from_content(data) {
+        Some((inferred, "magic"))
+    } else if texture::is_texture_std(data) {
+        Some((Mime::TEXT_PLAIN, "texture"))
+    } else {
+        None
+    }
+}
+
+/// Follows the same order as `from_path_and_content`, naming the source that decided it.
+fn guess(path: &Path, data: &[u8]) -> Option<(Mime, &'static str)> {
+    #[cfg(feature = "extension")]
+    if let Ok(guessed) = Mime::from_path(path) {
+        return Some((guessed, "extension"));
+    }
+
+    if let Ok(guessed_light) = Mime::from_path_light(path) {
+        return Some((guessed_light, "extension-light"));
+    }
+
+    sniff(data)
+}
+
+fn report(out: &mut impl Write, guessed: Option<(Mime, &'static str)>) -> Result<(), Failure> {
+    match guessed {
+        Some((mime, source)) => {
+            writeln!(out, "{mime} ({source})")?;
+            Ok(())
+        }
+        None => {
+            writeln!(out, "{} (fallback)", mime::APPLICATION_OCTET_STREAM)?;
+            Err(Failure::Unknown)
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Failure> {
+    let mut stdout = std::io::stdout().lock();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["guess", path] => {
+            let data = read_input(path)?;
+            report(&mut stdout, guess(Path::new(path), &data))
+        }
+        ["sniff", input] => {
+            let data = read_input(input)?;
+            report(&mut stdout, sniff(&data))
+        }
+        ["dataurl", "encode", path] => {
+            let data = read_input(path)?;
+            let mime = mime_more::from_path_and_content(Path::new(path), &data)
+                .map_err(|_| Failure::Unknown)?;
+            let dataurl = Dataurl::new(mime, data).map_err(|_| Failure::Unknown)?;
+            writeln!(stdout, "{}", dataurl)?;
+            Ok(())
+        }
+        ["dataurl", "decode", input] => {
+            let url = if input.starts_with("data:") {
+                input.to_string()
+            } else {
+                String::from_utf8_lossy(&read_input(input)?)
+                    .trim()
+                    .to_string()
+            };
+            let dataurl = Dataurl::from_str(&url).map_err(Failure::InvalidDataurl)?;
+            stdout.write_all(&dataurl.data)?;
+            Ok(())
+        }
+        _ => Err(Failure::Usage),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            match &failure {
+                Failure::Io(err) => eprintln!("mime-more: {err}"),
+                Failure::Usage => eprintln!("{USAGE}"),
+                Failure::Unknown => eprintln!("mime-more: unknown type"),
+                Failure::InvalidDataurl(err) => eprintln!("mime-more: {err}"),
+            }
+            failure.exit_code()
+        }
+    }
+}
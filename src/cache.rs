@@ -0,0 +1,85 @@
+//! Parsed MIME types for hot static strings, so that the light table and `Mime::cached` clone instead of re-parsing.
+
+use crate::MimeError;
+use core::str::FromStr;
+use mime::Mime as MimeType;
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::{LazyLock, PoisonError, RwLock};
+
+/// Every type of the light and compound extension tables, and the constants of `Mime`. The constants come from `mime` itself, so their clones don't even allocate. It never changes, so it is read without a lock.
+#[cfg(feature = "std")]
+static SEEDED: LazyLock<HashMap<&'static str, MimeType>> = LazyLock::new(|| {
+    #[cfg(feature = "extension-light")]
+    let tables = crate::light_guess::MIME_TYPES.values();
+    #[cfg(not(feature = "extension-light"))]
+    let tables = core::iter::empty::<&&'static str>();
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    let tables = tables.chain(crate::compound::COMPOUND_EXTENSIONS.values());
+
+    let mut seeded: HashMap<_, _> = tables
+        .filter_map(|&essence| Some((essence, MimeType::from_str(essence).ok()?)))
+        .collect();
+    seeded.extend(crate::MIME_CONSTANTS.iter().cloned());
+    seeded
+});
+
+/// The other strings, added on first use.
+#[cfg(feature = "std")]
+static ADDED: LazyLock<RwLock<HashMap<&'static str, MimeType>>> = LazyLock::new(Default::default);
+
+/// Parses a static string once and clones the result afterwards. Without `std`, it parses every time.
+pub fn parse(s: &'static str) -> Result<MimeType, MimeError> {
+    #[cfg(feature = "std")]
+    {
+        if let Some(mime) = SEEDED.get(s) {
+            return Ok(mime.clone());
+        }
+        if let Some(mime) = ADDED.read().unwrap_or_else(PoisonError::into_inner).get(s) {
+            return Ok(mime.clone());
+        }
+        let mime = MimeType::from_str(s)?;
+        ADDED
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(s, mime.clone());
+        Ok(mime)
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        Ok(MimeType::from_str(s)?)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "extension-light")]
+    #[test]
+    fn seeded() {
+        for essence in crate::light_guess::MIME_TYPES.values() {
+            assert_eq!(SEEDED[essence], MimeType::from_str(essence).unwrap());
+        }
+        assert_eq!(SEEDED["application/json"], mime::APPLICATION_JSON);
+    }
+
+    #[test]
+    fn added_on_first_use() {
+        let essence = "application/vnd.mime-more.cache-test";
+        assert!(!ADDED.read().unwrap().contains_key(essence));
+        assert_eq!(parse(essence).unwrap(), essence);
+        assert!(ADDED.read().unwrap().contains_key(essence));
+        assert_eq!(parse(essence).unwrap(), essence);
+    }
+
+    #[test]
+    fn invalid_not_cached() {
+        let invalid = "not a mime";
+        assert!(matches!(parse(invalid), Err(MimeError::Parse(_))));
+        assert!(!ADDED.read().unwrap().contains_key(invalid));
+    }
+}
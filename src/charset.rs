@@ -0,0 +1,114 @@
+//! Normalization of `charset` parameters, which are spelled many ways in the wild: `utf-8`, `UTF8`, `"utf-8"` or `utf_8`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::{Charset, Mime};
+//! use std::str::FromStr;
+//!
+//! let json = Mime::from_str("application/json; charset=\"UTF_8\"").unwrap();
+//! assert_eq!(json.normalized_charset(), Some(Charset::Utf8));
+//! assert!(json.charset_is_utf8());
+//! assert_eq!(Charset::from_label("ISO-8859-1"), Charset::Latin1);
+//! ```
+
+use alloc::string::String;
+use core::fmt;
+
+/// A charset label, normalized to the encoding it names. Labels are matched case-insensitively, ignoring surrounding quotes and whitespace as well as the `-`, `_`, `.` and space separators.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Charset {
+    Utf8,
+    /// UTF-16 of unspecified byte order, which a byte order mark decides.
+    Utf16,
+    Utf16Le,
+    Utf16Be,
+    /// ISO-8859-1, which browsers read as windows-1252.
+    Latin1,
+    UsAscii,
+    /// Any other charset, as its lowercase label without quotes, e.g. `shift_jis`.
+    Other(String),
+}
+
+impl Charset {
+    /// Normalizes a charset label, e.g. the value of a `charset` parameter.
+    pub fn from_label(label: &str) -> Self {
+        let label = label.trim().trim_matches('"').trim();
+        let mut squashed = String::with_capacity(label.len());
+        squashed.extend(
+            label
+                .chars()
+                .filter(|c| !matches!(c, '-' | '_' | '.' | ' '))
+                .map(|c| c.to_ascii_lowercase()),
+        );
+        match squashed.as_str() {
+            "utf8" | "unicode11utf8" => Self::Utf8,
+            "utf16" => Self::Utf16,
+            "utf16le" => Self::Utf16Le,
+            "utf16be" => Self::Utf16Be,
+            "iso88591" | "isolatin1" | "latin1" | "l1" | "cp819" | "ibm819" => Self::Latin1,
+            "usascii" | "ascii" | "iso646us" | "ansix341968" | "cp367" | "ibm367" => Self::UsAscii,
+            _ => Self::Other(label.to_ascii_lowercase()),
+        }
+    }
+
+    /// Returns the canonical label, e.g. `utf-8` or `iso-8859-1`, or the label of an `Other` charset.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Utf8 => "utf-8",
+            Self::Utf16 => "utf-16",
+            Self::Utf16Le => "utf-16le",
+            Self::Utf16Be => "utf-16be",
+            Self::Latin1 => "iso-8859-1",
+            Self::UsAscii => "us-ascii",
+            Self::Other(label) => label,
+        }
+    }
+
+    /// Returns `true` for UTF-8.
+    pub fn is_utf8(&self) -> bool {
+        *self == Self::Utf8
+    }
+}
+
+impl fmt::Display for Charset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn labels() {
+        for (label, expected) in [
+            ("utf-8", Charset::Utf8),
+            ("UTF8", Charset::Utf8),
+            ("\"utf-8\"", Charset::Utf8),
+            (" Utf_8 ", Charset::Utf8),
+            ("UTF-16", Charset::Utf16),
+            ("utf-16LE", Charset::Utf16Le),
+            ("UTF_16BE", Charset::Utf16Be),
+            ("ISO-8859-1", Charset::Latin1),
+            ("latin1", Charset::Latin1),
+            ("US-ASCII", Charset::UsAscii),
+            ("ANSI_X3.4-1968", Charset::UsAscii),
+            ("Shift_JIS", Charset::Other("shift_jis".to_string())),
+            ("utf-7", Charset::Other("utf-7".to_string())),
+        ] {
+            assert_eq!(Charset::from_label(label), expected, "{label}");
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Charset::from_label("UTF8").to_string(), "utf-8");
+        assert_eq!(Charset::from_label("L1").to_string(), "iso-8859-1");
+        assert_eq!(Charset::from_label("\"EUC-JP\"").to_string(), "euc-jp");
+        assert!(Charset::from_label("utf_8").is_utf8());
+        assert!(!Charset::UsAscii.is_utf8());
+    }
+}
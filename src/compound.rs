@@ -0,0 +1,62 @@
+#![cfg(any(feature = "extension", feature = "extension-light"))]
+//! Extensions made of two dot-separated segments, which `Path::extension` would cut down to the last one.
+
+use phf::{phf_map, Map};
+
+pub static COMPOUND_EXTENSIONS: Map<&'static str, &'static str> = phf_map! {
+    // Tarballs keep the type of their outer compression.
+    "tar.gz" => "application/gzip",
+    "tar.bz2" => "application/x-bzip2",
+    "tar.xz" => "application/x-xz",
+    "tar.zst" => "application/zstd",
+    // TypeScript declarations, which the single `ts` would take for MPEG transport streams.
+    "d.ts" => "text/typescript",
+    "d.mts" => "text/typescript",
+    "d.cts" => "text/typescript",
+    // Scripts
+    "min.js" => "text/javascript",
+    "map.js" => "text/javascript",
+    "user.js" => "text/javascript",
+};
+
+/// Looks up a compound extension such as `tar.gz`, case-insensitively.
+pub fn mime_type_by_extension(ext: &str) -> Option<&'static str> {
+    match COMPOUND_EXTENSIONS.get(ext) {
+        Some(mime) => Some(*mime),
+        None if ext.bytes().any(|byte| byte.is_ascii_uppercase()) => COMPOUND_EXTENSIONS
+            .get(ext.to_ascii_lowercase().as_str())
+            .copied(),
+        None => None,
+    }
+}
+
+/// Returns the last two segments of the file name if they form a known compound extension. The name must have a stem of its own, so `tar.gz` alone is a file named `tar`.
+pub fn split(file_name: &str) -> Option<&str> {
+    let (rest, _) = file_name.rsplit_once('.')?;
+    let (stem, _) = rest.rsplit_once('.')?;
+    if stem.is_empty() {
+        return None;
+    }
+    let ext = &file_name[stem.len() + 1..];
+    mime_type_by_extension(ext).map(|_| ext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_known() {
+        assert_eq!(split("archive.tar.gz"), Some("tar.gz"));
+        assert_eq!(split("index.D.TS"), Some("D.TS"));
+        assert_eq!(split("jquery.3.7.min.js"), Some("min.js"));
+    }
+
+    #[test]
+    fn split_unknown() {
+        assert_eq!(split("photo.final.jpg"), None);
+        assert_eq!(split("tar.gz"), None);
+        assert_eq!(split(".d.ts"), None);
+        assert_eq!(split("plain"), None);
+    }
+}
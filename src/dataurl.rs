@@ -16,13 +16,23 @@
 //! ```
 
 use crate::magic::mime_type_by_content;
-use crate::utils::get_extension;
-use crate::{from_ext_and_content, Mime};
+use crate::{Mime, MimeError};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
 use base64::engine::{general_purpose, Engine as _};
-use std::io::Read;
-use std::path::Path;
-use std::str::FromStr;
-use urlencoding::encode;
+use core::str::FromStr;
+
+/// The media type that an empty one stands for, as per [RFC 2397](https://www.rfc-editor.org/rfc/rfc2397).
+const IMPLICIT_MIME: &str = "text/plain;charset=US-ASCII";
+
+mod encoding;
+mod extract;
+mod percent;
+#[cfg(feature = "encoding")]
+pub use encoding::{guess_charset, Encoding};
+pub use extract::{extract, DataurlContext, ExtractedDataurl};
+pub use percent::PercentEncodeSet;
 
 /// The `DataurlCharset` enum represents the charset of a `dataurl` scheme.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Copy)]
@@ -41,13 +51,23 @@ impl DataurlCharset {
 }
 
 impl FromStr for DataurlCharset {
-    type Err = anyhow::Error;
+    type Err = MimeError;
 
+    /// Parses the marker before the comma of a data URL: `base64`, or `charset=<label>` or a bare label naming UTF-8, e.g. `charset="UTF-8"` or `utf8`. Both are matched case-insensitively.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "utf-8" => Ok(Self::Utf8),
-            "base64" => Ok(Self::Base64),
-            _ => Err(anyhow::anyhow!("Invalid charset")),
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("base64") {
+            return Ok(Self::Base64);
+        }
+        let label = match s.split_once('=') {
+            Some((key, value)) if key.trim().eq_ignore_ascii_case("charset") => value,
+            Some(_) => return Err(MimeError::InvalidDataurl("charset")),
+            None => s,
+        };
+        if crate::Charset::from_label(label).is_utf8() {
+            Ok(Self::Utf8)
+        } else {
+            Err(MimeError::InvalidDataurl("charset"))
         }
     }
 }
@@ -62,8 +82,8 @@ impl From<bool> for DataurlCharset {
     }
 }
 
-impl std::fmt::Display for DataurlCharset {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for DataurlCharset {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Utf8 => write!(f, "utf-8"),
             Self::Base64 => write!(f, "base64"),
@@ -71,30 +91,120 @@ impl std::fmt::Display for DataurlCharset {
     }
 }
 
+/// The payload of a `Dataurl`. It dereferences to `[u8]`, and with the `bytes` feature it can also hold a `bytes::Bytes`, so that async stacks share their buffers instead of copying them.
+#[derive(Debug, Clone)]
+pub enum DataurlData {
+    Vec(Vec<u8>),
+    #[cfg(feature = "bytes")]
+    Bytes(bytes::Bytes),
+}
+
+impl DataurlData {
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Vec(data) => data,
+            #[cfg(feature = "bytes")]
+            Self::Bytes(data) => data,
+        }
+    }
+
+    /// Converts the payload into a `Vec<u8>`. It only copies when the underlying `Bytes` buffer is shared.
+    pub fn into_vec(self) -> Vec<u8> {
+        match self {
+            Self::Vec(data) => data,
+            #[cfg(feature = "bytes")]
+            Self::Bytes(data) => data.into(),
+        }
+    }
+}
+
+impl core::ops::Deref for DataurlData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for DataurlData {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl From<Vec<u8>> for DataurlData {
+    fn from(data: Vec<u8>) -> Self {
+        Self::Vec(data)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for DataurlData {
+    fn from(data: bytes::Bytes) -> Self {
+        Self::Bytes(data)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<DataurlData> for bytes::Bytes {
+    fn from(data: DataurlData) -> Self {
+        match data {
+            DataurlData::Vec(data) => data.into(),
+            DataurlData::Bytes(data) => data,
+        }
+    }
+}
+
+impl PartialEq for DataurlData {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for DataurlData {}
+
+impl PartialEq<[u8]> for DataurlData {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl PartialEq<Vec<u8>> for DataurlData {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl PartialOrd for DataurlData {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DataurlData {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
 /// The `Dataurl` struct represents a `dataurl` scheme. It contains the MIME type and the data.
-#[cfg_attr(feature, feature = "dataurl")]
 #[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
 pub struct Dataurl {
     pub mime: Mime,
-    pub data: Vec<u8>,
+    pub data: DataurlData,
     pub charset: DataurlCharset,
 }
 
 impl Dataurl {
-    pub fn new(mime: Mime, data: Vec<u8>) -> Self {
-        Self {
-            charset: mime.clone().is_texture(&data).into(),
-            mime,
-            data,
+    /// Builds a data URL, choosing the percent-encoded form for textures that are valid UTF-8 and base64 otherwise. A type declaring another charset than UTF-8, e.g. `text/plain; charset=iso-8859-1`, is always base64-encoded, as the percent-encoded form declares `charset=utf-8`. `mime` may be a `Mime` or a `mime::Mime`, but not a media range such as `image/*`.
+    pub fn new(mime: impl Into<Mime>, data: impl Into<DataurlData>) -> Result<Self, MimeError> {
+        let mime = mime.into();
+        if mime.is_wildcard() {
+            return Err(MimeError::Wildcard(mime.to_essence_string()));
         }
-    }
-
-    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
-        let mut file = std::fs::File::open(path).unwrap();
-        let mut data = Vec::new();
-        file.read_to_end(&mut data)?;
-        let mime = from_ext_and_content(get_extension(path).unwrap(), &data)?;
-        let charset = mime.clone().is_texture(&data).into();
+        let data = data.into();
+        let charset = charset_for(&mime, &data);
+        debug!(mime = %mime, charset = ?charset, len = data.len(), "dataurl charset chosen");
         Ok(Self {
             charset,
             mime,
@@ -102,64 +212,801 @@ impl Dataurl {
         })
     }
 
-    pub fn from_data(data: Vec<u8>) -> anyhow::Result<Self> {
-        let mime = mime_type_by_content(&data)?;
-        let mime = Mime::new(mime);
-        let charset = mime.clone().is_texture(&data).into();
+    /// Builds a data URL with the given encoding rather than the one `new` would pick, e.g. to put back the parts of `into_parts`. `Utf8` requires the data to be UTF-8, and fails with `MimeError::InvalidDataurl` otherwise.
+    pub fn from_parts(
+        mime: impl Into<Mime>,
+        data: impl Into<DataurlData>,
+        charset: DataurlCharset,
+    ) -> Result<Self, MimeError> {
+        let mime = mime.into();
+        if mime.is_wildcard() {
+            return Err(MimeError::Wildcard(mime.to_essence_string()));
+        }
+        let data = data.into();
+        if charset == DataurlCharset::Utf8 && core::str::from_utf8(&data).is_err() {
+            return Err(MimeError::InvalidDataurl("percent-encoded payload"));
+        }
         Ok(Self {
+            mime,
+            data,
             charset,
+        })
+    }
+
+    /// Splits the data URL into its MIME type, data and encoding without cloning them. The data is only copied when it is a shared `Bytes` buffer.
+    pub fn into_parts(self) -> (Mime, Vec<u8>, DataurlCharset) {
+        (self.mime, self.data.into_vec(), self.charset)
+    }
+
+    /// The same as `new`, but borrows the data, e.g. a memory-mapped asset, instead of taking a `Vec`. The result can be displayed or written like a `Dataurl`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mime_more::{dataurl::Dataurl, Mime};
+    ///
+    /// let asset: &[u8] = b"body { margin: 0 }";
+    /// let dataurl = Dataurl::borrowed(Mime::TEXT_CSS, asset).unwrap();
+    /// assert_eq!(dataurl.to_string(), "data:text/css;charset=utf-8,body { margin: 0 }");
+    /// ```
+    pub fn borrowed(mime: impl Into<Mime>, data: &[u8]) -> Result<DataurlSlice<'_>, MimeError> {
+        let mime = mime.into();
+        if mime.is_wildcard() {
+            return Err(MimeError::Wildcard(mime.to_essence_string()));
+        }
+        let charset = charset_for(&mime, data);
+        Ok(DataurlSlice {
             mime,
             data,
+            charset,
         })
     }
 
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(path = %path.display()))
+    )]
+    pub fn from_path(path: &std::path::Path) -> Result<Self, MimeError> {
+        use std::io::Read;
+
+        let mut file = open(path)?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        Self::from_path_and_data(path, data)
+    }
+
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[cfg_attr(
+        all(
+            feature = "std",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ),
+        doc = "Writes the decoded data to a file, e.g. to extract the assets inlined in an HTML document. An existing file is replaced only if `overwrite` is `true`, and is otherwise an `io::ErrorKind::AlreadyExists` error naming the path."
+    )]
+    pub fn to_file(&self, path: &std::path::Path, overwrite: bool) -> Result<(), MimeError> {
+        use std::io::Write;
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(overwrite)
+            .truncate(overwrite)
+            .create_new(!overwrite)
+            .open(path)
+            .map_err(|err| io_error(path, err))?;
+        file.write_all(&self.data)
+            .map_err(|err| io_error(path, err))
+    }
+
+    /// Returns a file name for the data: the stem followed by the preferred extension of the MIME type, e.g. `logo.png` for `image/png`, or the bare stem for a type without a known extension.
+    pub fn suggested_filename(&self, stem: &str) -> alloc::string::String {
+        match self.mime.preferred_extension() {
+            Some(ext) => alloc::format!("{stem}.{ext}"),
+            None => stem.into(),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[cfg_attr(
+        feature = "std",
+        doc = "Reads a data URL from any reader, e.g. a zip entry or the output of a process, guessing the type like `from_path` from the extension hint and the content. With a `limit`, reading more than `limit` bytes fails with an `io::ErrorKind::FileTooLarge` error instead of buffering unbounded input."
+    )]
+    pub fn from_reader(
+        reader: &mut impl std::io::Read,
+        ext_hint: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Self, MimeError> {
+        use crate::from_ext_and_content;
+        use std::io::Read;
+
+        let mut data = Vec::new();
+        match limit {
+            Some(limit) => {
+                reader
+                    .take((limit as u64).saturating_add(1))
+                    .read_to_end(&mut data)?;
+                if data.len() > limit {
+                    return Err(MimeError::Io(std::io::Error::new(
+                        std::io::ErrorKind::FileTooLarge,
+                        std::format!("data URL input exceeds {limit} bytes"),
+                    )));
+                }
+            }
+            None => {
+                reader.read_to_end(&mut data)?;
+            }
+        }
+        let mime = from_ext_and_content(ext_hint.unwrap_or_default(), &data)?;
+        Self::new(mime, data)
+    }
+
+    /// Like `from_path`, but learns the type with `Mime::sniff_path` from the first `max_bytes` before the rest of the file is read into the same buffer, so the content is read only once. The content takes priority over the extension.
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(path = %path.display()))
+    )]
+    pub fn from_path_sniffed(path: &std::path::Path, max_bytes: usize) -> Result<Self, MimeError> {
+        use std::io::Read;
+
+        let mut file = open(path)?;
+        let mut data = Vec::new();
+        (&mut file).take(max_bytes as u64).read_to_end(&mut data)?;
+        let mime = Mime::sniff_prefix(path, &data)?;
+        file.read_to_end(&mut data)?;
+        Self::new(mime, data)
+    }
+
+    /// Guesses the type of the data read from the path, shared by `from_path` and `from_path_async`. A path without an extension is guessed from the content alone.
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub(crate) fn from_path_and_data(
+        path: &std::path::Path,
+        data: Vec<u8>,
+    ) -> Result<Self, MimeError> {
+        use crate::from_ext_and_content;
+        use crate::utils::get_extension;
+
+        let mime = from_ext_and_content(get_extension(path).unwrap_or_default(), &data)?;
+        Self::new(mime, data)
+    }
+
+    #[cfg(all(
+        feature = "camino",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[cfg_attr(
+        all(
+            feature = "camino",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ),
+        doc = "The same as `from_path`, but takes a `camino::Utf8Path`."
+    )]
+    pub fn from_utf8_path(path: &camino::Utf8Path) -> Result<Self, MimeError> {
+        Self::from_path(path.as_std_path())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(len = data.len()))
+    )]
+    pub fn from_data(data: Vec<u8>) -> Result<Self, MimeError> {
+        Self::new(mime_type_by_content(&data)?, data)
+    }
+
+    #[cfg(feature = "bytes")]
+    #[cfg_attr(
+        feature = "bytes",
+        doc = "The same as `from_data`, but keeps the `bytes::Bytes` buffer without copying it."
+    )]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(len = data.len()))
+    )]
+    pub fn from_bytes(data: bytes::Bytes) -> Result<Self, MimeError> {
+        Self::new(mime_type_by_content(&data)?, data)
+    }
+
+    #[cfg(feature = "bytes")]
+    #[cfg_attr(
+        feature = "bytes",
+        doc = "Returns the payload as `bytes::Bytes`. It shares the underlying buffer when the data was constructed from `Bytes`, and copies otherwise."
+    )]
+    pub fn data_bytes(&self) -> bytes::Bytes {
+        match &self.data {
+            DataurlData::Vec(data) => bytes::Bytes::copy_from_slice(data),
+            DataurlData::Bytes(data) => data.clone(),
+        }
+    }
+
+    /// Replaces a mis-guessed MIME type, e.g. an SVG without an extension detected as `text/xml`, keeping the data. The encoding is chosen again as in `new`, so any `with_charset` override is dropped. Media ranges such as `image/*` are rejected, as in `new`.
+    pub fn with_mime(mut self, mime: impl Into<Mime>) -> Result<Self, MimeError> {
+        self.set_mime(mime)?;
+        Ok(self)
+    }
+
+    /// The same as `with_mime`, but in place. On error the data URL is left unchanged.
+    pub fn set_mime(&mut self, mime: impl Into<Mime>) -> Result<(), MimeError> {
+        let mime = mime.into();
+        if mime.is_wildcard() {
+            return Err(MimeError::Wildcard(mime.to_essence_string()));
+        }
+        self.charset = charset_for(&mime, &self.data);
+        self.mime = mime;
+        Ok(())
+    }
+
+    /// Overrides the encoding `new` picked, e.g. `DataurlCharset::Base64` for an SVG full of `%`, `#` and non-ASCII characters, whose percent-encoded form would be longer. `Display` still falls back to base64 for data that isn't UTF-8.
+    pub fn with_charset(mut self, charset: DataurlCharset) -> Self {
+        self.charset = charset;
+        self
+    }
+
     pub fn is_texture(&self) -> bool {
         self.charset.to_texture_bool()
     }
+
+    /// Returns `true` if the media type is `text/plain;charset=US-ASCII`, which an empty media type stands for, e.g. in `data:,Hello`. As the two are equivalent, an explicit `text/plain;charset=US-ASCII` counts too.
+    pub fn mime_was_implicit(&self) -> bool {
+        is_implicit(&self.mime)
+    }
+
+    /// The same as `to_string`, but leaves out the media type when `mime_was_implicit`, e.g. `data:,Hello` or `data:;base64,AAEC`. Non-ASCII text keeps its `charset=utf-8` marker.
+    pub fn to_minimal_string(&self) -> alloc::string::String {
+        use alloc::string::ToString;
+        Formatted {
+            dataurl: self,
+            with_mime: !self.mime_was_implicit(),
+            charset: self.charset,
+            set: PercentEncodeSet::default(),
+        }
+        .to_string()
+    }
+
+    /// The same as `to_string`, but percent-encodes text with the given set rather than `PercentEncodeSet::Minimal`, e.g. `Strict` for tools that expect only unreserved characters.
+    pub fn to_string_with(&self, set: PercentEncodeSet) -> alloc::string::String {
+        use alloc::string::ToString;
+        Formatted {
+            dataurl: self,
+            with_mime: true,
+            charset: self.charset,
+            set,
+        }
+        .to_string()
+    }
+
+    /// Writes an SVG image in the text form that inlines best, e.g. in CSS `url("...")`: `"` becomes `'`, and only `<`, `>`, `#`, `%`, `{`, `}`, control characters and non-ASCII characters are escaped. The `;charset=utf-8` marker is left out, as SVG defaults to UTF-8. A document that already has a `'` keeps its `"`, escaped, since swapping them could break its markup; otherwise it parses back with its `"` turned into `'`, which XML reads alike.
+    ///
+    /// Fails with `MimeError::InvalidDataurl` if the type isn't `image/svg+xml` or the data isn't UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mime_more::{dataurl::Dataurl, Mime};
+    ///
+    /// let svg = r##"<svg xmlns="http://www.w3.org/2000/svg"><circle r="6" fill="#e43"/></svg>"##;
+    /// let dataurl = Dataurl::new(Mime::IMAGE_SVG, svg.as_bytes().to_vec()).unwrap();
+    /// assert_eq!(
+    ///     dataurl.to_svg_string().unwrap(),
+    ///     "data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg'%3E%3Ccircle r='6' fill='%23e43'/%3E%3C/svg%3E"
+    /// );
+    /// ```
+    pub fn to_svg_string(&self) -> Result<alloc::string::String, MimeError> {
+        use core::fmt::Write;
+        if self.mime.essence() != "image/svg+xml" {
+            return Err(MimeError::InvalidDataurl("SVG payload"));
+        }
+        let svg = core::str::from_utf8(&self.data)
+            .map_err(|_| MimeError::InvalidDataurl("SVG payload"))?;
+        let mut out = alloc::string::String::new();
+        let _ = write!(out, "data:{},", self.mime);
+        let _ = percent::encode_svg(&mut out, svg);
+        Ok(out)
+    }
+
+    /// Returns the length of `to_string()` without building it, e.g. to check an inlining limit. Like `Display`, it counts data that isn't UTF-8 as base64 whatever `charset` says.
+    pub fn encoded_len(&self) -> usize {
+        encoded_len(&self.mime, self.written_charset(), &self.data)
+    }
+
+    fn written_charset(&self) -> DataurlCharset {
+        written_charset(self.charset, &self.data)
+    }
+
+    /// Returns the same string as `Display`, but allocates it once with the capacity of `encoded_len`, and base64-encodes straight into it.
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> alloc::string::String {
+        use core::fmt::Write;
+        let mut out = alloc::string::String::with_capacity(self.encoded_len());
+        if self.written_charset() == DataurlCharset::Base64 {
+            let _ = write!(out, "data:{};base64,", self.mime);
+            general_purpose::STANDARD.encode_string(&self.data, &mut out);
+        } else {
+            let _ = self.fmt_to(&mut out);
+        }
+        out
+    }
+
+    /// Returns the shorter of the percent-encoded and the base64 forms, with the encoding it picked, whatever `charset` says. Both lengths, markers included, are computed without encoding. Text that percent-encodes to the same length is kept readable, and data that isn't UTF-8 is always base64.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mime_more::{dataurl::{Dataurl, DataurlCharset}, Mime};
+    ///
+    /// let slug = b"the_quick_brown_fox_jumps_over_the_lazy_dog".to_vec();
+    /// let slug = Dataurl::new(Mime::TEXT_PLAIN, slug).unwrap();
+    /// let (smallest, charset) = slug.to_string_smallest();
+    /// assert_eq!(charset, DataurlCharset::Utf8);
+    /// assert!(smallest.ends_with(",the_quick_brown_fox_jumps_over_the_lazy_dog"));
+    ///
+    /// let cjk = Dataurl::new(Mime::TEXT_PLAIN, "中文".as_bytes().to_vec()).unwrap();
+    /// let (smallest, charset) = cjk.to_string_smallest();
+    /// assert_eq!(charset, DataurlCharset::Base64);
+    /// assert_eq!(smallest, "data:text/plain;base64,5Lit5paH");
+    /// ```
+    pub fn to_string_smallest(&self) -> (alloc::string::String, DataurlCharset) {
+        use alloc::string::ToString;
+        let charset = if core::str::from_utf8(&self.data).is_ok()
+            && encoded_len(&self.mime, DataurlCharset::Utf8, &self.data)
+                <= encoded_len(&self.mime, DataurlCharset::Base64, &self.data)
+        {
+            DataurlCharset::Utf8
+        } else {
+            DataurlCharset::Base64
+        };
+        let formatted = Formatted {
+            dataurl: self,
+            with_mime: true,
+            charset,
+            set: PercentEncodeSet::default(),
+        };
+        (formatted.to_string(), charset)
+    }
+
+    /// Writes the same string as `to_string` to a `fmt::Write`, encoding the payload in blocks rather than building it whole, so that a large payload is never held twice. `Display` writes through it.
+    pub fn fmt_to<W: core::fmt::Write>(&self, w: &mut W) -> core::fmt::Result {
+        self.write(w, true, self.charset, PercentEncodeSet::default())
+    }
+
+    #[cfg(feature = "std")]
+    #[cfg_attr(
+        feature = "std",
+        doc = "The same as `fmt_to`, but writes to an `io::Write`, e.g. a file or a socket. Wrap an unbuffered writer in a `BufWriter`, as the data URL is written in many small pieces."
+    )]
+    pub fn write_to<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        let mut writer = IoWriter {
+            inner: w,
+            error: None,
+        };
+        self.fmt_to(&mut writer).map_err(|_| {
+            writer
+                .error
+                .take()
+                .unwrap_or_else(|| std::io::Error::other("formatter error"))
+        })
+    }
+
+    /// The same as `to_string`, but fails if `charset` is `Utf8` while the data isn't UTF-8, which `Display` silently writes as base64 instead.
+    pub fn try_to_string(&self) -> Result<alloc::string::String, MimeError> {
+        if self.charset == DataurlCharset::Utf8 && core::str::from_utf8(&self.data).is_err() {
+            return Err(MimeError::InvalidDataurl("percent-encoded payload"));
+        }
+        Ok(self.to_string())
+    }
+
+    /// Parses a data URL like `from_str`, but decodes a percent-encoded payload leniently: a `%` that doesn't start an escape is kept as is, and bytes that aren't UTF-8 are replaced by U+FFFD, unless the media type declares another charset. Malformed base64 is still an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mime_more::dataurl::Dataurl;
+    ///
+    /// let dataurl = Dataurl::from_str_lossy("data:text/plain,caf%E9%20100%").unwrap();
+    /// assert_eq!(&*dataurl.data, "caf\u{FFFD} 100%".as_bytes());
+    /// ```
+    pub fn from_str_lossy(s: &str) -> Result<Self, MimeError> {
+        Self::parse(s, true)
+    }
+
+    fn parse(input: &str, lossy: bool) -> Result<Self, MimeError> {
+        let (mime, charset, data) = split_dataurl(input)?;
+        // Text in another charset, e.g. `charset=iso-8859-1`, is kept as bytes, and written back as base64 like `Dataurl::new` does, since the percent-encoded form declares UTF-8.
+        let legacy = charset == DataurlCharset::Utf8 && declares_legacy_charset(&mime);
+        let data = if charset == DataurlCharset::Utf8 {
+            let decoded = urlencoding::decode_binary(data.as_bytes());
+            if let Some(offset) = invalid_escape(data).filter(|_| !lossy) {
+                let payload_start = input.len() - data.len();
+                return Err(MimeError::InvalidPercentEscape(payload_start + offset));
+            } else if legacy {
+                decoded.into_owned()
+            } else if lossy {
+                String::from_utf8_lossy(&decoded).into_owned().into_bytes()
+            } else if core::str::from_utf8(&decoded).is_err() {
+                return Err(MimeError::InvalidDataurl("percent-encoded payload"));
+            } else {
+                decoded.into_owned()
+            }
+        } else {
+            general_purpose::STANDARD
+                .decode(data.as_bytes())
+                .map_err(|_| MimeError::InvalidDataurl("base64 payload"))?
+        };
+        Ok(Self {
+            charset: if legacy {
+                DataurlCharset::Base64
+            } else {
+                charset
+            },
+            mime,
+            data: data.into(),
+        })
+    }
+
+    /// Writes the data URL, with the media type unless `with_mime` is `false`.
+    fn write(
+        &self,
+        f: &mut impl core::fmt::Write,
+        with_mime: bool,
+        charset: DataurlCharset,
+        set: PercentEncodeSet,
+    ) -> core::fmt::Result {
+        let mime = with_mime.then_some(&self.mime);
+        write_dataurl(f, mime, &self.data, charset, set)
+    }
 }
 
-/// Implement the `Display` trait for `Dataurl`, so that we can directly use `to_string` to convert it to a `String`.
-impl std::fmt::Display for Dataurl {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.is_texture() {
-            let text = encode(&std::str::from_utf8(&self.data).unwrap());
-            write!(f, "data:{};charset=utf-8,{text}", self.mime)
+/// Returns the encoding `Display` writes: `charset`, unless it is `Utf8` for data that isn't UTF-8.
+fn written_charset(charset: DataurlCharset, data: &[u8]) -> DataurlCharset {
+    match charset {
+        DataurlCharset::Utf8 if core::str::from_utf8(data).is_err() => DataurlCharset::Base64,
+        charset => charset,
+    }
+}
+
+/// Picks the encoding `Dataurl::new` documents: percent-encoding for UTF-8 textures without another declared charset, and base64 otherwise.
+fn charset_for(mime: &Mime, data: &[u8]) -> DataurlCharset {
+    ((mime.charset().is_none() || mime.charset_is_utf8())
+        && mime.is_texture(data)
+        && core::str::from_utf8(data).is_ok())
+    .into()
+}
+
+/// Splits a data URL into its media type, the encoding of its payload, and the payload as written. Shared by `Dataurl` and `DataurlRef`, so that they accept the same strings.
+fn split_dataurl(input: &str) -> Result<(Mime, DataurlCharset, &str), MimeError> {
+    let s = match input.get(..5) {
+        // Schemes are case-insensitive, e.g. `DATA:`.
+        Some(scheme) if scheme.eq_ignore_ascii_case("data:") => input[5..].trim_start(),
+        _ => return Err(MimeError::InvalidDataurl("scheme")),
+    };
+    let Some(comma) = crate::http::find_unquoted(s, ',') else {
+        return Err(MimeError::InvalidDataurl(if s.contains(';') {
+            "separator"
         } else {
-            let encoded = general_purpose::STANDARD.encode(&self.data);
-            write!(f, "data:{};base64,{encoded}", self.mime)
+            "media type"
+        }));
+    };
+    let (header, data) = (&s[..comma], &s[comma + 1..]);
+    let without_base64 = strip_base64(header);
+    let (mime, charset) = match (&without_base64, header.rsplit_once(';')) {
+        (Some(mime), _) => (&**mime, DataurlCharset::Base64),
+        (None, Some((mime, marker))) => match DataurlCharset::from_str(marker) {
+            Ok(charset) => (mime, charset),
+            // A token that isn't a parameter can only be a misspelt marker.
+            Err(err) if !marker.contains('=') => return Err(err),
+            // Another parameter, e.g. `charset=iso-8859-1`, belongs to the media type.
+            Err(_) => (header, DataurlCharset::Utf8),
+        },
+        (None, None) => (header, DataurlCharset::Utf8),
+    };
+    let mime = if mime.trim().is_empty() {
+        Mime::from_str(IMPLICIT_MIME)?
+    } else {
+        Mime::from_concrete_str(mime)?
+    };
+    Ok((mime, charset, data))
+}
+
+/// Returns `true` if the media type declares a charset that UTF-8 text can't be read as, e.g. `charset=iso-8859-1`. US-ASCII, which an empty media type implies, is a subset of UTF-8.
+fn declares_legacy_charset(mime: &Mime) -> bool {
+    mime.normalized_charset()
+        .is_some_and(|charset| !charset.is_utf8() && charset != crate::Charset::UsAscii)
+}
+
+/// Returns `true` if the media type is `text/plain;charset=US-ASCII`, which an empty media type stands for.
+fn is_implicit(mime: &Mime) -> bool {
+    mime.essence() == "text/plain"
+        && mime.params().count() == 1
+        && mime.normalized_charset() == Some(crate::Charset::UsAscii)
+}
+
+/// Returns the media type and the `;charset=utf-8` marker written before percent-encoded text. The marker is left out when the media type declares its own charset, except for non-ASCII text under the implicit `charset=US-ASCII`, which the marker replaces.
+fn text_header(mime: &Mime, ascii: bool) -> (&str, &'static str) {
+    match mime.charset() {
+        None => (mime.as_mime().as_ref(), ";charset=utf-8"),
+        Some(_) if !ascii && is_implicit(mime) => ("text/plain", ";charset=utf-8"),
+        Some(_) => (mime.as_mime().as_ref(), ""),
+    }
+}
+
+/// Writes a data URL from its parts, leaving out the media type if `mime` is `None`. Shared by `Dataurl` and `DataurlSlice`.
+fn write_dataurl(
+    f: &mut impl core::fmt::Write,
+    mime: Option<&Mime>,
+    data: &[u8],
+    charset: DataurlCharset,
+    set: PercentEncodeSet,
+) -> core::fmt::Result {
+    f.write_str("data:")?;
+    // A payload that isn't UTF-8 falls back to base64, even if `charset` was set by hand.
+    match core::str::from_utf8(data) {
+        Ok(text) if charset.to_texture_bool() => {
+            match mime {
+                Some(mime) => {
+                    let (mime, marker) = text_header(mime, text.is_ascii());
+                    f.write_str(mime)?;
+                    f.write_str(marker)?;
+                }
+                None if !text.is_ascii() => f.write_str(";charset=utf-8")?,
+                None => {}
+            }
+            f.write_str(",")?;
+            percent::encode(f, text, set)
+        }
+        _ => {
+            if let Some(mime) = mime {
+                write!(f, "{mime}")?;
+            }
+            f.write_str(";base64,")?;
+            // Whole groups of three bytes, so that padding only ends the last block.
+            let mut encoded = [0; BASE64_BLOCK / 3 * 4];
+            for block in data.chunks(BASE64_BLOCK) {
+                let len = general_purpose::STANDARD
+                    .encode_slice(block, &mut encoded)
+                    .map_err(|_| core::fmt::Error)?;
+                f.write_str(core::str::from_utf8(&encoded[..len]).map_err(|_| core::fmt::Error)?)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// The number of bytes `Dataurl::fmt_to` base64-encodes at once, a multiple of three.
+const BASE64_BLOCK: usize = 3072;
+
+/// Adapts an `io::Write` to `fmt::Write` for `Dataurl::write_to`, keeping the IO error that `fmt::Error` can't carry.
+#[cfg(feature = "std")]
+struct IoWriter<W> {
+    inner: W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> core::fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            core::fmt::Error
+        })
+    }
+}
+
+/// Opens a file, naming the path in the error, which `std::io::Error` leaves out.
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+fn open(path: &std::path::Path) -> Result<std::fs::File, MimeError> {
+    std::fs::File::open(path).map_err(|err| io_error(path, err))
+}
+
+/// Wraps an IO error with the path it occurred on. Shared by `open` and `from_path_async`.
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub(crate) fn io_error(path: &std::path::Path, err: std::io::Error) -> MimeError {
+    MimeError::Io(std::io::Error::new(
+        err.kind(),
+        std::format!("{}: {err}", path.display()),
+    ))
+}
+
+/// Displays a `Dataurl` with a given encoding, and with or without its media type.
+struct Formatted<'a> {
+    dataurl: &'a Dataurl,
+    with_mime: bool,
+    charset: DataurlCharset,
+    set: PercentEncodeSet,
+}
+
+impl core::fmt::Display for Formatted<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.dataurl
+            .write(f, self.with_mime, self.charset, self.set)
+    }
+}
+
+/// Computes the length of the string `Display` would produce for the given parts, without encoding the payload.
+pub(crate) fn encoded_len(mime: &Mime, charset: DataurlCharset, data: &[u8]) -> usize {
+    match charset {
+        DataurlCharset::Utf8 => {
+            let (mime, marker) = text_header(mime, data.is_ascii());
+            let payload = percent::encoded_len(data, PercentEncodeSet::default());
+            "data:".len() + mime.len() + marker.len() + ",".len() + payload
+        }
+        DataurlCharset::Base64 => {
+            let mime_len = mime.as_mime().as_ref().len();
+            "data:".len() + mime_len + ";base64,".len() + data.len().div_ceil(3) * 4
         }
     }
 }
 
+/// Implement the `Display` trait for `Dataurl`, so that we can directly use `to_string` to convert it to a `String`.
+impl core::fmt::Display for Dataurl {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_to(f)
+    }
+}
+
+/// Returns the offset of the first `%` that doesn't start an escape of two hex digits, which `urlencoding` would keep as is.
+fn invalid_escape(data: &str) -> Option<usize> {
+    let bytes = data.as_bytes();
+    bytes.iter().enumerate().position(|(i, &byte)| {
+        byte == b'%'
+            && !bytes
+                .get(i + 1..i + 3)
+                .is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+    })
+}
+
+/// Removes the `base64` indicator from the parameters of a data URL's media type, e.g. `text/plain;charset=iso-8859-1;base64` gives `text/plain;charset=iso-8859-1`. [WHATWG](https://fetch.spec.whatwg.org/#data-urls) only allows it as the final parameter, but `;base64;charset=...` is found in the wild too. Returns `None` without the indicator.
+fn strip_base64(header: &str) -> Option<Cow<'_, str>> {
+    let params = crate::http::split_unquoted(header, ';');
+    let index = 1 + params[1..]
+        .iter()
+        .position(|param| param.trim().eq_ignore_ascii_case("base64"))?;
+    if index == params.len() - 1 {
+        return Some(Cow::Borrowed(
+            &header[..header.len() - params[index].len() - 1],
+        ));
+    }
+    let mut rest = String::with_capacity(header.len());
+    for (i, param) in params.iter().enumerate().filter(|&(i, _)| i != index) {
+        if i > 0 {
+            rest.push(';');
+        }
+        rest.push_str(param);
+    }
+    Some(Cow::Owned(rest))
+}
+
 impl FromStr for Dataurl {
-    type Err = anyhow::Error;
+    type Err = MimeError;
 
+    /// Parses the strings `Display` produces: `data:<mime>;charset=utf-8,<percent-encoded>` or `data:<mime>;base64,<base64>`, with the scheme matched case-insensitively, where the MIME type may carry parameters, with commas and semicolons inside quoted values. A `base64` indicator is found among the other parameters, e.g. `charset=iso-8859-1;base64`, which stay on the MIME type. Otherwise the marker is read by `DataurlCharset::from_str`, so `charset="UTF-8"` or a bare `utf-8` are accepted too, and as per [RFC 2397](https://www.rfc-editor.org/rfc/rfc2397) the marker may be left out, e.g. `data:text/plain,Hello`, for a percent-encoded payload. An empty media type means `text/plain;charset=US-ASCII`. A percent-encoded payload whose media type declares another charset, e.g. `charset=iso-8859-1`, is kept as bytes and stored as `Base64`. Invalid base64 and any other percent-encoded payload that isn't UTF-8 are reported as `MimeError::InvalidDataurl`, and a `%` that doesn't start an escape as `MimeError::InvalidPercentEscape`; use `from_str_lossy` or `DataurlRef` to decode leniently.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if !s.starts_with("data:") {
-            return Err(anyhow::anyhow!("Invalid dataurl"));
-        }
-        let s = s[5..].trim_start();
-        let (mime, body) = s
-            .split_once(';')
-            .ok_or_else(|| anyhow::anyhow!("Invalid dataurl"))?;
-        let (charset, data) = body
-            .split_once(',')
-            .ok_or_else(|| anyhow::anyhow!("Invalid dataurl"))?;
-        let mime = Mime::from_str(mime)?;
-        let charset = DataurlCharset::from_str(charset)?;
-        let data = if charset == DataurlCharset::Utf8 {
-            let text = urlencoding::decode(data).unwrap();
-            text.as_bytes().to_vec()
-        } else {
-            let data = general_purpose::STANDARD
-                .decode(data.as_bytes())
-                .unwrap();
-            data
+        Self::parse(s, false)
+    }
+}
+
+/// A data URL to be encoded from borrowed data, made by `Dataurl::borrowed`. It is written exactly as the `Dataurl` holding the same data would be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataurlSlice<'a> {
+    pub mime: Mime,
+    pub data: &'a [u8],
+    pub charset: DataurlCharset,
+}
+
+impl DataurlSlice<'_> {
+    /// The same as `Dataurl::fmt_to`.
+    pub fn fmt_to<W: core::fmt::Write>(&self, w: &mut W) -> core::fmt::Result {
+        write_dataurl(
+            w,
+            Some(&self.mime),
+            self.data,
+            self.charset,
+            PercentEncodeSet::default(),
+        )
+    }
+
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "std", doc = "The same as `Dataurl::write_to`.")]
+    pub fn write_to<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
+        let mut writer = IoWriter {
+            inner: w,
+            error: None,
         };
+        self.fmt_to(&mut writer).map_err(|_| {
+            writer
+                .error
+                .take()
+                .unwrap_or_else(|| std::io::Error::other("formatter error"))
+        })
+    }
+
+    /// The same as `Dataurl::encoded_len`.
+    pub fn encoded_len(&self) -> usize {
+        encoded_len(
+            &self.mime,
+            written_charset(self.charset, self.data),
+            self.data,
+        )
+    }
+
+    /// Copies the data into an owned `Dataurl`.
+    pub fn to_owned(&self) -> Dataurl {
+        Dataurl {
+            mime: self.mime.clone(),
+            data: self.data.to_vec().into(),
+            charset: self.charset,
+        }
+    }
+}
+
+impl core::fmt::Display for DataurlSlice<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_to(f)
+    }
+}
+
+/// A `dataurl` that borrows its payload from the parsed string. Parsing only validates the structure and the MIME type; the payload is decoded by `to_owned`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataurlRef<'a> {
+    pub mime: Mime,
+    pub charset: DataurlCharset,
+    /// The payload as written, i.e. still base64- or percent-encoded.
+    pub raw_data: &'a str,
+}
+
+impl<'a> DataurlRef<'a> {
+    /// Parses `data:[<mediatype>][;base64],<data>` as per [RFC 2397](https://www.rfc-editor.org/rfc/rfc2397), reading the scheme, the media type and the marker exactly as `Dataurl::from_str` does. Only the payload is left to `decode`.
+    pub fn parse(s: &'a str) -> Result<Self, MimeError> {
+        let (mime, charset, raw_data) = split_dataurl(s)?;
         Ok(Self {
-            charset,
             mime,
-            data,
+            charset,
+            raw_data,
+        })
+    }
+
+    /// Decodes the payload. It is borrowed from the parsed string when it is percent-encoded text without any escape, and decoded into a new buffer otherwise.
+    pub fn decode(&self) -> Result<Cow<'a, [u8]>, MimeError> {
+        match self.charset {
+            DataurlCharset::Utf8 => Ok(urlencoding::decode_binary(self.raw_data.as_bytes())),
+            DataurlCharset::Base64 => general_purpose::STANDARD
+                .decode(self.raw_data.as_bytes())
+                .map(Cow::Owned)
+                .map_err(|_| MimeError::InvalidDataurl("base64 payload")),
+        }
+    }
+
+    /// Decodes the payload into an owned `Dataurl`. Percent-encoded payloads that are not valid UTF-8, or whose media type declares another charset, are kept as `Base64`, so that the result can be displayed again.
+    pub fn to_owned(&self) -> Result<Dataurl, MimeError> {
+        let data = self.decode()?.into_owned();
+        let charset = match self.charset {
+            DataurlCharset::Utf8
+                if declares_legacy_charset(&self.mime) || core::str::from_utf8(&data).is_err() =>
+            {
+                DataurlCharset::Base64
+            }
+            charset => charset,
+        };
+        Ok(Dataurl {
+            mime: self.mime.clone(),
+            data: data.into(),
+            charset,
         })
     }
 }
@@ -168,6 +1015,567 @@ impl FromStr for Dataurl {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_new_from_inner_mime() {
+        let dataurl = Dataurl::new(mime::TEXT_PLAIN, b"hi".to_vec()).unwrap();
+        assert_eq!(dataurl.mime, Mime::TEXT_PLAIN);
+        assert_eq!(dataurl.to_string(), "data:text/plain;charset=utf-8,hi");
+    }
+
+    #[test]
+    fn test_without_marker() {
+        for (input, mime, data) in [
+            ("data:text/plain,Hello", "text/plain", "Hello"),
+            (
+                "data:text/plain,Hello%2C%20World",
+                "text/plain",
+                "Hello, World",
+            ),
+            ("data:text/html,<p>hi</p>", "text/html", "<p>hi</p>"),
+            (
+                "data:text/plain;charset=UTF-8,caf%C3%A9%20%E2%82%AC",
+                "text/plain",
+                "café €",
+            ),
+            (
+                "data:,A%20brief%20note",
+                "text/plain; charset=US-ASCII",
+                "A brief note",
+            ),
+        ] {
+            let dataurl = Dataurl::from_str(input).unwrap();
+            assert_eq!(dataurl.mime, mime, "{input}");
+            assert_eq!(dataurl.charset, DataurlCharset::Utf8, "{input}");
+            assert_eq!(&*dataurl.data, data.as_bytes(), "{input}");
+            assert_eq!(Dataurl::from_str(&dataurl.to_string()).unwrap(), dataurl);
+        }
+        assert!(matches!(
+            Dataurl::from_str("data:image/*,x"),
+            Err(MimeError::Wildcard(_))
+        ));
+    }
+
+    #[test]
+    fn test_charset_parameter() {
+        for input in [
+            "data:text/plain;charset=utf-8,hello",
+            "data:text/plain;CHARSET=\"UTF-8\",hello",
+            "data:text/plain; Charset = utf8 ,hello",
+            "data:text/plain;utf-8,hello",
+        ] {
+            let dataurl = Dataurl::from_str(input).unwrap();
+            assert_eq!(dataurl.mime, Mime::TEXT_PLAIN, "{input}");
+            assert_eq!(dataurl.charset, DataurlCharset::Utf8, "{input}");
+            assert_eq!(&*dataurl.data, b"hello", "{input}");
+        }
+        // Text in another charset is kept as bytes, and written back as base64.
+        let latin1 = Dataurl::from_str("data:text/plain;charset=iso-8859-1,caf%E9").unwrap();
+        assert_eq!(latin1.mime, "text/plain; charset=iso-8859-1");
+        assert_eq!(latin1.charset, DataurlCharset::Base64);
+        assert_eq!(&*latin1.data, b"caf\xe9");
+        assert_eq!(
+            latin1.to_string(),
+            "data:text/plain;charset=iso-8859-1;base64,Y2Fm6Q=="
+        );
+        let latin1 = Dataurl::from_str("data:text/plain;charset=iso-8859-1,abc").unwrap();
+        assert_eq!(
+            latin1.to_string(),
+            "data:text/plain;charset=iso-8859-1;base64,YWJj"
+        );
+        assert_eq!(latin1.encoded_len(), latin1.to_string().len());
+        assert_eq!(Dataurl::from_str(&latin1.to_string()).unwrap(), latin1);
+        // Forced back to text, it keeps the declared charset without a second one.
+        let latin1 = latin1.with_charset(DataurlCharset::Utf8);
+        assert_eq!(latin1.to_string(), "data:text/plain;charset=iso-8859-1,abc");
+        assert_eq!(latin1.encoded_len(), latin1.to_string().len());
+        let utf8 = Dataurl::new(Mime::TEXT_PLAIN_UTF_8, b"abc".to_vec()).unwrap();
+        assert_eq!(utf8.to_string(), "data:text/plain; charset=utf-8,abc");
+        let latin1 =
+            Dataurl::from_str("data:text/plain;charset=iso-8859-1;BASE64,Y2Fm6Q==").unwrap();
+        assert_eq!(latin1.mime, "text/plain; charset=iso-8859-1");
+        assert_eq!(&*latin1.data, b"caf\xe9");
+
+        let text =
+            Dataurl::new(Mime::TEXT_PLAIN_UTF_8, "héllo, wörld".as_bytes().to_vec()).unwrap();
+        assert_eq!(text.charset, DataurlCharset::Utf8);
+        // The declared charset stands for the marker, which parsing strips from the media type.
+        let parsed = Dataurl::from_str(&text.to_string()).unwrap();
+        assert_eq!(parsed.mime, Mime::TEXT_PLAIN);
+        assert_eq!((parsed.charset, parsed.data), (text.charset, text.data));
+    }
+
+    #[test]
+    fn test_charset_and_base64() {
+        for input in [
+            "data:text/plain;charset=iso-8859-1;base64,Y2Fm6Q==",
+            "data:text/plain;base64;charset=iso-8859-1,Y2Fm6Q==",
+            "data:text/plain; Base64 ;charset=\"iso-8859-1\",Y2Fm6Q==",
+        ] {
+            let dataurl = Dataurl::from_str(input).unwrap();
+            assert_eq!(dataurl.charset, DataurlCharset::Base64, "{input}");
+            assert_eq!(
+                dataurl.mime.normalized_charset(),
+                Some(crate::Charset::Latin1),
+                "{input}"
+            );
+            assert_eq!(&*dataurl.data, b"caf\xe9", "{input}");
+            assert_eq!(Dataurl::from_str(&dataurl.to_string()).unwrap(), dataurl);
+
+            let borrowed = DataurlRef::parse(input).unwrap();
+            assert_eq!(borrowed.mime, dataurl.mime, "{input}");
+            assert_eq!(borrowed.to_owned().unwrap(), dataurl);
+        }
+        let utf8 = Dataurl::from_str("data:text/plain;base64;charset=utf-8,aMOp").unwrap();
+        assert_eq!(utf8.mime, Mime::TEXT_PLAIN_UTF_8);
+        assert_eq!(&*utf8.data, "hé".as_bytes());
+        assert_eq!(strip_base64("text/plain;charset=utf-8"), None);
+        assert_eq!(strip_base64("base64"), None);
+    }
+
+    #[test]
+    fn test_utf8_charset_with_binary_data() {
+        let dataurl = Dataurl {
+            mime: Mime::TEXT_PLAIN,
+            charset: DataurlCharset::Utf8,
+            data: b"caf\xe9".to_vec().into(),
+        };
+        let displayed = dataurl.to_string();
+        assert_eq!(displayed, "data:text/plain;base64,Y2Fm6Q==");
+        let parsed = Dataurl::from_str(&displayed).unwrap();
+        assert_eq!(parsed.charset, DataurlCharset::Base64);
+        assert_eq!(parsed.data, dataurl.data);
+        assert!(matches!(
+            dataurl.try_to_string(),
+            Err(MimeError::InvalidDataurl("percent-encoded payload"))
+        ));
+
+        let text = Dataurl::new(Mime::TEXT_PLAIN, b"hi".to_vec()).unwrap();
+        assert_eq!(text.try_to_string().unwrap(), text.to_string());
+    }
+
+    #[test]
+    fn test_with_charset() {
+        let svg = "<svg><text>50% #1 café</text></svg>";
+        let percent = Dataurl::new(Mime::IMAGE_SVG, svg.as_bytes().to_vec()).unwrap();
+        assert_eq!(percent.charset, DataurlCharset::Utf8);
+        let base64 = percent.clone().with_charset(DataurlCharset::Base64);
+        assert!(base64.to_string().starts_with("data:image/svg+xml;base64,"));
+
+        let reparsed = Dataurl::from_str(&base64.to_string()).unwrap();
+        assert_eq!(reparsed.charset, DataurlCharset::Base64);
+        assert_eq!(reparsed.data, percent.data);
+        let forced = Dataurl::from_str(&percent.to_string())
+            .unwrap()
+            .with_charset(DataurlCharset::Base64);
+        assert_eq!(forced.to_string(), base64.to_string());
+        assert_eq!(
+            Dataurl::from_str(&forced.with_charset(DataurlCharset::Utf8).to_string()).unwrap(),
+            percent
+        );
+    }
+
+    #[test]
+    fn test_to_string_smallest() {
+        let css = b".site-header.is-sticky.has-shadow.theme-dark.layout-wide{}\n".to_vec();
+        let css = Dataurl::new(Mime::TEXT_CSS, css)
+            .unwrap()
+            .with_charset(DataurlCharset::Base64);
+        let cjk = "数据网址以文本或二进制形式内联小文件。".repeat(4);
+        let cjk = Dataurl::new(Mime::TEXT_PLAIN_UTF_8, cjk.into_bytes()).unwrap();
+        let binary = Dataurl::new(Mime::IMAGE_PNG, b"\x89PNG\r\n".to_vec()).unwrap();
+        for (dataurl, expected) in [
+            (&css, DataurlCharset::Utf8),
+            (&cjk, DataurlCharset::Base64),
+            (&binary, DataurlCharset::Base64),
+        ] {
+            let (smallest, charset) = dataurl.to_string_smallest();
+            assert_eq!(charset, expected, "{smallest}");
+            let other = dataurl.clone().with_charset(match charset {
+                DataurlCharset::Utf8 => DataurlCharset::Base64,
+                DataurlCharset::Base64 => DataurlCharset::Utf8,
+            });
+            assert!(smallest.len() <= other.to_string().len(), "{smallest}");
+            let parsed = Dataurl::from_str(&smallest).unwrap();
+            assert_eq!(parsed.data, dataurl.data);
+            assert_eq!(parsed.charset, charset);
+        }
+    }
+
+    #[test]
+    fn test_percent_encode_set() {
+        let css = Dataurl::new(Mime::TEXT_CSS, b"a { color: #333 }".to_vec()).unwrap();
+        assert_eq!(
+            css.to_string(),
+            "data:text/css;charset=utf-8,a { color: %23333 }"
+        );
+        assert_eq!(
+            css.to_string_with(PercentEncodeSet::Strict),
+            "data:text/css;charset=utf-8,a%20%7B%20color%3A%20%23333%20%7D"
+        );
+        for set in [PercentEncodeSet::Strict, PercentEncodeSet::Minimal] {
+            assert_eq!(Dataurl::from_str(&css.to_string_with(set)).unwrap(), css);
+        }
+    }
+
+    #[test]
+    fn test_to_svg_string() {
+        let icon = br##"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24" viewBox="0 0 24 24" fill="none" stroke="#1f2937" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><path d="M3 9l9-7 9 7v11a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2z"/><polyline points="9 22 9 12 15 12 15 22"/></svg>"##;
+        let dataurl = Dataurl::new(Mime::IMAGE_SVG, icon.to_vec()).unwrap();
+        let svg = dataurl.to_svg_string().unwrap();
+        let base64 = dataurl
+            .clone()
+            .with_charset(DataurlCharset::Base64)
+            .to_string();
+        assert!(svg.starts_with("data:image/svg+xml,%3Csvg xmlns='http"));
+        // About 20% shorter than base64.
+        assert!(svg.len() * 5 < base64.len() * 4, "{svg}");
+
+        let parsed = Dataurl::from_str(&svg).unwrap();
+        assert_eq!(parsed.mime, Mime::IMAGE_SVG);
+        let swapped: Vec<u8> = icon
+            .iter()
+            .map(|&byte| if byte == b'"' { b'\'' } else { byte })
+            .collect();
+        assert_eq!(parsed.data, swapped);
+
+        let quoted = br#"<svg><text title="it's">x</text></svg>"#;
+        let quoted = Dataurl::new(Mime::IMAGE_SVG, quoted.to_vec()).unwrap();
+        let parsed = Dataurl::from_str(&quoted.to_svg_string().unwrap()).unwrap();
+        assert_eq!(parsed.data, quoted.data);
+
+        for dataurl in [
+            Dataurl::new(Mime::TEXT_PLAIN, b"<svg/>".to_vec()).unwrap(),
+            Dataurl::new(Mime::IMAGE_SVG, b"<svg>\xff</svg>".to_vec()).unwrap(),
+        ] {
+            assert!(matches!(
+                dataurl.to_svg_string(),
+                Err(MimeError::InvalidDataurl("SVG payload"))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_streaming() {
+        let text = "a { color: #333 } /* 中文 */\n".repeat(1000);
+        let large: Vec<u8> = (0..3 * 1024 * 1024 + 1)
+            .map(|i| (i * 7 % 251) as u8)
+            .collect();
+        for dataurl in [
+            Dataurl::new(Mime::TEXT_CSS, text.into_bytes()).unwrap(),
+            Dataurl::new(Mime::APPLICATION_OCTET_STREAM, large).unwrap(),
+            Dataurl::new(Mime::IMAGE_PNG, b"\x89PNG".to_vec()).unwrap(),
+        ] {
+            let expected = dataurl.to_string();
+            assert_eq!(expected, alloc::string::ToString::to_string(&dataurl));
+            // `encoded_len` is exact, so the string never grew.
+            assert_eq!(expected.capacity(), expected.len());
+            if dataurl.charset == DataurlCharset::Base64 {
+                let whole = general_purpose::STANDARD.encode(&dataurl.data);
+                assert_eq!(
+                    expected,
+                    alloc::format!("data:{};base64,{whole}", dataurl.mime)
+                );
+            }
+            let mut formatted = String::new();
+            dataurl.fmt_to(&mut formatted).unwrap();
+            assert_eq!(formatted, expected);
+
+            #[cfg(feature = "std")]
+            {
+                let mut written = Vec::new();
+                dataurl.write_to(&mut written).unwrap();
+                assert_eq!(written, expected.as_bytes());
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_to_error() {
+        struct Full;
+
+        impl std::io::Write for Full {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::StorageFull.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let dataurl = Dataurl::new(Mime::TEXT_PLAIN, b"hi".to_vec()).unwrap();
+        let err = dataurl.write_to(&mut Full).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::StorageFull);
+    }
+
+    #[test]
+    fn test_dataurl_ref_decode() {
+        let plain = "data:text/plain,hello-world";
+        let decoded = DataurlRef::parse(plain).unwrap().decode().unwrap();
+        assert!(
+            matches!(&decoded, Cow::Borrowed(data) if core::ptr::eq(data.as_ptr(), plain[16..].as_ptr()))
+        );
+        assert_eq!(&*decoded, b"hello-world");
+
+        let escaped = DataurlRef::parse("data:text/plain,50%25")
+            .unwrap()
+            .decode()
+            .unwrap();
+        assert!(matches!(&escaped, Cow::Owned(data) if data == b"50%"));
+        let base64 = DataurlRef::parse("data:;base64,SGVsbG8=").unwrap();
+        assert_eq!(&*base64.decode().unwrap(), b"Hello");
+        assert!(matches!(
+            DataurlRef::parse("data:;base64,!!!").unwrap().decode(),
+            Err(MimeError::InvalidDataurl("base64 payload"))
+        ));
+    }
+
+    #[test]
+    fn test_borrowed() {
+        let payloads: [&[u8]; 3] = [
+            b"a { color: #333 }",
+            "héllo".as_bytes(),
+            b"\x89PNG\r\n\x1a\n",
+        ];
+        for (mime, data) in [Mime::TEXT_CSS, Mime::TEXT_PLAIN, Mime::IMAGE_PNG]
+            .into_iter()
+            .zip(payloads)
+        {
+            let slice = Dataurl::borrowed(mime.clone(), data).unwrap();
+            assert!(core::ptr::eq(slice.data, data));
+            let owned = Dataurl::new(mime, data.to_vec()).unwrap();
+            assert_eq!(slice.charset, owned.charset);
+            assert_eq!(slice.to_string(), owned.to_string());
+            assert_eq!(slice.encoded_len(), owned.encoded_len());
+            assert_eq!(slice.to_owned(), owned);
+
+            #[cfg(feature = "std")]
+            {
+                let mut written = Vec::new();
+                slice.write_to(&mut written).unwrap();
+                assert_eq!(written, owned.to_string().as_bytes());
+            }
+        }
+        assert!(matches!(
+            Dataurl::borrowed(mime::IMAGE_STAR, b""),
+            Err(MimeError::Wildcard(_))
+        ));
+    }
+
+    #[test]
+    fn test_lossy() {
+        for (input, data) in [
+            ("data:text/plain;charset=utf-8,%E0%A4%A", "\u{FFFD}%A"),
+            ("data:text/plain,100%", "100%"),
+            ("data:text/plain,%ZZ%41", "%ZZA"),
+            ("data:text/plain,caf%E9", "caf\u{FFFD}"),
+        ] {
+            assert!(Dataurl::from_str(input).is_err(), "{input}");
+            let dataurl = Dataurl::from_str_lossy(input).unwrap();
+            assert_eq!(dataurl.charset, DataurlCharset::Utf8, "{input}");
+            assert_eq!(&*dataurl.data, data.as_bytes(), "{input}");
+        }
+        assert!(Dataurl::from_str_lossy("data:image/png;base64,!!!").is_err());
+    }
+
+    #[test]
+    fn test_implicit_mime() {
+        for (input, data, minimal, full) in [
+            (
+                "data:,Hello",
+                b"Hello".as_slice(),
+                "data:,Hello",
+                "data:text/plain;charset=us-ascii,Hello",
+            ),
+            (
+                "data:;base64,SGVsbG8=",
+                b"Hello",
+                "data:;base64,SGVsbG8=",
+                "data:text/plain;charset=us-ascii;base64,SGVsbG8=",
+            ),
+            ("data:,", b"", "data:,", "data:text/plain;charset=us-ascii,"),
+            // Non-ASCII text can't be declared US-ASCII, so the marker replaces the implicit charset.
+            (
+                "data:,caf%C3%A9",
+                "café".as_bytes(),
+                "data:;charset=utf-8,caf%C3%A9",
+                "data:text/plain;charset=utf-8,caf%C3%A9",
+            ),
+        ] {
+            let dataurl = Dataurl::from_str(input).unwrap();
+            assert!(dataurl.mime_was_implicit(), "{input}");
+            assert_eq!(&*dataurl.data, data, "{input}");
+            assert_eq!(dataurl.to_minimal_string(), minimal, "{input}");
+            assert_eq!(Dataurl::from_str(minimal).unwrap(), dataurl);
+            assert_eq!(dataurl.to_string(), full, "{input}");
+            assert_eq!(dataurl.encoded_len(), full.len(), "{input}");
+            let reparsed = Dataurl::from_str(full).unwrap();
+            assert_eq!(reparsed.data, dataurl.data, "{input}");
+            assert_eq!(reparsed.to_string(), full, "{input}");
+        }
+        let binary = Dataurl::new(Mime::from_str(IMPLICIT_MIME).unwrap(), vec![0, 1, 2]).unwrap();
+        assert_eq!(binary.to_minimal_string(), "data:;base64,AAEC");
+
+        let explicit = Dataurl::from_str("data:text/plain,Hello").unwrap();
+        assert!(!explicit.mime_was_implicit());
+        assert_eq!(explicit.to_minimal_string(), explicit.to_string());
+        assert!(
+            !Dataurl::from_str("data:text/plain;charset=us-ascii;format=flowed,x")
+                .unwrap()
+                .mime_was_implicit()
+        );
+    }
+
+    #[test]
+    fn test_declared_charset() {
+        for (ty, charset) in [
+            ("text/plain; charset=UTF8", DataurlCharset::Utf8),
+            ("text/plain; charset=\"utf_8\"", DataurlCharset::Utf8),
+            ("text/plain; charset=iso-8859-1", DataurlCharset::Base64),
+            ("text/plain; charset=Shift_JIS", DataurlCharset::Base64),
+        ] {
+            let dataurl = Dataurl::new(Mime::from_str(ty).unwrap(), b"hi".to_vec()).unwrap();
+            assert_eq!(dataurl.charset, charset, "{ty}");
+        }
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for dataurl in [
+            Dataurl::new(Mime::TEXT_PLAIN, b"hi there".to_vec()).unwrap(),
+            Dataurl::new(
+                Mime::from_str(r#"text/plain; name="a,b;c""#).unwrap(),
+                b"x,y".to_vec(),
+            )
+            .unwrap(),
+            // Text that isn't UTF-8 is base64-encoded instead of panicking.
+            Dataurl::new(Mime::TEXT_PLAIN_UTF_8, b"\x89PNG".to_vec()).unwrap(),
+        ] {
+            assert_eq!(Dataurl::from_str(&dataurl.to_string()).unwrap(), dataurl);
+        }
+        assert_eq!(
+            Dataurl::from_str("data:text/plain;utf-8,hi").unwrap().data,
+            b"hi".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_wildcard() {
+        assert!(matches!(
+            Dataurl::new(Mime::IMAGE_STAR, b"hi".to_vec()),
+            Err(MimeError::Wildcard(_))
+        ));
+        assert!(matches!(
+            Dataurl::from_str("data:image/*;base64,iVBORw0KGgoRRRQZGYEA"),
+            Err(MimeError::Wildcard(_))
+        ));
+        assert!(matches!(
+            DataurlRef::parse("data:*/*,hi"),
+            Err(MimeError::Wildcard(_))
+        ));
+        assert!(Dataurl::from_str("data:image/png;base64,iVBORw0KGgoRRRQZGYEA").is_ok());
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        let png = Dataurl::from_str("data:image/png;base64,iVBORw0KGgoRRRQZGYEA").unwrap();
+        let text = Dataurl::new(
+            Mime::from_str("text/plain").unwrap(),
+            b"Hello, world~".to_vec(),
+        )
+        .unwrap();
+        let utf8 =
+            Dataurl::new(Mime::TEXT_PLAIN_UTF_8, "héllo 世界 #1 ".as_bytes().to_vec()).unwrap();
+        let empty = Dataurl::new(Mime::TEXT_CSS, Vec::new()).unwrap();
+        let mislabeled = Dataurl {
+            mime: Mime::TEXT_PLAIN,
+            charset: DataurlCharset::Utf8,
+            data: b"\x00\xff\xfe".to_vec().into(),
+        };
+        for padding in 0..3 {
+            let binary =
+                Dataurl::new(Mime::APPLICATION_OCTET_STREAM, vec![0xAB; 10 + padding]).unwrap();
+            assert_eq!(binary.encoded_len(), binary.to_string().len());
+        }
+        for dataurl in [png, text, utf8, empty, mislabeled] {
+            assert_eq!(
+                dataurl.encoded_len(),
+                dataurl.to_string().len(),
+                "{dataurl}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_dataurl_ref() {
+        let dataurl = DataurlRef::parse("data:image/png;base64,iVBORw0KGgoRRRQZGYEA").unwrap();
+        assert_eq!(dataurl.mime, "image/png");
+        assert_eq!(dataurl.charset, DataurlCharset::Base64);
+        assert_eq!(dataurl.raw_data, "iVBORw0KGgoRRRQZGYEA");
+        assert_eq!(
+            dataurl.to_owned().unwrap(),
+            Dataurl::from_str("data:image/png;base64,iVBORw0KGgoRRRQZGYEA").unwrap()
+        );
+
+        let dataurl = DataurlRef::parse("data:,A%20brief%20note").unwrap();
+        assert_eq!(dataurl.mime, "text/plain;charset=US-ASCII");
+        assert_eq!(dataurl.to_owned().unwrap().data, b"A brief note".to_vec());
+
+        let dataurl = DataurlRef::parse("DATA:image/svg+xml;utf8,<svg/>").unwrap();
+        assert_eq!(dataurl.mime, "image/svg+xml");
+        assert_eq!(dataurl.charset, DataurlCharset::Utf8);
+
+        assert!(DataurlRef::parse("data:image/png;base64").is_err());
+        assert!(DataurlRef::parse("image/png;base64,iVBORw0KGgo=").is_err());
+        assert!(DataurlRef::parse("data:image/png;base64,!!!!")
+            .unwrap()
+            .to_owned()
+            .is_err());
+    }
+
+    #[test]
+    fn test_dataurl_ref_agrees() {
+        for input in [
+            "data:text/plain;a=\"x,y\",hi",
+            "Data:,Hello",
+            "DATA:image/svg+xml;utf8,<svg/>",
+            "data:text/plain;charset=iso-8859-1,abc",
+            "data:text/plain;charset=iso-8859-1;base64,Y2Fm6Q==",
+            "data:;base64,SGVsbG8=",
+            "data: text/plain ;base64,SGVsbG8=",
+            "data:;base64, SGVsbG8= ",
+            "data:text/plain",
+            "data:text/plain;base64",
+            "data:image/png;base32,AAAA",
+            "data:image/*,hi",
+            "data:not a mime,hi",
+            "http://example.com",
+        ] {
+            match (Dataurl::from_str(input), DataurlRef::parse(input)) {
+                (Ok(owned), Ok(borrowed)) => {
+                    assert_eq!(borrowed.mime, owned.mime, "{input}");
+                    assert_eq!(borrowed.to_owned().unwrap(), owned, "{input}");
+                }
+                (Err(owned), Ok(borrowed)) => {
+                    // Only the payload is left to `decode`.
+                    assert_eq!(
+                        borrowed.to_owned().unwrap_err().to_string(),
+                        owned.to_string(),
+                        "{input}"
+                    );
+                }
+                (Err(owned), Err(borrowed)) => {
+                    assert_eq!(borrowed.to_string(), owned.to_string(), "{input}")
+                }
+                (Ok(_), Err(err)) => panic!("{input}: {err}"),
+            }
+        }
+        let quoted = DataurlRef::parse("data:text/plain;a=\"x,y\",hi").unwrap();
+        assert_eq!(quoted.raw_data, "hi");
+        assert_eq!(quoted.mime.get_param("a"), Some("x,y"));
+    }
+
     #[test]
     fn test_dataurl() {
         let content = &[
@@ -183,4 +1591,215 @@ mod tests {
         assert_eq!(dataurl.mime, "image/png");
         assert_eq!(dataurl.data, content.to_vec());
     }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn test_bytes_zero_copy() {
+        let content = bytes::Bytes::from_static(&[
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x11, 0x45, 0x14, 0x19, 0x19, 0x81, 0x0,
+        ]);
+
+        let dataurl = Dataurl::from_bytes(content.clone()).unwrap();
+        assert_eq!(dataurl.mime, "image/png");
+        assert_eq!(dataurl.data.as_ptr(), content.as_ptr());
+        assert_eq!(dataurl.data_bytes().as_ptr(), content.as_ptr());
+        assert_eq!(
+            bytes::Bytes::from(dataurl.data.clone()).as_ptr(),
+            content.as_ptr()
+        );
+
+        let from_vec = Dataurl::from_data(content.to_vec()).unwrap();
+        assert_eq!(dataurl, from_vec);
+        assert_eq!(dataurl.to_string(), from_vec.to_string());
+        assert_eq!(
+            dataurl.to_string(),
+            "data:image/png;base64,iVBORw0KGgoRRRQZGYEA"
+        );
+    }
+
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[test]
+    fn test_from_path_sniffed() {
+        let path = std::path::Path::new("tests/fixtures/pixel.png");
+        let sniffed = Dataurl::from_path_sniffed(path, 8).unwrap();
+        assert_eq!(sniffed, Dataurl::from_path(path).unwrap());
+        assert_eq!(sniffed.mime, "image/png");
+
+        // One byte is too short for any matcher, so the extension decides.
+        let svg = std::path::Path::new("tests/fixtures/logo.svg");
+        assert_eq!(
+            Dataurl::from_path_sniffed(svg, 1).unwrap(),
+            Dataurl::from_path(svg).unwrap()
+        );
+        assert!(matches!(
+            Dataurl::from_path_sniffed(std::path::Path::new("does/not/exist"), 8),
+            Err(MimeError::Io(_))
+        ));
+    }
+
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[test]
+    fn test_from_path_errors() {
+        let missing = std::path::Path::new("does/not/exist.png");
+        let err = Dataurl::from_path(missing).unwrap_err();
+        assert!(
+            matches!(&err, MimeError::Io(io) if io.kind() == std::io::ErrorKind::NotFound),
+            "{err:?}"
+        );
+        assert!(err.to_string().contains("does/not/exist.png"), "{err}");
+
+        let license = Dataurl::from_path(std::path::Path::new("LICENSE")).unwrap();
+        assert_eq!(license.mime, Mime::TEXT_PLAIN);
+        assert_eq!(license.charset, DataurlCharset::Utf8);
+        assert!(license
+            .to_string()
+            .starts_with("data:text/plain;charset=utf-8,"));
+    }
+
+    #[test]
+    fn test_with_mime() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg"/>"#.to_vec();
+        // Textual data is percent-encoded whatever its type, unless the type declares another charset.
+        let latin1 = Mime::from_str("text/xml; charset=iso-8859-1").unwrap();
+        let guessed = Dataurl::new(latin1.clone(), svg.clone()).unwrap();
+        assert_eq!(guessed.charset, DataurlCharset::Base64);
+        assert!(guessed
+            .to_string()
+            .starts_with("data:text/xml; charset=iso-8859-1;base64,"));
+
+        let fixed = guessed.clone().with_mime(Mime::IMAGE_SVG).unwrap();
+        assert_eq!(fixed.charset, DataurlCharset::Utf8);
+        assert_eq!(fixed.data, svg);
+        assert!(fixed
+            .to_string()
+            .starts_with("data:image/svg+xml;charset=utf-8,<svg"));
+        assert_eq!(fixed, Dataurl::new(Mime::IMAGE_SVG, svg).unwrap());
+
+        let mut back = fixed.clone();
+        back.set_mime(latin1).unwrap();
+        assert_eq!(back, guessed);
+        assert!(matches!(
+            back.set_mime(mime::IMAGE_STAR),
+            Err(MimeError::Wildcard(_))
+        ));
+        assert_eq!(back, guessed);
+    }
+
+    #[test]
+    fn test_parts() {
+        let parsed = Dataurl::from_str("data:text/plain;charset=utf-8,h%C3%A9").unwrap();
+        let owned = parsed.clone();
+        let data_ptr = owned.data.as_ptr();
+        let (mime, data, charset) = owned.into_parts();
+        assert_eq!(mime, Mime::TEXT_PLAIN);
+        assert_eq!(data, "hé".as_bytes());
+        assert_eq!(data.as_ptr(), data_ptr);
+        assert_eq!(charset, DataurlCharset::Utf8);
+        assert_eq!(Dataurl::from_parts(mime, data, charset).unwrap(), parsed);
+
+        let base64 =
+            Dataurl::from_parts(Mime::TEXT_PLAIN, b"hi".to_vec(), DataurlCharset::Base64).unwrap();
+        assert_eq!(base64.to_string(), "data:text/plain;base64,aGk=");
+
+        assert!(matches!(
+            Dataurl::from_parts(Mime::TEXT_PLAIN, b"caf\xe9".to_vec(), DataurlCharset::Utf8),
+            Err(MimeError::InvalidDataurl("percent-encoded payload"))
+        ));
+        assert!(matches!(
+            Dataurl::from_parts(mime::IMAGE_STAR, Vec::new(), DataurlCharset::Base64),
+            Err(MimeError::Wildcard(_))
+        ));
+    }
+
+    #[test]
+    fn test_suggested_filename() {
+        let png = Dataurl::from_str("data:image/png;base64,iVBORw0KGgo=").unwrap();
+        assert_eq!(png.suggested_filename("logo"), "logo.png");
+        let svg = Dataurl::new(Mime::IMAGE_SVG, b"<svg/>".to_vec()).unwrap();
+        assert_eq!(svg.suggested_filename("icon"), "icon.svg");
+        let unknown = Dataurl::from_str("data:application/x-unknown-thing;base64,AA==").unwrap();
+        assert_eq!(unknown.suggested_filename("blob"), "blob");
+    }
+
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[test]
+    fn test_to_file() {
+        let png = Dataurl::from_path(std::path::Path::new("tests/fixtures/pixel.png")).unwrap();
+        let dir =
+            std::env::temp_dir().join(std::format!("mime_more-to_file-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(png.suggested_filename("pixel"));
+
+        png.to_file(&path, false).unwrap();
+        assert_eq!(Mime::sniff_path(&path, 8192).unwrap(), Mime::IMAGE_PNG);
+        assert_eq!(std::fs::read(&path).unwrap(), png.data.as_slice());
+
+        let err = png.to_file(&path, false).unwrap_err();
+        assert!(
+            matches!(&err, MimeError::Io(io) if io.kind() == std::io::ErrorKind::AlreadyExists),
+            "{err:?}"
+        );
+        assert!(err.to_string().contains("pixel.png"), "{err}");
+        let text = Dataurl::new(Mime::TEXT_PLAIN, b"hi".to_vec()).unwrap();
+        text.to_file(&path, true).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"hi");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_reader() {
+        use std::io::Cursor;
+
+        for (path, ext) in [
+            ("tests/fixtures/pixel.png", Some("png")),
+            ("tests/fixtures/logo.svg", Some("svg")),
+            ("tests/fixtures/notes.txt", None),
+        ] {
+            let data = std::fs::read(path).unwrap();
+            let read = Dataurl::from_reader(&mut Cursor::new(data), ext, None).unwrap();
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            assert_eq!(
+                read,
+                Dataurl::from_path(std::path::Path::new(path)).unwrap()
+            );
+        }
+
+        let png = std::fs::read("tests/fixtures/pixel.png").unwrap();
+        let unhinted = Dataurl::from_reader(&mut Cursor::new(png.clone()), None, None).unwrap();
+        assert_eq!(unhinted.mime, Mime::IMAGE_PNG);
+        assert_eq!(unhinted.charset, DataurlCharset::Base64);
+        let text =
+            Dataurl::from_reader(&mut Cursor::new(b"hello".to_vec()), None, Some(5)).unwrap();
+        assert_eq!(text.mime, Mime::TEXT_PLAIN);
+        assert_eq!(text.to_string(), "data:text/plain;charset=utf-8,hello");
+
+        let err = Dataurl::from_reader(&mut Cursor::new(png), None, Some(16)).unwrap_err();
+        assert!(matches!(&err, MimeError::Io(io) if io.kind() == std::io::ErrorKind::FileTooLarge));
+        assert_eq!(err.to_string(), "data URL input exceeds 16 bytes");
+
+        let unbounded =
+            Dataurl::from_reader(&mut Cursor::new(b"hello".to_vec()), None, Some(usize::MAX))
+                .unwrap();
+        assert_eq!(unbounded, text);
+    }
+
+    #[cfg(feature = "camino")]
+    #[test]
+    fn test_from_utf8_path() {
+        let path = camino::Utf8Path::new("Cargo.toml");
+        let dataurl = Dataurl::from_utf8_path(path).unwrap();
+        assert_eq!(dataurl, Dataurl::from_path(path.as_std_path()).unwrap());
+        assert_eq!(dataurl.mime, "text/x-toml");
+    }
 }
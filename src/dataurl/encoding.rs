@@ -0,0 +1,117 @@
+#![cfg(feature = "encoding")]
+//! Re-encodes textual payloads in legacy charsets (e.g. Shift-JIS or windows-1252) to UTF-8 via [`encoding_rs`](https://docs.rs/encoding_rs/latest/encoding_rs/), as browsers read percent-encoded data URLs as UTF-8.
+
+use super::{Dataurl, DataurlCharset};
+use crate::Mime;
+pub use encoding_rs::Encoding;
+
+impl Dataurl {
+    /// Converts the payload from `source` to UTF-8 and switches to the percent-encoded form. A leading byte order mark of `source` is dropped, and so is the `charset` parameter of the MIME type, as the data URL then declares `charset=utf-8`.
+    ///
+    /// Fails without modifying the data URL if the payload is malformed in `source`, rather than replacing the malformed bytes.
+    pub fn reencode_to_utf8(&mut self, source: &'static Encoding) -> anyhow::Result<()> {
+        let data = match Encoding::for_bom(&self.data) {
+            Some((encoding, bom_length)) if encoding == source => &self.data[bom_length..],
+            _ => &self.data[..],
+        };
+        let text = source
+            .decode_without_bom_handling_and_without_replacement(data)
+            .ok_or_else(|| anyhow::anyhow!("Payload is not valid {}", source.name()))?
+            .into_owned();
+        let mime = self.mime.without_param("charset")?;
+        debug!(
+            from = source.name(),
+            len = text.len(),
+            "dataurl payload reencoded to utf-8"
+        );
+        self.mime = mime;
+        self.data = text.into_bytes().into();
+        self.charset = DataurlCharset::Utf8;
+        Ok(())
+    }
+
+    /// The same as `reencode_to_utf8`, with the source charset taken from `guess_charset`. Returns the charset that was used.
+    pub fn reencode_to_utf8_auto(&mut self) -> anyhow::Result<&'static Encoding> {
+        let source = guess_charset(&self.mime, &self.data)
+            .ok_or_else(|| anyhow::anyhow!("Failed to guess the charset of the payload"))?;
+        self.reencode_to_utf8(source)?;
+        Ok(source)
+    }
+}
+
+/// Guesses the charset of a textual payload from its byte order mark, then from the `charset` parameter of its MIME type. A payload with neither is only recognized when it is valid UTF-8, since legacy charsets can't be told apart reliably.
+pub fn guess_charset(mime: &Mime, data: &[u8]) -> Option<&'static Encoding> {
+    if let Some((encoding, _)) = Encoding::for_bom(data) {
+        return Some(encoding);
+    }
+    if let Some(encoding) = mime
+        .charset()
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+    {
+        return Some(encoding);
+    }
+    core::str::from_utf8(data)
+        .is_ok()
+        .then_some(encoding_rs::UTF_8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    /// "こんにちは、世界" in Shift-JIS.
+    const SHIFT_JIS: &[u8] = b"\x82\xb1\x82\xf1\x82\xc9\x82\xbf\x82\xcd\x81\x41\x90\xa2\x8a\x45";
+    /// "café “quoted” – 50€" in windows-1252.
+    const WINDOWS_1252: &[u8] = b"caf\xe9 \x93quoted\x94 \x96 50\x80";
+
+    fn dataurl(mime: &str, data: &[u8]) -> Dataurl {
+        Dataurl::new(Mime::from_str(mime).unwrap(), data.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn shift_jis() {
+        let mut url = dataurl("text/plain; charset=Shift_JIS", SHIFT_JIS);
+        url.reencode_to_utf8(encoding_rs::SHIFT_JIS).unwrap();
+        assert_eq!(url.data, "こんにちは、世界".as_bytes().to_vec());
+        assert_eq!(url.mime, "text/plain");
+        assert_eq!(url.charset, DataurlCharset::Utf8);
+        assert_eq!(
+            url.to_string(),
+            "data:text/plain;charset=utf-8,%E3%81%93%E3%82%93%E3%81%AB%E3%81%A1%E3%81%AF%E3%80%81%E4%B8%96%E7%95%8C"
+        );
+    }
+
+    #[test]
+    fn windows_1252_auto() {
+        let mut url = dataurl("text/css; charset=windows-1252; media=print", WINDOWS_1252);
+        assert_eq!(
+            url.reencode_to_utf8_auto().unwrap(),
+            encoding_rs::WINDOWS_1252
+        );
+        assert_eq!(url.data, "café “quoted” – 50€".as_bytes().to_vec());
+        assert_eq!(url.mime, "text/css; media=print");
+    }
+
+    #[test]
+    fn byte_order_mark() {
+        let mut url = dataurl("text/plain", b"\xff\xfeh\0i\0");
+        assert_eq!(url.reencode_to_utf8_auto().unwrap(), encoding_rs::UTF_16LE);
+        assert_eq!(url.data, b"hi".to_vec());
+    }
+
+    #[test]
+    fn lossy_conversions_fail() {
+        let mut url = dataurl("text/plain; charset=Shift_JIS", &SHIFT_JIS[..3]);
+        let before = url.clone();
+        assert!(url.reencode_to_utf8(encoding_rs::SHIFT_JIS).is_err());
+        assert_eq!(url, before);
+
+        let mut url = dataurl("text/plain", WINDOWS_1252);
+        assert!(url.reencode_to_utf8_auto().is_err());
+        assert_eq!(
+            guess_charset(&Mime::from_str("text/plain").unwrap(), b"plain"),
+            Some(encoding_rs::UTF_8)
+        );
+    }
+}
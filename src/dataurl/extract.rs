@@ -0,0 +1,196 @@
+//! Finds the data URLs embedded in CSS, HTML, or JavaScript source text, e.g. to measure the inlined weight of a bundle.
+
+use super::DataurlRef;
+use crate::MimeError;
+use core::ops::Range;
+
+/// The delimiters around an extracted data URL, which also decide where it ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataurlContext {
+    /// Inside a CSS `url(...)`, optionally quoted. Unquoted, it ends at the first `)`, whitespace, or quote, none of which can appear in a base64 payload.
+    Url { quote: Option<char> },
+    /// Inside a quoted string, such as an HTML attribute or a JavaScript literal. It ends at the matching unescaped quote, so percent-encoded payloads may contain the other quote.
+    Quoted { quote: char },
+    /// Not delimited. It ends at the first whitespace, quote, parenthesis, `<`, `>`, or backtick.
+    Bare,
+}
+
+/// A data URL found by `extract`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedDataurl<'a> {
+    /// The byte range of the data URL in the source, without its delimiters.
+    pub range: Range<usize>,
+    pub context: DataurlContext,
+    source: &'a str,
+}
+
+impl<'a> ExtractedDataurl<'a> {
+    /// Returns the data URL as written in the source.
+    pub fn as_str(&self) -> &'a str {
+        &self.source[self.range.clone()]
+    }
+
+    /// Parses the data URL. It is only done on demand, so that scanning stays cheap when only the ranges are needed.
+    pub fn dataurl(&self) -> Result<DataurlRef<'a>, MimeError> {
+        DataurlRef::parse(self.as_str())
+    }
+}
+
+/// Returns every data URL in the source text, in order.
+///
+/// The scan is lexical: `data:` has to start a word and be followed by a `,` before its end, but the URL is not validated until `ExtractedDataurl::dataurl` is called.
+///
+/// # Example
+///
+/// ```rust
+/// use mime_more::dataurl::{extract, DataurlContext};
+///
+/// let css = ".logo { background: url(data:image/png;base64,iVBORw0KGgo=) }";
+/// let found: Vec<_> = extract(css).collect();
+/// assert_eq!(found.len(), 1);
+/// assert_eq!(found[0].as_str(), "data:image/png;base64,iVBORw0KGgo=");
+/// assert_eq!(found[0].context, DataurlContext::Url { quote: None });
+/// assert_eq!(found[0].dataurl().unwrap().mime, "image/png");
+/// ```
+pub fn extract(source: &str) -> impl Iterator<Item = ExtractedDataurl<'_>> {
+    let mut offset = 0;
+    core::iter::from_fn(move || loop {
+        let start = offset + find_scheme(&source[offset..])?;
+        offset = start + 5;
+        if start > 0 && is_word_byte(source.as_bytes()[start - 1]) {
+            continue;
+        }
+        let context = context_before(&source[..start]);
+        let end = offset + find_end(&source[offset..], context);
+        offset = end;
+        if source[start..end].contains(',') {
+            return Some(ExtractedDataurl {
+                range: start..end,
+                context,
+                source,
+            });
+        }
+    })
+}
+
+/// Finds `data:`, case-insensitively.
+fn find_scheme(source: &str) -> Option<usize> {
+    source
+        .as_bytes()
+        .windows(5)
+        .position(|window| window.eq_ignore_ascii_case(b"data:"))
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'
+}
+
+/// Classifies the text right before `data:`. A quote has to be adjacent, while `url(` may be followed by whitespace.
+fn context_before(prefix: &str) -> DataurlContext {
+    let quote = match prefix.as_bytes().last() {
+        Some(&quote @ (b'"' | b'\'')) => Some(quote as char),
+        _ => None,
+    };
+    let before = prefix[..prefix.len() - quote.map_or(0, char::len_utf8)].trim_end();
+    let in_url = before
+        .strip_suffix('(')
+        .map(|before| before.trim_end().as_bytes())
+        .and_then(|before| before.get(before.len().checked_sub(3)?..))
+        .is_some_and(|name| name.eq_ignore_ascii_case(b"url"));
+    match (in_url, quote) {
+        (true, quote) => DataurlContext::Url { quote },
+        (false, Some(quote)) => DataurlContext::Quoted { quote },
+        (false, None) => DataurlContext::Bare,
+    }
+}
+
+/// Returns the length of the data URL in `rest`, i.e. the offset of its closing delimiter.
+fn find_end(rest: &str, context: DataurlContext) -> usize {
+    let mut escaped = false;
+    for (index, byte) in rest.bytes().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        let stop = match context {
+            DataurlContext::Url { quote: Some(quote) } | DataurlContext::Quoted { quote } => {
+                escaped = byte == b'\\';
+                byte == quote as u8
+            }
+            DataurlContext::Url { quote: None } => {
+                byte == b')' || byte == b'"' || byte == b'\'' || byte.is_ascii_whitespace()
+            }
+            DataurlContext::Bare => byte.is_ascii_whitespace() || b"\"'()<>`".contains(&byte),
+        };
+        if stop {
+            return index;
+        }
+    }
+    rest.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataurl::DataurlCharset;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn css_fixture() {
+        let css = include_str!("../../tests/fixtures/dataurls.css");
+        let found: Vec<_> = extract(css).collect();
+        assert_eq!(found.len(), 3);
+
+        assert_eq!(found[0].context, DataurlContext::Url { quote: None });
+        assert_eq!(
+            found[0].as_str(),
+            "data:image/png;base64,iVBORw0KGgoRRRQZGYEA"
+        );
+        assert_eq!(&css[found[0].range.clone()], found[0].as_str());
+        let png = found[0].dataurl().unwrap();
+        assert_eq!(png.mime, "image/png");
+        assert_eq!(png.charset, DataurlCharset::Base64);
+
+        assert_eq!(found[1].context, DataurlContext::Url { quote: Some('"') });
+        let svg = found[1].dataurl().unwrap();
+        assert_eq!(svg.mime, "image/svg+xml");
+        assert_eq!(svg.charset, DataurlCharset::Utf8);
+        assert!(svg.raw_data.ends_with("%3C/svg%3E"));
+
+        assert_eq!(found[2].context, DataurlContext::Url { quote: Some('\'') });
+        let svg = found[2].dataurl().unwrap();
+        assert_eq!(svg.mime, "image/svg+xml");
+        assert_eq!(svg.charset, DataurlCharset::Utf8);
+        assert!(svg.raw_data.contains(r#"viewBox="0 0 1 1""#));
+        assert!(svg.raw_data.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn html_fixture() {
+        let html = include_str!("../../tests/fixtures/dataurls.html");
+        let found: Vec<_> = extract(html).collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].context, DataurlContext::Quoted { quote: '"' });
+        let gif = found[0].dataurl().unwrap();
+        assert_eq!(gif.mime, "image/gif");
+        assert!(gif.to_owned().unwrap().data.starts_with(b"GIF89a"));
+    }
+
+    #[test]
+    fn boundaries() {
+        assert_eq!(extract("metadata:a,b").count(), 0);
+        assert_eq!(extract("data: none").count(), 0);
+
+        let found: Vec<_> = extract("see data:,hi there").collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].context, DataurlContext::Bare);
+        assert_eq!(found[0].as_str(), "data:,hi");
+
+        let js = r#"const a = "data:text/plain,say \"hi\""; const b = 'DATA:,x';"#;
+        let found: Vec<_> = extract(js).collect();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].as_str(), r#"data:text/plain,say \"hi\""#);
+        assert_eq!(found[1].context, DataurlContext::Quoted { quote: '\'' });
+        assert_eq!(found[1].as_str(), "DATA:,x");
+    }
+}
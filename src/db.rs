@@ -0,0 +1,180 @@
+#![cfg(feature = "db")]
+//! The known-type database of the crate, i.e. the tables `Mime::from_ext_light` and `Mime::from_path_light` look up: the `extension-light` table with the compound extensions such as `tar.gz`, and the dotfile table, as data. It lets other tools (e.g. a web UI) share the exact same table without drifting.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::db::{entries, AssetKind};
+//!
+//! let png = entries().find(|entry| entry.ext == "png").unwrap();
+//! assert_eq!(png.mime, "image/png");
+//! assert!(!png.texture);
+//! assert_eq!(png.category, AssetKind::Image);
+//! ```
+
+use crate::compound::COMPOUND_EXTENSIONS;
+use crate::dotfile::DOTFILES;
+use crate::light_guess::MIME_TYPES;
+use crate::texture::is_texture_mime;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+/// The broad kind of asset a MIME type describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum AssetKind {
+    Text,
+    Image,
+    Font,
+    Audio,
+    Video,
+    Other,
+}
+
+impl AssetKind {
+    /// Classifies a MIME type by its top-level type. `application` types that are textures (e.g. JSON) are `Text`.
+    pub fn of(mime: &mime::Mime) -> Self {
+        match mime.type_() {
+            mime::TEXT => Self::Text,
+            mime::IMAGE => Self::Image,
+            mime::FONT => Self::Font,
+            mime::AUDIO => Self::Audio,
+            mime::VIDEO => Self::Video,
+            _ if is_texture_mime(mime) => Self::Text,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Which table an entry comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum DbSource {
+    /// The `extension-light` table, keyed by extension, including compound extensions such as `tar.gz`.
+    Extension,
+    /// The dotfile table, keyed by the whole file name, e.g. `.babelrc`.
+    Dotfile,
+}
+
+/// An entry of the known-type database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DbEntry {
+    /// The extension (without the leading dot), or the whole file name for `DbSource::Dotfile`.
+    pub ext: &'static str,
+    pub mime: &'static str,
+    /// Whether the type is a texture, i.e. encoded as text in data URLs.
+    pub texture: bool,
+    pub category: AssetKind,
+    pub source: DbSource,
+}
+
+impl DbEntry {
+    fn new(source: DbSource, ext: &'static str, mime: &'static str) -> Self {
+        let parsed =
+            mime::Mime::from_str(mime).expect("The built-in tables only hold valid MIME types");
+        Self {
+            ext,
+            mime,
+            texture: is_texture_mime(&parsed),
+            category: AssetKind::of(&parsed),
+            source,
+        }
+    }
+}
+
+/// Returns every entry of the database, extensions first, each table sorted by key.
+pub fn entries() -> impl Iterator<Item = DbEntry> {
+    let mut entries: Vec<DbEntry> = MIME_TYPES
+        .entries()
+        .chain(COMPOUND_EXTENSIONS.entries())
+        .map(|(ext, mime)| DbEntry::new(DbSource::Extension, ext, mime))
+        .chain(
+            DOTFILES
+                .entries()
+                .map(|(name, mime)| DbEntry::new(DbSource::Dotfile, name, mime)),
+        )
+        .collect();
+    entries.sort_by_key(|entry| (entry.source, entry.ext));
+    entries.into_iter()
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(
+    feature = "serde",
+    doc = "Serializes `entries()` as a pretty-printed JSON array. The output is stable: the order only changes when the tables do."
+)]
+pub fn to_json() -> alloc::string::String {
+    let entries: Vec<DbEntry> = entries().collect();
+    serde_json::to_string_pretty(&entries).expect("Serializing the database can't fail")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn covers_both_tables() {
+        let entries: Vec<DbEntry> = entries().collect();
+        assert_eq!(
+            entries.len(),
+            MIME_TYPES.len() + COMPOUND_EXTENSIONS.len() + DOTFILES.len()
+        );
+        assert!(entries
+            .windows(2)
+            .all(|pair| (pair[0].source, pair[0].ext) < (pair[1].source, pair[1].ext)));
+
+        let json = entries.iter().find(|entry| entry.ext == "json").unwrap();
+        assert_eq!(json.mime, "application/json");
+        assert!(json.texture);
+        assert_eq!(json.category, AssetKind::Text);
+        assert_eq!(json.source, DbSource::Extension);
+
+        let babelrc = entries
+            .iter()
+            .find(|entry| entry.ext == ".babelrc")
+            .unwrap();
+        assert_eq!(babelrc.mime, "application/json");
+        assert_eq!(babelrc.source, DbSource::Dotfile);
+    }
+
+    /// Every entry is what the light guessers return, so the export can't list a type they don't know.
+    #[cfg(feature = "std")]
+    #[test]
+    fn matches_the_guessers() {
+        use crate::Mime;
+        use std::path::Path;
+
+        for entry in entries() {
+            let guessed = match entry.source {
+                DbSource::Extension => Mime::from_ext_light(entry.ext),
+                DbSource::Dotfile => Mime::from_path_light(Path::new(entry.ext)),
+            };
+            assert_eq!(guessed.unwrap(), entry.mime, "{}", entry.ext);
+        }
+        assert!(entries().all(|entry| entry.ext != "Makefile"));
+    }
+
+    #[test]
+    fn asset_kinds() {
+        assert_eq!(AssetKind::of(&mime::IMAGE_SVG), AssetKind::Image);
+        assert_eq!(AssetKind::of(&mime::FONT_WOFF2), AssetKind::Font);
+        assert_eq!(AssetKind::of(&mime::APPLICATION_PDF), AssetKind::Other);
+        assert_eq!(AssetKind::of(&mime::TEXT_CSS), AssetKind::Text);
+    }
+
+    /// Table edits show up as a diff of `tests/snapshots/db.json`. Run with `UPDATE_SNAPSHOTS=1` to accept them.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_snapshot() {
+        const SNAPSHOT: &str = "tests/snapshots/db.json";
+
+        let json = to_json() + "\n";
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(SNAPSHOT, &json).unwrap();
+        }
+        assert_eq!(json, std::fs::read_to_string(SNAPSHOT).unwrap());
+    }
+}
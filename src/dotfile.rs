@@ -0,0 +1,114 @@
+#![cfg(any(feature = "extension", feature = "extension-light"))]
+//! Well-known dotfiles, whose whole name `Path::extension` takes for a stem.
+
+use phf::{phf_map, Map};
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::string::String;
+#[cfg(feature = "std")]
+use std::sync::{LazyLock, PoisonError, RwLock};
+
+/// The built-in table. It is fixed at compile time, like the extension tables; `register` adds to it at runtime.
+pub static DOTFILES: Map<&'static str, &'static str> = phf_map! {
+    // Ignore lists and attributes
+    ".gitignore" => "text/plain",
+    ".gitattributes" => "text/plain",
+    ".gitmodules" => "text/plain",
+    ".dockerignore" => "text/plain",
+    ".npmignore" => "text/plain",
+    ".prettierignore" => "text/plain",
+    ".eslintignore" => "text/plain",
+    // Settings
+    ".env" => "text/plain",
+    ".npmrc" => "text/plain",
+    ".yarnrc" => "text/plain",
+    ".nvmrc" => "text/plain",
+    ".editorconfig" => "text/plain",
+    ".htaccess" => "text/plain",
+    ".browserslistrc" => "text/plain",
+    // JSON settings without an extension
+    ".babelrc" => "application/json",
+    ".eslintrc" => "application/json",
+    ".prettierrc" => "application/json",
+    ".jshintrc" => "application/json",
+    ".swcrc" => "application/json",
+};
+
+/// The dotfiles registered at runtime, keyed by their lowercase name.
+#[cfg(feature = "std")]
+static ADDED: LazyLock<RwLock<HashMap<String, &'static str>>> = LazyLock::new(Default::default);
+
+/// Registers a dotfile, overriding the built-in table. The name must be a dotfile and the type must be valid.
+#[cfg(feature = "std")]
+pub fn register(name: &str, mime: &'static str) {
+    debug_assert!(is_dotfile(name));
+    ADDED
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(name.to_ascii_lowercase(), mime);
+}
+
+/// Looks up a dotfile by its file name, case-insensitively. Registered dotfiles come first.
+pub fn mime_type_by_name(name: &str) -> Option<&'static str> {
+    #[cfg(feature = "std")]
+    {
+        let added = ADDED.read().unwrap_or_else(PoisonError::into_inner);
+        if !added.is_empty() {
+            if let Some(mime) = added.get(name.to_ascii_lowercase().as_str()) {
+                return Some(mime);
+            }
+        }
+    }
+    match DOTFILES.get(name) {
+        Some(mime) => Some(*mime),
+        None if name.bytes().any(|byte| byte.is_ascii_uppercase()) => {
+            DOTFILES.get(name.to_ascii_lowercase().as_str()).copied()
+        }
+        None => None,
+    }
+}
+
+/// Returns `true` for a name starting with `.` that has no other `.`, e.g. `.env` but not `.eslintrc.json`, `.` or `..`.
+pub fn is_dotfile(name: &str) -> bool {
+    name.strip_prefix('.')
+        .is_some_and(|rest| !rest.is_empty() && !rest.contains('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known() {
+        assert_eq!(mime_type_by_name(".babelrc"), Some("application/json"));
+        assert_eq!(mime_type_by_name(".GitIgnore"), Some("text/plain"));
+        assert_eq!(mime_type_by_name(".unknownrc"), None);
+        assert_eq!(mime_type_by_name("gitignore"), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn registered() {
+        assert_eq!(mime_type_by_name(".dotfile-test-rc"), None);
+        register(".Dotfile-Test-RC", "application/toml");
+        assert_eq!(
+            mime_type_by_name(".dotfile-test-rc"),
+            Some("application/toml")
+        );
+        assert_eq!(
+            mime_type_by_name(".DOTFILE-TEST-RC"),
+            Some("application/toml")
+        );
+    }
+
+    #[test]
+    fn dotfiles() {
+        assert!(is_dotfile(".env"));
+        assert!(!is_dotfile(".eslintrc.json"));
+        assert!(!is_dotfile("."));
+        assert!(!is_dotfile(".."));
+        assert!(!is_dotfile("env"));
+    }
+}
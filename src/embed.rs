@@ -0,0 +1,85 @@
+#![cfg(feature = "embed")]
+//! Embedding small assets as data URLs. The file is included into the binary with `include_bytes!` at compile time, and encoded with the same logic as `Dataurl::from_path` the first time it is used, so the result is always identical to the runtime encoding.
+//!
+//! # Example
+//!
+//! ```rust
+//! let logo: &'static str = mime_more::dataurl!("tests/fixtures/logo.svg");
+//! assert!(logo.starts_with("data:image/svg+xml;charset=utf-8,"));
+//! ```
+
+use crate::utils::get_extension;
+use crate::{from_ext_and_content, Dataurl};
+use alloc::string::String;
+use std::path::Path;
+
+/// Encodes embedded data as a data URL, guessing the MIME type like `Dataurl::from_path`. Used by the `dataurl!` macro.
+#[doc(hidden)]
+pub fn __encode(path: &str, data: &'static [u8]) -> String {
+    let ext = get_extension(Path::new(path)).unwrap_or_default();
+    let mime = from_ext_and_content(ext, data)
+        .expect("Guessing the MIME type of an embedded file can't fail");
+    Dataurl::new(mime, data.to_vec())
+        .expect("Guessed MIME types are never wildcards")
+        .to_string()
+}
+
+/// Embeds a file as a data URL, evaluating to a `&'static str`. The path is relative to the `CARGO_MANIFEST_DIR` of the calling crate, and a missing file is a compile error naming the path.
+///
+/// ```rust,compile_fail
+/// let missing: &'static str = mime_more::dataurl!("does/not/exist.png");
+/// ```
+#[macro_export]
+macro_rules! dataurl {
+    ($path:literal) => {{
+        static DATAURL: ::std::sync::LazyLock<::std::string::String> =
+            ::std::sync::LazyLock::new(|| {
+                $crate::embed::__encode(
+                    $path,
+                    ::core::include_bytes!(::core::concat!(
+                        ::core::env!("CARGO_MANIFEST_DIR"),
+                        "/",
+                        $path
+                    )),
+                )
+            });
+        ::std::string::String::as_str(&DATAURL)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Dataurl;
+    use std::path::Path;
+
+    #[test]
+    fn embed_png() {
+        let embedded: &'static str = dataurl!("tests/fixtures/pixel.png");
+        let runtime = Dataurl::from_path(Path::new("tests/fixtures/pixel.png")).unwrap();
+        assert_eq!(embedded, runtime.to_string());
+        assert!(embedded.starts_with("data:image/png;base64,"));
+    }
+
+    #[test]
+    fn embed_svg() {
+        let embedded: &'static str = dataurl!("tests/fixtures/logo.svg");
+        let runtime = Dataurl::from_path(Path::new("tests/fixtures/logo.svg")).unwrap();
+        assert_eq!(embedded, runtime.to_string());
+        assert!(embedded.starts_with("data:image/svg+xml;charset=utf-8,"));
+    }
+
+    #[test]
+    fn embed_compound_extension() {
+        let embedded: &'static str = dataurl!("tests/fixtures/shapes.d.ts");
+        let runtime = Dataurl::from_path(Path::new("tests/fixtures/shapes.d.ts")).unwrap();
+        assert_eq!(embedded, runtime.to_string());
+        // Not `ts`, which would be an MPEG transport stream.
+        assert!(embedded.starts_with("data:text/typescript;charset=utf-8,"));
+    }
+
+    #[test]
+    fn embedded_once() {
+        let first = || dataurl!("tests/fixtures/logo.svg");
+        assert!(core::ptr::eq(first(), first()));
+    }
+}
@@ -0,0 +1,288 @@
+//! The error type of the guessing functions and the data URL parsers.
+
+use alloc::string::String;
+use core::fmt;
+
+/// Why a MIME type couldn't be guessed or parsed, or a data URL couldn't be read.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MimeError {
+    /// The extension is not in the consulted table.
+    UnknownExtension(String),
+    /// The extension is empty, e.g. `.` or only whitespace.
+    EmptyExtension,
+    /// The path has no extension.
+    #[cfg(feature = "std")]
+    NoExtension(std::path::PathBuf),
+    /// The extension is not valid UTF-8, so it can't be in any table.
+    #[cfg(feature = "std")]
+    UndecodableExtension(std::ffi::OsString),
+    /// The filename has no extension, e.g. `LICENSE`, `.gitignore` or `photo.`.
+    NoFilenameExtension(String),
+    /// The name to register isn't a dotfile, e.g. `.eslintrc.json`, which has an extension.
+    NotADotfile(String),
+    /// The path of the URL has no extension, e.g. `https://example.com/` or `/download?file=logo.png`.
+    NoUrlExtension(String),
+    /// No magic number matched the content.
+    InferenceFailed,
+    /// The string is not a valid MIME type.
+    Parse(mime::FromStrError),
+    /// Reading a file failed.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// A media range such as `image/*` was given where a concrete type is required.
+    Wildcard(String),
+    /// The type is well-formed but not a known IANA registration.
+    Unregistered(String),
+    /// A header line names another header than `Content-Type`.
+    UnexpectedHeader(String),
+    /// The data URL is malformed. The payload names the part at fault.
+    InvalidDataurl(&'static str),
+    /// A percent-encoded data URL has a `%` that doesn't start an escape of two hex digits. The payload is its byte offset in the data URL.
+    InvalidPercentEscape(usize),
+}
+
+impl fmt::Display for MimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownExtension(ext) => write!(f, "No mime type found for extension: {}", ext),
+            Self::EmptyExtension => f.write_str("Empty extension"),
+            #[cfg(feature = "std")]
+            Self::NoExtension(path) => write!(f, "No extension found for path: {:?}", path),
+            #[cfg(feature = "std")]
+            Self::UndecodableExtension(ext) => {
+                write!(f, "Extension is not valid UTF-8: {:?}", ext)
+            }
+            Self::NoFilenameExtension(name) => {
+                write!(f, "No extension found for filename: {:?}", name)
+            }
+            Self::NotADotfile(name) => write!(f, "Not a dotfile: {:?}", name),
+            Self::NoUrlExtension(url) => write!(f, "No extension found for URL: {:?}", url),
+            Self::InferenceFailed => f.write_str("Failed to infer mime type"),
+            Self::Parse(err) => write!(f, "Invalid mime type: {}", err),
+            #[cfg(feature = "std")]
+            Self::Io(err) => err.fmt(f),
+            Self::Wildcard(range) => {
+                write!(f, "Expected a concrete mime type, got the range: {}", range)
+            }
+            Self::Unregistered(essence) => {
+                write!(f, "Not a registered IANA media type: {}", essence)
+            }
+            Self::UnexpectedHeader(name) => {
+                write!(f, "Expected a Content-Type header, got: {}", name)
+            }
+            Self::InvalidDataurl(part) => write!(f, "Invalid dataurl: {}", part),
+            Self::InvalidPercentEscape(offset) => {
+                write!(
+                    f,
+                    "Invalid dataurl: malformed percent escape at offset {}",
+                    offset
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MimeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse(err) => Some(err),
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<mime::FromStrError> for MimeError {
+    fn from(err: mime::FromStrError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for MimeError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mime;
+    use alloc::string::ToString;
+    use core::str::FromStr;
+
+    #[cfg(feature = "extension-light")]
+    #[test]
+    fn unknown_extension() {
+        let err = Mime::from_ext_light("xyzzy").unwrap_err();
+        assert!(matches!(&err, MimeError::UnknownExtension(ext) if ext == "xyzzy"));
+        assert_eq!(err.to_string(), "No mime type found for extension: xyzzy");
+    }
+
+    #[cfg(all(feature = "std", feature = "extension-light"))]
+    #[test]
+    fn no_extension() {
+        let path = std::path::Path::new("assets/Makefile");
+        assert!(matches!(
+            Mime::from_path_light(path),
+            Err(MimeError::NoExtension(missing)) if missing == path
+        ));
+    }
+
+    #[cfg(all(unix, feature = "std", feature = "extension-light"))]
+    #[test]
+    fn undecodable_extension() {
+        use std::os::unix::ffi::OsStrExt;
+        let path = std::path::Path::new(std::ffi::OsStr::from_bytes(b"caf\xe9.p\xe9ng"));
+        let err = Mime::from_path_light(path).unwrap_err();
+        assert!(
+            matches!(&err, MimeError::UndecodableExtension(ext) if ext.as_bytes() == b"p\xe9ng")
+        );
+        assert_eq!(
+            err.to_string(),
+            "Extension is not valid UTF-8: \"p\\xE9ng\""
+        );
+    }
+
+    #[cfg(feature = "extension-light")]
+    #[test]
+    fn no_filename_extension() {
+        let err = Mime::from_filename_light("uploads\\LICENSE").unwrap_err();
+        assert!(matches!(&err, MimeError::NoFilenameExtension(name) if name == "uploads\\LICENSE"));
+        assert_eq!(
+            err.to_string(),
+            "No extension found for filename: \"uploads\\\\LICENSE\""
+        );
+    }
+
+    #[cfg(feature = "extension-light")]
+    #[test]
+    fn no_url_extension() {
+        let err = Mime::from_url_light("/download?file=logo.png").unwrap_err();
+        assert!(matches!(&err, MimeError::NoUrlExtension(url) if url == "/download?file=logo.png"));
+        assert_eq!(
+            err.to_string(),
+            "No extension found for URL: \"/download?file=logo.png\""
+        );
+    }
+
+    #[cfg(feature = "magic")]
+    #[test]
+    fn inference_failed() {
+        assert!(matches!(
+            Mime::from_content(b"plain words"),
+            Err(MimeError::InferenceFailed)
+        ));
+    }
+
+    #[test]
+    fn parse() {
+        let err = Mime::from_str("not a mime").unwrap_err();
+        assert!(matches!(err, MimeError::Parse(_)));
+        assert!(err.to_string().starts_with("Invalid mime type: "));
+    }
+
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[test]
+    fn io() {
+        use std::error::Error;
+        let err = Mime::from_file(std::path::Path::new("does/not/exist.png")).unwrap_err();
+        let MimeError::Io(io) = &err else {
+            panic!("expected an IO error, got {err:?}");
+        };
+        assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+        assert!(err.source().is_some());
+    }
+
+    #[test]
+    fn wildcard() {
+        let err = Mime::from_concrete_str("image/*").unwrap_err();
+        assert!(matches!(&err, MimeError::Wildcard(range) if range == "image/*"));
+        assert_eq!(
+            err.to_string(),
+            "Expected a concrete mime type, got the range: image/*"
+        );
+    }
+
+    #[cfg(feature = "iana")]
+    #[test]
+    fn unregistered() {
+        let err = Mime::from_str_strict("Application/X-My-Thing; a=b").unwrap_err();
+        assert!(
+            matches!(&err, MimeError::Unregistered(essence) if essence == "application/x-my-thing")
+        );
+        assert_eq!(
+            err.to_string(),
+            "Not a registered IANA media type: application/x-my-thing"
+        );
+    }
+
+    #[test]
+    fn unexpected_header() {
+        let err = Mime::from_header("Content-Disposition: inline").unwrap_err();
+        assert!(matches!(&err, MimeError::UnexpectedHeader(name) if name == "Content-Disposition"));
+        assert_eq!(
+            err.to_string(),
+            "Expected a Content-Type header, got: Content-Disposition"
+        );
+    }
+
+    #[cfg(feature = "dataurl")]
+    #[test]
+    fn invalid_dataurl() {
+        use crate::dataurl::{Dataurl, DataurlRef};
+        for (input, part) in [
+            ("http://example.com", "scheme"),
+            ("data:image/png", "media type"),
+            ("data:image/png;base64", "separator"),
+            ("data:image/png;base32,AAAA", "charset"),
+            ("data:image/png;base64,!!!", "base64 payload"),
+            ("data:image/png;base64,!!!notbase64!!!", "base64 payload"),
+            ("data:image/png;base64,AAA", "base64 payload"),
+            ("data:text/plain,caf%E9", "percent-encoded payload"),
+            ("data:text/plain,%FF%FE", "percent-encoded payload"),
+        ] {
+            assert!(
+                matches!(Dataurl::from_str(input), Err(MimeError::InvalidDataurl(found)) if found == part),
+                "{input}"
+            );
+        }
+        assert!(matches!(
+            DataurlRef::parse("data:text/plain;base64"),
+            Err(MimeError::InvalidDataurl("separator"))
+        ));
+        assert!(matches!(
+            Dataurl::from_str("data:not a mime;base64,AAAA"),
+            Err(MimeError::Parse(_))
+        ));
+    }
+
+    #[cfg(feature = "dataurl")]
+    #[test]
+    fn invalid_percent_escape() {
+        use crate::dataurl::Dataurl;
+        for (input, offset) in [
+            ("data:text/plain;charset=utf-8,%E0%A4%A", 36),
+            ("data:text/plain,100%", 19),
+            ("data:text/plain,%ZZ", 16),
+            ("data: text/plain,ok%2", 19),
+        ] {
+            let err = Dataurl::from_str(input).unwrap_err();
+            assert!(
+                matches!(err, MimeError::InvalidPercentEscape(found) if found == offset),
+                "{input}"
+            );
+            assert_eq!(&input[offset..offset + 1], "%");
+            assert_eq!(
+                err.to_string(),
+                alloc::format!("Invalid dataurl: malformed percent escape at offset {offset}")
+            );
+        }
+    }
+}
@@ -0,0 +1,218 @@
+#![cfg(feature = "ffi")]
+//! A C ABI for embedding the crate in non-Rust tools. The header is generated by [`cbindgen`](https://github.com/mozilla/cbindgen) from `cbindgen.toml` into `include/mime_more.h`.
+//!
+//! The crate is built as an `rlib` by default. Where the C bindings are packaged, build the library with `cargo rustc --lib --release --features ffi --crate-type staticlib`, or `--crate-type cdylib` for a shared library, and link against it.
+//!
+//! Every function returns a newly allocated, NUL-terminated string, or null when the input is null, invalid, or can't be guessed. Returned strings must be released with `mime_more_string_free`. Panics are caught at each entry point and reported as null, so they never unwind across the boundary.
+//!
+//! # Example
+//!
+//! ```c
+//! #include "mime_more.h"
+//!
+//! char *mime = mime_more_from_ext("png");
+//! if (mime != NULL) {
+//!     puts(mime); // image/png
+//!     mime_more_string_free(mime);
+//! }
+//! ```
+
+use crate::{Dataurl, Mime};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, UnwindSafe};
+use std::str::FromStr;
+
+/// Runs `f` without letting a panic escape, converting the result into an owned C string.
+fn guarded(f: impl FnOnce() -> Option<String> + UnwindSafe) -> *mut c_char {
+    catch_unwind(f)
+        .ok()
+        .flatten()
+        .and_then(|s| CString::new(s).ok())
+        .map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Borrows a C string as UTF-8, or `None` when it is null or not valid UTF-8.
+///
+/// # Safety
+///
+/// `s` must be null or point to a valid NUL-terminated string.
+unsafe fn borrow_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        None
+    } else {
+        CStr::from_ptr(s).to_str().ok()
+    }
+}
+
+/// Borrows a byte buffer, or `None` when it is null. A zero length is always accepted.
+///
+/// # Safety
+///
+/// `data` must be null or point to at least `len` readable bytes.
+unsafe fn borrow_bytes<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if len == 0 {
+        Some(&[])
+    } else if data.is_null() {
+        None
+    } else {
+        Some(std::slice::from_raw_parts(data, len))
+    }
+}
+
+/// Guesses the MIME type from the extension (without the leading dot).
+///
+/// # Safety
+///
+/// `ext` must be null or point to a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn mime_more_from_ext(ext: *const c_char) -> *mut c_char {
+    let Some(ext) = borrow_str(ext) else {
+        return std::ptr::null_mut();
+    };
+    guarded(|| {
+        #[cfg(feature = "extension")]
+        if let Ok(guessed) = Mime::from_ext(ext) {
+            return Some(guessed.to_string());
+        }
+
+        Mime::from_ext_light(ext).ok().map(|mime| mime.to_string())
+    })
+}
+
+/// Infers the MIME type from the magic number of `len` bytes at `data`.
+///
+/// # Safety
+///
+/// `data` must be null or point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mime_more_from_content(data: *const u8, len: usize) -> *mut c_char {
+    let Some(data) = borrow_bytes(data, len) else {
+        return std::ptr::null_mut();
+    };
+    guarded(|| Mime::from_content(data).ok().map(|mime| mime.to_string()))
+}
+
+/// Encodes `len` bytes at `data` as a data URL. When `mime` is null, the MIME type is inferred from the content.
+///
+/// # Safety
+///
+/// `mime` must be null or point to a valid NUL-terminated string, and `data` must be null or point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn mime_more_dataurl_encode(
+    mime: *const c_char,
+    data: *const u8,
+    len: usize,
+) -> *mut c_char {
+    let Some(data) = borrow_bytes(data, len) else {
+        return std::ptr::null_mut();
+    };
+    let mime = if mime.is_null() {
+        None
+    } else {
+        match borrow_str(mime) {
+            Some(mime) => Some(mime),
+            None => return std::ptr::null_mut(),
+        }
+    };
+    guarded(|| {
+        let dataurl = match mime {
+            Some(mime) => Dataurl::new(Mime::from_str(mime).ok()?, data.to_vec()).ok()?,
+            None => Dataurl::from_data(data.to_vec()).ok()?,
+        };
+        Some(dataurl.to_string())
+    })
+}
+
+/// Releases a string returned by any `mime_more_*` function. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `s` must be null or a pointer returned by this library that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn mime_more_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Takes ownership of a returned string, freeing it through the C entry point.
+    fn take(s: *mut c_char) -> Option<String> {
+        if s.is_null() {
+            return None;
+        }
+        let owned = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+        unsafe { mime_more_string_free(s) };
+        Some(owned)
+    }
+
+    #[test]
+    fn from_ext() {
+        assert_eq!(
+            take(unsafe { mime_more_from_ext(c"png".as_ptr()) }).as_deref(),
+            Some("image/png")
+        );
+        assert_eq!(
+            take(unsafe { mime_more_from_ext(c"unknown".as_ptr()) }),
+            None
+        );
+        assert_eq!(take(unsafe { mime_more_from_ext(std::ptr::null()) }), None);
+    }
+
+    #[test]
+    fn from_ext_invalid_utf8() {
+        let ext = [0xFF, 0xFE, 0x00];
+        assert_eq!(
+            take(unsafe { mime_more_from_ext(ext.as_ptr().cast()) }),
+            None
+        );
+    }
+
+    #[test]
+    fn from_content() {
+        let data = [
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x11, 0x45, 0x14, 0x19, 0x19, 0x81, 0x0,
+        ];
+        assert_eq!(
+            take(unsafe { mime_more_from_content(data.as_ptr(), data.len()) }).as_deref(),
+            Some("image/png")
+        );
+        assert_eq!(
+            take(unsafe { mime_more_from_content(std::ptr::null(), 4) }),
+            None
+        );
+        assert_eq!(
+            take(unsafe { mime_more_from_content(std::ptr::null(), 0) }),
+            None
+        );
+    }
+
+    #[test]
+    fn dataurl_encode() {
+        let data = [
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x11, 0x45, 0x14, 0x19, 0x19, 0x81, 0x0,
+        ];
+        assert_eq!(
+            take(unsafe { mime_more_dataurl_encode(std::ptr::null(), data.as_ptr(), data.len()) })
+                .as_deref(),
+            Some("data:image/png;base64,iVBORw0KGgoRRRQZGYEA")
+        );
+        assert_eq!(
+            take(unsafe { mime_more_dataurl_encode(c"text/plain".as_ptr(), b"Hi".as_ptr(), 2) })
+                .as_deref(),
+            Some("data:text/plain;charset=utf-8,Hi")
+        );
+        assert_eq!(
+            take(unsafe { mime_more_dataurl_encode(c"not a mime".as_ptr(), b"Hi".as_ptr(), 2) }),
+            None
+        );
+    }
+
+    #[test]
+    fn string_free_null() {
+        unsafe { mime_more_string_free(std::ptr::null_mut()) };
+    }
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "arbitrary")]
+//! [`Arbitrary`](https://docs.rs/arbitrary/latest/arbitrary/trait.Arbitrary.html) implementations for fuzzing and property tests. They only generate values that parse: lowercase RFC 6838 names, an optional structured syntax suffix, and up to two parameters whose values are tokens or quoted strings.
+
+#[cfg(feature = "dataurl")]
+use crate::dataurl::{Dataurl, DataurlCharset};
+use crate::Mime;
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+use core::str::FromStr;
+
+const TOP_LEVEL_TYPES: &[&str] = &[
+    "application",
+    "audio",
+    "font",
+    "image",
+    "message",
+    "model",
+    "multipart",
+    "text",
+    "video",
+];
+const SUFFIXES: &[&str] = &["json", "xml", "zip", "gzip", "cbor"];
+
+const LOWER_ALPHANUMERIC: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+/// The `restricted-name-chars` of RFC 6838, without `+`, which starts the suffix.
+const NAME_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789!#$&-^_.";
+const TOKEN_CHARS: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$&-^_.+~";
+/// Commas and semicolons are included, as they must not split quoted values.
+const QUOTED_CHARS: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789 ,;=/:.-";
+
+fn word(u: &mut Unstructured<'_>, first: &[u8], rest: &[u8], max_len: usize) -> Result<String> {
+    let len = u.int_in_range(1..=max_len)?;
+    let mut word = String::with_capacity(len);
+    word.push(char::from(*u.choose(first)?));
+    for _ in 1..len {
+        word.push(char::from(*u.choose(rest)?));
+    }
+    Ok(word)
+}
+
+impl<'a> Arbitrary<'a> for Mime {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let type_ = if u.ratio(3, 4)? {
+            String::from(*u.choose(TOP_LEVEL_TYPES)?)
+        } else {
+            word(u, LOWER_ALPHANUMERIC, NAME_CHARS, 12)?
+        };
+        let mut mime = type_ + "/" + &word(u, LOWER_ALPHANUMERIC, NAME_CHARS, 24)?;
+        if u.ratio(1, 4)? {
+            mime.push('+');
+            mime.push_str(u.choose(SUFFIXES)?);
+        }
+        for _ in 0..u.int_in_range(0..=2)? {
+            mime.push_str("; ");
+            mime.push_str(&word(u, LOWER_ALPHANUMERIC, NAME_CHARS, 8)?);
+            mime.push('=');
+            if u.arbitrary()? {
+                mime.push('"');
+                mime.push_str(&word(u, QUOTED_CHARS, QUOTED_CHARS, 16)?);
+                mime.push('"');
+            } else {
+                mime.push_str(&word(u, TOKEN_CHARS, TOKEN_CHARS, 16)?);
+            }
+        }
+        Mime::from_str(&mime).map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+#[cfg(feature = "dataurl")]
+impl<'a> Arbitrary<'a> for DataurlCharset {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(bool::arbitrary(u)?.into())
+    }
+}
+
+/// Both charsets are generated for any type, but the payload of a `Utf8` data URL is always valid UTF-8, as `Display` requires.
+#[cfg(feature = "dataurl")]
+impl<'a> Arbitrary<'a> for Dataurl {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mime = Mime::arbitrary(u)?;
+        let charset = DataurlCharset::arbitrary(u)?;
+        let data = match charset {
+            DataurlCharset::Utf8 => String::arbitrary(u)?.into_bytes(),
+            DataurlCharset::Base64 => Vec::arbitrary(u)?,
+        };
+        Ok(Self {
+            mime,
+            data: data.into(),
+            charset,
+        })
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use proptest::prelude::{any, prop_assert, prop_assert_eq, proptest};
+
+    /// Feeds random bytes to `Arbitrary`, skipping the inputs it can't build a value from.
+    fn build<T: for<'a> Arbitrary<'a>>(bytes: &[u8]) -> Option<T> {
+        T::arbitrary(&mut Unstructured::new(bytes)).ok()
+    }
+
+    proptest! {
+        #[test]
+        fn mime_round_trips(bytes in proptest::collection::vec(any::<u8>(), 0..256)) {
+            if let Some(mime) = build::<Mime>(&bytes) {
+                prop_assert!(!mime.is_wildcard());
+                prop_assert_eq!(Mime::from_str(&mime.to_string()).unwrap(), mime);
+            }
+        }
+
+        #[cfg(feature = "dataurl")]
+        #[test]
+        fn dataurl_round_trips(bytes in proptest::collection::vec(any::<u8>(), 0..1024)) {
+            if let Some(dataurl) = build::<Dataurl>(&bytes) {
+                let displayed = dataurl.to_string();
+                prop_assert_eq!(Dataurl::from_str(&displayed).unwrap(), dataurl);
+            }
+        }
+    }
+
+    #[cfg(feature = "dataurl")]
+    #[test]
+    fn both_charsets() {
+        let charsets: Vec<_> = (0..=255u8)
+            .filter_map(|byte| build::<Dataurl>(&[byte; 64]))
+            .map(|dataurl| dataurl.charset)
+            .collect();
+        assert!(charsets.contains(&DataurlCharset::Utf8));
+        assert!(charsets.contains(&DataurlCharset::Base64));
+    }
+}
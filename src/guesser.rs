@@ -0,0 +1,273 @@
+//! A builder for guessing a MIME type from several sources, with an explicit priority.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[cfg(all(feature = "magic", feature = "extension-light"))]
+//! # {
+//! use mime_more::{Mime, MimeGuesser};
+//!
+//! let png = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+//! let guessed = MimeGuesser::new()
+//!     .prefer_content()
+//!     .with_extension("txt")
+//!     .with_data(&png)
+//!     .with_fallback(Mime::default())
+//!     .guess();
+//! assert_eq!(guessed, Mime::IMAGE_PNG);
+//! # }
+//! ```
+
+use crate::Mime;
+
+/// Guesses a MIME type from an extension and content, consulting the sources in the order it was told to prefer.
+///
+/// The sources are the `mime_guess` table (`extension` feature), the light table (`extension-light` feature), magic numbers (`magic` feature) and the texture check (`texture` feature). Every compiled-in source is enabled by default and can be disabled individually, e.g. to keep a bundler on the zero-dependency light table. The heavy table is always consulted before the light one, and the texture check always comes last, since it only tells that the content is some text.
+///
+/// By default the extension is preferred over the content, as in `from_ext_and_content`.
+#[derive(Debug, Clone)]
+pub struct MimeGuesser<'a> {
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    ext: Option<&'a str>,
+    /// The file name of a path without an extension, looked up as a dotfile.
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    dotfile: Option<&'a str>,
+    #[cfg(any(feature = "magic", feature = "texture"))]
+    data: Option<&'a [u8]>,
+    fallback: Mime,
+    prefer_content: bool,
+    #[cfg(feature = "extension")]
+    extension: bool,
+    #[cfg(feature = "extension-light")]
+    extension_light: bool,
+    #[cfg(feature = "magic")]
+    magic: bool,
+    #[cfg(feature = "texture")]
+    texture: bool,
+    #[cfg(not(any(
+        feature = "extension",
+        feature = "extension-light",
+        feature = "magic",
+        feature = "texture"
+    )))]
+    _marker: core::marker::PhantomData<&'a ()>,
+}
+
+impl Default for MimeGuesser<'_> {
+    fn default() -> Self {
+        Self {
+            #[cfg(any(feature = "extension", feature = "extension-light"))]
+            ext: None,
+            #[cfg(any(feature = "extension", feature = "extension-light"))]
+            dotfile: None,
+            #[cfg(any(feature = "magic", feature = "texture"))]
+            data: None,
+            fallback: Mime::default(),
+            prefer_content: false,
+            #[cfg(feature = "extension")]
+            extension: true,
+            #[cfg(feature = "extension-light")]
+            extension_light: true,
+            #[cfg(feature = "magic")]
+            magic: true,
+            #[cfg(feature = "texture")]
+            texture: true,
+            #[cfg(not(any(
+                feature = "extension",
+                feature = "extension-light",
+                feature = "magic",
+                feature = "texture"
+            )))]
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a> MimeGuesser<'a> {
+    /// Creates a guesser with every compiled-in source enabled, preferring the extension and falling back to `application/octet-stream`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consults the content (magic numbers) before the extension.
+    pub fn prefer_content(mut self) -> Self {
+        self.prefer_content = true;
+        self
+    }
+
+    /// Consults the extension before the content. It is the default.
+    pub fn prefer_extension(mut self) -> Self {
+        self.prefer_content = false;
+        self
+    }
+
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    #[cfg_attr(
+        any(feature = "extension", feature = "extension-light"),
+        doc = "Sets the extension to guess from, normalized like `Mime::from_ext`."
+    )]
+    pub fn with_extension(mut self, ext: &'a str) -> Self {
+        self.ext = Some(ext);
+        self
+    }
+
+    #[cfg(all(
+        feature = "std",
+        any(feature = "extension", feature = "extension-light")
+    ))]
+    #[cfg_attr(
+        all(
+            feature = "std",
+            any(feature = "extension", feature = "extension-light")
+        ),
+        doc = "Sets the extension from a path like `Mime::from_path` does: a compound extension such as `tar.gz` is preferred, and a path without an extension is looked up as a well-known dotfile, e.g. `.babelrc`."
+    )]
+    pub fn with_path(mut self, path: &'a std::path::Path) -> Self {
+        self.ext = crate::utils::get_extension(path);
+        self.dotfile = match self.ext {
+            Some(_) => None,
+            None => path.file_name().and_then(|name| name.to_str()),
+        };
+        self
+    }
+
+    #[cfg(any(feature = "magic", feature = "texture"))]
+    #[cfg_attr(
+        any(feature = "magic", feature = "texture"),
+        doc = "Sets the content to guess from. A prefix of a few kilobytes is enough for every source."
+    )]
+    pub fn with_data(mut self, data: &'a [u8]) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Sets the type returned when no source matches. Defaults to `application/octet-stream`.
+    pub fn with_fallback(mut self, fallback: Mime) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    #[cfg(feature = "extension")]
+    #[cfg_attr(
+        feature = "extension",
+        doc = "Enables or disables the `mime_guess` table."
+    )]
+    pub fn extension(mut self, enabled: bool) -> Self {
+        self.extension = enabled;
+        self
+    }
+
+    #[cfg(feature = "extension-light")]
+    #[cfg_attr(
+        feature = "extension-light",
+        doc = "Enables or disables the light extension table."
+    )]
+    pub fn extension_light(mut self, enabled: bool) -> Self {
+        self.extension_light = enabled;
+        self
+    }
+
+    #[cfg(feature = "magic")]
+    #[cfg_attr(feature = "magic", doc = "Enables or disables magic number inference.")]
+    pub fn magic(mut self, enabled: bool) -> Self {
+        self.magic = enabled;
+        self
+    }
+
+    #[cfg(feature = "texture")]
+    #[cfg_attr(
+        feature = "texture",
+        doc = "Enables or disables the texture check, which guesses `text/plain` for textual content."
+    )]
+    pub fn texture(mut self, enabled: bool) -> Self {
+        self.texture = enabled;
+        self
+    }
+
+    /// Consults the enabled sources in order and returns the first match, or the fallback.
+    pub fn guess(&self) -> Mime {
+        let guessed = if self.prefer_content {
+            self.guess_magic().or_else(|| self.guess_extension())
+        } else {
+            self.guess_extension().or_else(|| self.guess_magic())
+        };
+        if let Some(guessed) = guessed.or_else(|| self.guess_texture()) {
+            return guessed;
+        }
+        debug!(source = "fallback", "fallback taken");
+        self.fallback.clone()
+    }
+
+    fn guess_extension(&self) -> Option<Mime> {
+        #[cfg(any(feature = "extension", feature = "extension-light"))]
+        let Some(ext) = self.ext
+        else {
+            return self.guess_dotfile();
+        };
+
+        #[cfg(feature = "extension")]
+        if self.extension {
+            if let Ok(guessed) = Mime::from_ext(ext) {
+                debug!(source = "extension", mime = %guessed, "extension table hit");
+                return Some(guessed);
+            } else {
+                trace!(source = "extension", "extension table miss");
+            }
+        }
+
+        #[cfg(feature = "extension-light")]
+        if self.extension_light {
+            if let Ok(guessed_light) = Mime::from_ext_light(ext) {
+                debug!(source = "extension-light", mime = %guessed_light, "light table hit");
+                return Some(guessed_light);
+            } else {
+                trace!(source = "extension-light", "light table miss");
+            }
+        }
+
+        None
+    }
+
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    fn guess_dotfile(&self) -> Option<Mime> {
+        #[cfg(all(feature = "extension", feature = "extension-light"))]
+        let enabled = self.extension || self.extension_light;
+        #[cfg(all(feature = "extension", not(feature = "extension-light")))]
+        let enabled = self.extension;
+        #[cfg(all(not(feature = "extension"), feature = "extension-light"))]
+        let enabled = self.extension_light;
+        if !enabled {
+            return None;
+        }
+        if let Some(guessed) = Mime::from_dotfile(self.dotfile?, false) {
+            debug!(source = "dotfile", mime = %guessed, "dotfile table hit");
+            return Some(guessed);
+        }
+        trace!(source = "dotfile", "dotfile table miss");
+        None
+    }
+
+    fn guess_magic(&self) -> Option<Mime> {
+        #[cfg(feature = "magic")]
+        if self.magic {
+            if let Ok(inferred) = Mime::from_content(self.data?) {
+                debug!(source = "magic", mime = %inferred, "magic matcher matched");
+                return Some(inferred);
+            } else {
+                trace!(source = "magic", "no magic matcher matched");
+            }
+        }
+
+        None
+    }
+
+    fn guess_texture(&self) -> Option<Mime> {
+        #[cfg(feature = "texture")]
+        if self.texture && crate::texture::is_texture_std(self.data?) {
+            debug!(source = "texture", "content is texture");
+            return Some(Mime::TEXT_PLAIN);
+        }
+
+        None
+    }
+}
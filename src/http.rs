@@ -0,0 +1,318 @@
+//! Helpers for HTTP headers that accompany a guessed `Content-Type`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::http::ContentDisposition;
+//!
+//! assert_eq!(
+//!     ContentDisposition::attachment("report.pdf"),
+//!     r#"attachment; filename="report.pdf""#
+//! );
+//! assert_eq!(
+//!     ContentDisposition::inline("报告.pdf"),
+//!     r#"inline; filename="__.pdf"; filename*=UTF-8''%E6%8A%A5%E5%91%8A.pdf"#
+//! );
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+/// The disposition type of a `Content-Disposition` header, as defined in [RFC 6266](https://www.rfc-editor.org/rfc/rfc6266).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContentDisposition {
+    Inline,
+    Attachment,
+}
+
+impl ContentDisposition {
+    /// Builds an `attachment` header value, which asks the browser to download the file with the given name.
+    pub fn attachment(filename: &str) -> String {
+        Self::Attachment.header_value(filename)
+    }
+
+    /// Builds an `inline` header value, which lets the browser display the file, keeping the given name for saving.
+    pub fn inline(filename: &str) -> String {
+        Self::Inline.header_value(filename)
+    }
+
+    /// Returns the disposition type as it appears in the header.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Inline => "inline",
+            Self::Attachment => "attachment",
+        }
+    }
+
+    /// Builds the header value for the given filename.
+    ///
+    /// Control characters are replaced by `_`. Printable ASCII names are written as a quoted `filename=` only. Other names also get a `filename*=UTF-8''...` parameter as per [RFC 5987](https://www.rfc-editor.org/rfc/rfc5987), with an ASCII `filename=` fallback in which every non-ASCII character is replaced by `_`.
+    pub fn header_value(self, filename: &str) -> String {
+        let mut value = String::with_capacity(filename.len() + 24);
+        value.push_str(self.as_str());
+        value.push_str("; filename=\"");
+        for c in filename.chars() {
+            match c {
+                '"' | '\\' => {
+                    value.push('\\');
+                    value.push(c);
+                }
+                ' '..='~' => value.push(c),
+                _ => value.push('_'),
+            }
+        }
+        value.push('"');
+
+        if filename.is_ascii() {
+            return value;
+        }
+
+        value.push_str("; filename*=UTF-8''");
+        for c in filename.chars() {
+            if c.is_control() {
+                value.push('_');
+            } else if c.is_ascii_alphanumeric() || "!#$&+-.^_`|~".contains(c) {
+                value.push(c);
+            } else {
+                let mut buf = [0; 4];
+                for byte in c.encode_utf8(&mut buf).bytes() {
+                    write!(value, "%{byte:02X}").unwrap();
+                }
+            }
+        }
+        value
+    }
+
+    /// Guesses the `Content-Type` of a file on disk via `Mime::from_file`, and builds the header value for its file name. Returns `(content_type, content_disposition)`.
+    ///
+    /// A file whose type can't be guessed is `application/octet-stream`. Fails with `MimeError::Io` if the file can't be read, or if the path has no file name, e.g. `..`.
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub fn for_file(
+        self,
+        path: &std::path::Path,
+    ) -> Result<(crate::Mime, String), crate::MimeError> {
+        let filename = path
+            .file_name()
+            .ok_or_else(|| {
+                crate::MimeError::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    std::format!("No file name found for path: {:?}", path),
+                ))
+            })?
+            .to_string_lossy();
+        let mime = match crate::Mime::from_file(path) {
+            Ok(mime) => mime,
+            Err(crate::MimeError::Io(err)) => return Err(crate::MimeError::Io(err)),
+            Err(_) => crate::Mime::APPLICATION_OCTET_STREAM,
+        };
+        Ok((mime, self.header_value(&filename)))
+    }
+}
+
+/// Returns `true` if the character is a `tchar` of [RFC 7230](https://www.rfc-editor.org/rfc/rfc7230#section-3.2.6).
+pub(crate) fn is_tchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+/// Appends a parameter value as read from `mime` (which keeps the backslash escapes of quoted values), as a token when possible and as a quoted string otherwise.
+///
+/// Control characters are dropped. Non-ASCII characters are written as `obs-text` unless `strict` is set, in which case they are an error.
+pub(crate) fn push_param_value(out: &mut String, raw: &str, strict: bool) -> anyhow::Result<()> {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        let c = if c == '\\' {
+            chars.next().unwrap_or(c)
+        } else {
+            c
+        };
+        if strict && !c.is_ascii() {
+            anyhow::bail!(
+                "Parameter value can't be represented in a header: {:?}",
+                raw
+            );
+        }
+        if c != '\t' && c.is_control() {
+            continue;
+        }
+        value.push(c);
+    }
+
+    if !value.is_empty() && value.chars().all(is_tchar) {
+        out.push_str(&value);
+        return Ok(());
+    }
+    out.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+    Ok(())
+}
+
+/// Finds the first `separator` outside quoted strings, skipping escaped quotes inside them.
+pub(crate) fn find_unquoted(value: &str, separator: char) -> Option<usize> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (index, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            _ if c == separator && !in_quotes => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits a header value on `separator`, except inside quoted strings, where it and escaped quotes are kept.
+pub(crate) fn split_unquoted(mut value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    while let Some(index) = find_unquoted(value, separator) {
+        parts.push(&value[..index]);
+        value = &value[index + separator.len_utf8()..];
+    }
+    parts.push(value);
+    parts
+}
+
+/// Rewrites a `Content-Type` header value into the shape `mime` parses: `essence; name=value; ...` without surrounding whitespace or empty parameters. Semicolons inside quoted values are kept.
+pub(crate) fn normalize_content_type(value: &str) -> String {
+    let mut normalized = String::with_capacity(value.len());
+    for segment in split_unquoted(value, ';') {
+        let segment = segment.trim();
+        if normalized.is_empty() {
+            normalized.push_str(segment);
+        } else if !segment.is_empty() {
+            normalized.push_str("; ");
+            normalized.push_str(segment);
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_type_normalization() {
+        assert_eq!(
+            normalize_content_type("  text/plain ; charset=utf-8 ;; "),
+            "text/plain; charset=utf-8"
+        );
+        assert_eq!(
+            normalize_content_type(r#"a/b;x="p; \"q\"";y=z"#),
+            r#"a/b; x="p; \"q\""; y=z"#
+        );
+    }
+
+    #[test]
+    fn unquoted_split() {
+        assert_eq!(
+            split_unquoted(r#"a/b; x="1,2", c/d;y="\",", e/f"#, ','),
+            ["a/b; x=\"1,2\"", r#" c/d;y="\",""#, " e/f"]
+        );
+        assert_eq!(split_unquoted("", ','), [""]);
+        assert_eq!(split_unquoted("a,", ','), ["a", ""]);
+    }
+
+    #[test]
+    fn ascii_name() {
+        assert_eq!(
+            ContentDisposition::attachment("report.pdf"),
+            r#"attachment; filename="report.pdf""#
+        );
+        assert_eq!(
+            ContentDisposition::inline("logo.png"),
+            r#"inline; filename="logo.png""#
+        );
+    }
+
+    #[test]
+    fn spaces_and_quotes() {
+        assert_eq!(
+            ContentDisposition::attachment(r#"my "final" report\v2.pdf"#),
+            r#"attachment; filename="my \"final\" report\\v2.pdf""#
+        );
+    }
+
+    #[test]
+    fn control_characters() {
+        assert_eq!(
+            ContentDisposition::attachment("evil\r\nSet-Cookie: a=b.txt"),
+            r#"attachment; filename="evil__Set-Cookie: a=b.txt""#
+        );
+    }
+
+    #[test]
+    fn cjk_name() {
+        assert_eq!(
+            ContentDisposition::attachment("年度 报告.pdf"),
+            r#"attachment; filename="__ __.pdf"; filename*=UTF-8''%E5%B9%B4%E5%BA%A6%20%E6%8A%A5%E5%91%8A.pdf"#
+        );
+    }
+
+    #[test]
+    fn param_values() {
+        let quote = |raw: &str, strict: bool| {
+            let mut out = String::new();
+            push_param_value(&mut out, raw, strict).map(|_| out)
+        };
+        assert_eq!(quote("utf-8", true).unwrap(), "utf-8");
+        assert_eq!(quote("a b", true).unwrap(), r#""a b""#);
+        assert_eq!(quote(r"a\\b", true).unwrap(), r#""a\\b""#);
+        assert_eq!(quote(r#"a\"b"#, true).unwrap(), r#""a\"b""#);
+        assert_eq!(quote("", true).unwrap(), r#""""#);
+        assert_eq!(quote("日本", false).unwrap(), r#""日本""#);
+        assert!(quote("日本", true).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn for_file() {
+        use crate::MimeError;
+
+        let (mime, disposition) = ContentDisposition::Attachment
+            .for_file(std::path::Path::new("Cargo.toml"))
+            .unwrap();
+        assert_eq!(disposition, r#"attachment; filename="Cargo.toml""#);
+        let expected = if cfg!(any(feature = "extension", feature = "extension-light")) {
+            "text/x-toml"
+        } else if cfg!(feature = "texture") {
+            "text/plain"
+        } else {
+            "application/octet-stream"
+        };
+        assert_eq!(mime, expected);
+
+        let (mime, _) = ContentDisposition::Inline
+            .for_file(std::path::Path::new("tests/fixtures/pixel"))
+            .unwrap();
+        let expected = if cfg!(feature = "magic") {
+            "image/png"
+        } else {
+            "application/octet-stream"
+        };
+        assert_eq!(mime, expected);
+
+        let missing = ContentDisposition::Attachment
+            .for_file(std::path::Path::new("tests/fixtures/missing.png"))
+            .unwrap_err();
+        assert!(
+            matches!(&missing, MimeError::Io(err) if err.kind() == std::io::ErrorKind::NotFound)
+        );
+        assert!(matches!(
+            ContentDisposition::Attachment.for_file(std::path::Path::new("..")),
+            Err(MimeError::Io(err)) if err.kind() == std::io::ErrorKind::InvalidInput
+        ));
+    }
+}
@@ -0,0 +1,498 @@
+#![cfg(feature = "iana")]
+//! The registered media types of the [IANA registry](https://www.iana.org/assignments/media-types/media-types.xhtml).
+//!
+//! The tables are checked in like the `extension-light` one, behind the `iana` feature. They hold the commonly used registrations of every top-level type rather than the whole registry, so `is_registered` returning `false` means "not a known registration" for an otherwise well-formed type.
+//!
+//! There is one table per top-level type, so that the cost of each is plain: 258 entries in all, of which 109 are `application`, 43 `image`, 28 `text`, 21 `video`, 15 `model`, 14 `audio`, 12 `multipart`, 10 `message` and 6 `font`. That is about 11 KB of read-only data, 4.5 KB of names and 24 bytes per entry, linked only with the feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::{iana::Tree, Mime};
+//! use std::str::FromStr;
+//!
+//! let json = Mime::from_str("application/json").unwrap();
+//! assert!(json.is_registered());
+//! assert_eq!(json.tree(), Tree::Standards);
+//!
+//! let custom = Mime::from_str("application/x-my-thing").unwrap();
+//! assert!(!custom.is_registered());
+//! assert!(Mime::from_str_strict("application/x-my-thing").is_err());
+//! assert_eq!(custom.tree(), Tree::Unregistered);
+//! ```
+
+use alloc::string::String;
+use phf::{phf_map, Map};
+
+/// The `application/*` registrations, keyed by lowercase essence, with whether they are obsolete or deprecated.
+static APPLICATION: Map<&'static str, bool> = phf_map! {
+    "application/activity+json" => false,
+    "application/atom+xml" => false,
+    "application/cbor" => false,
+    "application/cbor-seq" => false,
+    "application/dash+xml" => false,
+    "application/dicom" => false,
+    "application/dns" => false,
+    "application/dns+json" => false,
+    "application/dns-message" => false,
+    "application/ecmascript" => true,
+    "application/epub+zip" => false,
+    "application/fhir+json" => false,
+    "application/fhir+xml" => false,
+    "application/font-sfnt" => true,
+    "application/font-tdpfr" => false,
+    "application/font-woff" => true,
+    "application/geo+json" => false,
+    "application/gzip" => false,
+    "application/hl7v2+xml" => false,
+    "application/http" => false,
+    "application/javascript" => true,
+    "application/jose" => false,
+    "application/jose+json" => false,
+    "application/jrd+json" => false,
+    "application/json" => false,
+    "application/json-patch+json" => false,
+    "application/json-seq" => false,
+    "application/jwk+json" => false,
+    "application/jwk-set+json" => false,
+    "application/jwt" => false,
+    "application/ld+json" => false,
+    "application/manifest+json" => false,
+    "application/mathml+xml" => false,
+    "application/mbox" => false,
+    "application/merge-patch+json" => false,
+    "application/mp4" => false,
+    "application/msword" => false,
+    "application/n-quads" => false,
+    "application/n-triples" => false,
+    "application/ocsp-request" => false,
+    "application/ocsp-response" => false,
+    "application/octet-stream" => false,
+    "application/ogg" => false,
+    "application/pdf" => false,
+    "application/pem-certificate-chain" => false,
+    "application/pgp-encrypted" => false,
+    "application/pgp-keys" => false,
+    "application/pgp-signature" => false,
+    "application/pkcs10" => false,
+    "application/pkcs7-mime" => false,
+    "application/pkcs7-signature" => false,
+    "application/pkcs8" => false,
+    "application/pkix-cert" => false,
+    "application/pkix-crl" => false,
+    "application/postscript" => false,
+    "application/problem+json" => false,
+    "application/problem+xml" => false,
+    "application/rdf+xml" => false,
+    "application/rtf" => false,
+    "application/sdp" => false,
+    "application/senml+json" => false,
+    "application/sgml" => false,
+    "application/smil+xml" => false,
+    "application/soap+xml" => false,
+    "application/sparql-query" => false,
+    "application/sparql-results+xml" => false,
+    "application/sql" => false,
+    "application/timestamp-query" => false,
+    "application/timestamp-reply" => false,
+    "application/trig" => false,
+    "application/vnd.android.package-archive" => false,
+    "application/vnd.api+json" => false,
+    "application/vnd.apple.installer+xml" => false,
+    "application/vnd.apple.keynote" => false,
+    "application/vnd.apple.mpegurl" => false,
+    "application/vnd.apple.numbers" => false,
+    "application/vnd.apple.pages" => false,
+    "application/vnd.debian.binary-package" => false,
+    "application/vnd.google-earth.kml+xml" => false,
+    "application/vnd.google-earth.kmz" => false,
+    "application/vnd.microsoft.portable-executable" => false,
+    "application/vnd.mozilla.xul+xml" => false,
+    "application/vnd.ms-cab-compressed" => false,
+    "application/vnd.ms-excel" => false,
+    "application/vnd.ms-fontobject" => false,
+    "application/vnd.ms-powerpoint" => false,
+    "application/vnd.oasis.opendocument.graphics" => false,
+    "application/vnd.oasis.opendocument.presentation" => false,
+    "application/vnd.oasis.opendocument.spreadsheet" => false,
+    "application/vnd.oasis.opendocument.text" => false,
+    "application/vnd.openxmlformats-officedocument.presentationml.presentation" => false,
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => false,
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => false,
+    "application/vnd.rar" => false,
+    "application/vnd.sqlite3" => false,
+    "application/voicexml+xml" => false,
+    "application/wasm" => false,
+    "application/x-www-form-urlencoded" => false,
+    "application/xhtml+xml" => false,
+    "application/xhtml-voice+xml" => true,
+    "application/xml" => false,
+    "application/xml-dtd" => false,
+    "application/xml-external-parsed-entity" => false,
+    "application/xop+xml" => false,
+    "application/xslt+xml" => false,
+    "application/yaml" => false,
+    "application/zip" => false,
+    "application/zlib" => false,
+    "application/zstd" => false,
+};
+
+/// The `audio/*` registrations, keyed by lowercase essence, with whether they are obsolete or deprecated.
+static AUDIO: Map<&'static str, bool> = phf_map! {
+    "audio/3gpp" => false,
+    "audio/3gpp2" => false,
+    "audio/aac" => false,
+    "audio/ac3" => false,
+    "audio/basic" => false,
+    "audio/eac3" => false,
+    "audio/flac" => false,
+    "audio/matroska" => false,
+    "audio/mp4" => false,
+    "audio/mpeg" => false,
+    "audio/ogg" => false,
+    "audio/opus" => false,
+    "audio/vnd.wave" => false,
+    "audio/vorbis" => false,
+};
+
+/// The `font/*` registrations, keyed by lowercase essence, with whether they are obsolete or deprecated.
+static FONT: Map<&'static str, bool> = phf_map! {
+    "font/collection" => false,
+    "font/otf" => false,
+    "font/sfnt" => false,
+    "font/ttf" => false,
+    "font/woff" => false,
+    "font/woff2" => false,
+};
+
+/// The `image/*` registrations, keyed by lowercase essence, with whether they are obsolete or deprecated.
+static IMAGE: Map<&'static str, bool> = phf_map! {
+    "image/aces" => false,
+    "image/avci" => false,
+    "image/avcs" => false,
+    "image/avif" => false,
+    "image/bmp" => false,
+    "image/cgm" => false,
+    "image/dicom-rle" => false,
+    "image/emf" => false,
+    "image/fits" => false,
+    "image/g3fax" => false,
+    "image/gif" => false,
+    "image/heic" => false,
+    "image/heic-sequence" => false,
+    "image/heif" => false,
+    "image/heif-sequence" => false,
+    "image/ief" => false,
+    "image/jls" => false,
+    "image/jp2" => false,
+    "image/jpeg" => false,
+    "image/jph" => false,
+    "image/jphc" => false,
+    "image/jpm" => false,
+    "image/jpx" => false,
+    "image/jxr" => false,
+    "image/jxs" => false,
+    "image/ktx" => false,
+    "image/ktx2" => false,
+    "image/naplps" => false,
+    "image/png" => false,
+    "image/prs.btif" => false,
+    "image/prs.pti" => false,
+    "image/svg+xml" => false,
+    "image/t38" => false,
+    "image/tiff" => false,
+    "image/tiff-fx" => false,
+    "image/vnd.adobe.photoshop" => false,
+    "image/vnd.djvu" => false,
+    "image/vnd.dwg" => false,
+    "image/vnd.dxf" => false,
+    "image/vnd.microsoft.icon" => false,
+    "image/vnd.wap.wbmp" => false,
+    "image/webp" => false,
+    "image/wmf" => false,
+};
+
+/// The `message/*` registrations, keyed by lowercase essence, with whether they are obsolete or deprecated.
+static MESSAGE: Map<&'static str, bool> = phf_map! {
+    "message/delivery-status" => false,
+    "message/disposition-notification" => false,
+    "message/external-body" => false,
+    "message/global" => false,
+    "message/http" => false,
+    "message/news" => true,
+    "message/partial" => false,
+    "message/rfc822" => false,
+    "message/sip" => false,
+    "message/sipfrag" => false,
+};
+
+/// The `model/*` registrations, keyed by lowercase essence, with whether they are obsolete or deprecated.
+static MODEL: Map<&'static str, bool> = phf_map! {
+    "model/3mf" => false,
+    "model/e57" => false,
+    "model/gltf+json" => false,
+    "model/gltf-binary" => false,
+    "model/iges" => false,
+    "model/mesh" => false,
+    "model/mtl" => false,
+    "model/obj" => false,
+    "model/step" => false,
+    "model/stl" => false,
+    "model/vnd.collada+xml" => false,
+    "model/vnd.usdz+zip" => false,
+    "model/vrml" => false,
+    "model/x3d+xml" => false,
+    "model/x3d-vrml" => false,
+};
+
+/// The `multipart/*` registrations, keyed by lowercase essence, with whether they are obsolete or deprecated.
+static MULTIPART: Map<&'static str, bool> = phf_map! {
+    "multipart/alternative" => false,
+    "multipart/byteranges" => false,
+    "multipart/digest" => false,
+    "multipart/encrypted" => false,
+    "multipart/form-data" => false,
+    "multipart/mixed" => false,
+    "multipart/multilingual" => false,
+    "multipart/parallel" => false,
+    "multipart/related" => false,
+    "multipart/report" => false,
+    "multipart/signed" => false,
+    "multipart/voice-message" => false,
+};
+
+/// The `text/*` registrations, keyed by lowercase essence, with whether they are obsolete or deprecated.
+static TEXT: Map<&'static str, bool> = phf_map! {
+    "text/cache-manifest" => false,
+    "text/calendar" => false,
+    "text/css" => false,
+    "text/csv" => false,
+    "text/directory" => true,
+    "text/dns" => false,
+    "text/ecmascript" => true,
+    "text/enriched" => false,
+    "text/html" => false,
+    "text/javascript" => false,
+    "text/markdown" => false,
+    "text/n3" => false,
+    "text/plain" => false,
+    "text/rfc822-headers" => false,
+    "text/richtext" => false,
+    "text/rtf" => false,
+    "text/sgml" => false,
+    "text/tab-separated-values" => false,
+    "text/troff" => false,
+    "text/turtle" => false,
+    "text/uri-list" => false,
+    "text/vcard" => false,
+    "text/vnd.curl" => false,
+    "text/vnd.graphviz" => false,
+    "text/vnd.wap.wml" => false,
+    "text/vtt" => false,
+    "text/xml" => false,
+    "text/xml-external-parsed-entity" => false,
+};
+
+/// The `video/*` registrations, keyed by lowercase essence, with whether they are obsolete or deprecated.
+static VIDEO: Map<&'static str, bool> = phf_map! {
+    "video/3gpp" => false,
+    "video/3gpp2" => false,
+    "video/av1" => false,
+    "video/h261" => false,
+    "video/h263" => false,
+    "video/h264" => false,
+    "video/h265" => false,
+    "video/iso.segment" => false,
+    "video/jpeg" => false,
+    "video/matroska" => false,
+    "video/matroska-3d" => false,
+    "video/mj2" => false,
+    "video/mp2t" => false,
+    "video/mp4" => false,
+    "video/mpeg" => false,
+    "video/ogg" => false,
+    "video/quicktime" => false,
+    "video/raw" => false,
+    "video/vnd.mpegurl" => false,
+    "video/vp8" => false,
+    "video/vp9" => false,
+};
+
+/// Returns the table of a lowercase top-level type.
+fn table(type_: &str) -> Option<&'static Map<&'static str, bool>> {
+    Some(match type_ {
+        "application" => &APPLICATION,
+        "audio" => &AUDIO,
+        "font" => &FONT,
+        "image" => &IMAGE,
+        "message" => &MESSAGE,
+        "model" => &MODEL,
+        "multipart" => &MULTIPART,
+        "text" => &TEXT,
+        "video" => &VIDEO,
+        _ => return None,
+    })
+}
+
+/// An entry of the IANA registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Registration {
+    /// The registered `type/subtype`.
+    pub name: &'static str,
+    /// Whether the registration is marked obsolete or deprecated in favor of another type, e.g. `application/javascript` in favor of `text/javascript`.
+    pub obsolete: bool,
+}
+
+impl Registration {
+    /// Returns the URI of the registration template.
+    pub fn template_uri(&self) -> String {
+        alloc::format!("https://www.iana.org/assignments/media-types/{}", self.name)
+    }
+}
+
+/// The registration tree of a subtype, as defined in [RFC 6838](https://www.rfc-editor.org/rfc/rfc6838#section-3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Tree {
+    /// No facet, e.g. `application/json`.
+    Standards,
+    /// The `vnd.` facet, e.g. `application/vnd.api+json`.
+    Vendor,
+    /// The `prs.` facet, e.g. `image/prs.btif`.
+    Personal,
+    /// The `x-` or `x.` facet, which is never registered, e.g. `application/x-tar`.
+    Unregistered,
+}
+
+impl Tree {
+    /// Classifies a subtype by its facet.
+    pub fn of(subtype: &str) -> Self {
+        let facet = |prefix: &str| {
+            subtype
+                .get(..prefix.len())
+                .is_some_and(|facet| facet.eq_ignore_ascii_case(prefix))
+        };
+        if facet("vnd.") {
+            Self::Vendor
+        } else if facet("prs.") {
+            Self::Personal
+        } else if facet("x-") || facet("x.") {
+            Self::Unregistered
+        } else {
+            Self::Standards
+        }
+    }
+}
+
+/// Looks up the registration of a `type/subtype` essence, case-insensitively.
+pub fn registration(essence: &str) -> Option<Registration> {
+    let essence = essence.to_ascii_lowercase();
+    let (type_, _) = essence.split_once('/')?;
+    table(type_)?
+        .get_entry(essence.as_str())
+        .map(|(name, obsolete)| Registration {
+            name,
+            obsolete: *obsolete,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Mime, MimeError};
+    use core::str::FromStr;
+
+    fn mime(s: &str) -> Mime {
+        Mime::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn registered() {
+        let json = mime("Application/JSON; charset=utf-8");
+        assert!(json.is_registered());
+        let registration = json.registration().unwrap();
+        assert_eq!(registration.name, "application/json");
+        assert!(!registration.obsolete);
+        assert_eq!(
+            registration.template_uri(),
+            "https://www.iana.org/assignments/media-types/application/json"
+        );
+        assert_eq!(json.tree(), Tree::Standards);
+    }
+
+    #[test]
+    fn obsolete_alias() {
+        let javascript = mime("application/javascript");
+        assert!(javascript.is_registered());
+        assert!(javascript.registration().unwrap().obsolete);
+        assert!(!mime("text/javascript").registration().unwrap().obsolete);
+    }
+
+    #[test]
+    fn vendor_and_personal() {
+        let api = mime("application/vnd.api+json");
+        assert!(api.is_registered());
+        assert_eq!(api.tree(), Tree::Vendor);
+        assert_eq!(mime("image/prs.btif").tree(), Tree::Personal);
+        assert_eq!(mime("application/vnd.example.unknown").tree(), Tree::Vendor);
+        assert!(!mime("application/vnd.example.unknown").is_registered());
+    }
+
+    #[test]
+    fn strict_parsing() {
+        assert_eq!(
+            Mime::from_str_strict("text/html; charset=utf-8").unwrap(),
+            Mime::TEXT_HTML_UTF_8
+        );
+        assert!(Mime::from_str_strict("application/vnd.api+json").is_ok());
+        assert!(Mime::from_str_strict("application/javascript").is_ok());
+        for unregistered in [
+            "application/x-my-thing",
+            "application/vnd.example.unknown",
+            "foo/bar",
+        ] {
+            assert!(
+                matches!(
+                    Mime::from_str_strict(unregistered),
+                    Err(MimeError::Unregistered(_))
+                ),
+                "{unregistered}"
+            );
+        }
+        assert!(matches!(
+            Mime::from_str_strict("not a mime"),
+            Err(MimeError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn tables_by_type() {
+        for type_ in [
+            "application",
+            "audio",
+            "font",
+            "image",
+            "message",
+            "model",
+            "multipart",
+            "text",
+            "video",
+        ] {
+            let table = table(type_).unwrap();
+            assert!(table
+                .keys()
+                .all(|essence| essence.split_once('/').unwrap().0 == type_));
+        }
+        assert!(table("example").is_none());
+        assert_eq!(registration("json"), None);
+    }
+
+    #[test]
+    fn nonsense() {
+        let custom = mime("application/x-my-thing");
+        assert!(!custom.is_registered());
+        assert_eq!(custom.registration(), None);
+        assert_eq!(custom.tree(), Tree::Unregistered);
+        assert!(!mime("foo/bar").is_registered());
+        assert!(!mime("audio/wav").is_registered());
+    }
+}
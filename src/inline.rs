@@ -0,0 +1,195 @@
+#![cfg(feature = "dataurl")]
+//! Decides whether a bundler should inline an asset as a data URL or emit it as a file.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::{InlineDecision, InlinePolicy, Mime};
+//! use std::str::FromStr;
+//!
+//! let policy = InlinePolicy::default().never_inline_types(&["image/gif"]);
+//! let png = Mime::from_str("image/png").unwrap();
+//! assert!(matches!(policy.decide(&png, b"tiny"), InlineDecision::Inline(_)));
+//! assert!(matches!(policy.decide(&png, &[0; 8192]), InlineDecision::Emit));
+//! ```
+
+use crate::dataurl::{encoded_len, Dataurl, DataurlCharset};
+use crate::Mime;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// The outcome of `InlinePolicy::decide`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InlineDecision {
+    /// Inline the asset as this data URL.
+    Inline(Dataurl),
+    /// Emit the asset as a separate file.
+    Emit,
+}
+
+/// An inlining policy, built from the defaults of [Vite](https://vite.dev/config/build-options#build-assetsinlinelimit): assets smaller than 4 KiB are inlined, except HTML, and SVG is inlined as percent-encoded UTF-8.
+///
+/// `never_inline_types` takes precedence over `force_inline_types`, which takes precedence over the size limits. Types are matched by essence (without parameters), and `type/*` matches a whole top-level type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlinePolicy {
+    max_bytes: Option<usize>,
+    max_encoded_bytes: Option<usize>,
+    force_inline_types: Vec<String>,
+    never_inline_types: Vec<String>,
+    inline_svg_as_utf8: bool,
+}
+
+impl Default for InlinePolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: Some(4096),
+            max_encoded_bytes: None,
+            force_inline_types: Vec::new(),
+            never_inline_types: alloc::vec!["text/html".to_string()],
+            inline_svg_as_utf8: true,
+        }
+    }
+}
+
+impl InlinePolicy {
+    /// Only inlines assets smaller than `limit` bytes, like Vite's `assetsInlineLimit`.
+    pub fn max_bytes(mut self, limit: usize) -> Self {
+        self.max_bytes = Some(limit);
+        self
+    }
+
+    /// Only inlines assets whose data URL is shorter than `limit` bytes. The length is estimated without encoding.
+    pub fn max_encoded_bytes(mut self, limit: usize) -> Self {
+        self.max_encoded_bytes = Some(limit);
+        self
+    }
+
+    /// Always inlines these types, whatever their size.
+    pub fn force_inline_types(mut self, types: &[&str]) -> Self {
+        self.force_inline_types
+            .extend(types.iter().map(|ty| ty.to_ascii_lowercase()));
+        self
+    }
+
+    /// Never inlines these types. It replaces the default list, which only holds `text/html`.
+    pub fn never_inline_types(mut self, types: &[&str]) -> Self {
+        self.never_inline_types = types.iter().map(|ty| ty.to_ascii_lowercase()).collect();
+        self
+    }
+
+    /// Encodes SVG as percent-encoded UTF-8, which is shorter and compresses better than base64. Defaults to `true`.
+    pub fn inline_svg_as_utf8(mut self, enabled: bool) -> Self {
+        self.inline_svg_as_utf8 = enabled;
+        self
+    }
+
+    /// Decides how to handle an asset. Nothing is encoded or copied when the asset is emitted.
+    pub fn decide(&self, mime: &Mime, data: &[u8]) -> InlineDecision {
+        let essence = mime.essence();
+        if matches_any(&self.never_inline_types, essence) {
+            return InlineDecision::Emit;
+        }
+
+        let charset: DataurlCharset = if essence == "image/svg+xml" {
+            (self.inline_svg_as_utf8 && core::str::from_utf8(data).is_ok()).into()
+        } else {
+            mime.is_texture(data).into()
+        };
+        let fits = self.max_bytes.is_none_or(|limit| data.len() < limit)
+            && self
+                .max_encoded_bytes
+                .is_none_or(|limit| encoded_len(mime, charset, data) < limit);
+        if !fits && !matches_any(&self.force_inline_types, essence) {
+            return InlineDecision::Emit;
+        }
+
+        InlineDecision::Inline(Dataurl {
+            mime: mime.clone(),
+            data: data.to_vec().into(),
+            charset,
+        })
+    }
+}
+
+fn matches_any(patterns: &[String], essence: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| match pattern.strip_suffix("/*") {
+            Some(top_level) => essence
+                .split_once('/')
+                .is_some_and(|(ty, _)| ty == top_level),
+            None => pattern == essence,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn mime(s: &str) -> Mime {
+        Mime::from_str(s).unwrap()
+    }
+
+    fn is_inline(decision: &InlineDecision) -> bool {
+        matches!(decision, InlineDecision::Inline(_))
+    }
+
+    #[test]
+    fn vite_defaults() {
+        let policy = InlinePolicy::default();
+        assert!(is_inline(&policy.decide(&mime("image/png"), &[0; 4095])));
+        assert!(!is_inline(&policy.decide(&mime("image/png"), &[0; 4096])));
+        assert!(!is_inline(&policy.decide(&mime("text/html"), b"<p></p>")));
+        assert!(!is_inline(
+            &policy.decide(&mime("text/html; charset=utf-8"), b"<p></p>")
+        ));
+        assert!(is_inline(&policy.decide(&mime("text/css"), b"a{}")));
+    }
+
+    #[test]
+    fn svg_encoding() {
+        let svg = br#"<svg xmlns="http://www.w3.org/2000/svg"/>"#;
+        let InlineDecision::Inline(utf8) =
+            InlinePolicy::default().decide(&mime("image/svg+xml"), svg)
+        else {
+            panic!("expected the SVG to be inlined");
+        };
+        assert_eq!(utf8.charset, DataurlCharset::Utf8);
+        assert!(utf8
+            .to_string()
+            .starts_with("data:image/svg+xml;charset=utf-8,<svg"));
+
+        let InlineDecision::Inline(base64) = InlinePolicy::default()
+            .inline_svg_as_utf8(false)
+            .decide(&mime("image/svg+xml"), svg)
+        else {
+            panic!("expected the SVG to be inlined");
+        };
+        assert_eq!(base64.charset, DataurlCharset::Base64);
+    }
+
+    #[test]
+    fn overrides() {
+        let policy = InlinePolicy::default()
+            .max_bytes(16)
+            .force_inline_types(&["font/*"])
+            .never_inline_types(&["image/gif", "text/html"]);
+        assert!(is_inline(&policy.decide(&mime("font/woff2"), &[0; 64])));
+        assert!(!is_inline(&policy.decide(&mime("image/png"), &[0; 64])));
+        assert!(!is_inline(&policy.decide(&mime("image/gif"), b"GIF89a")));
+
+        let policy = InlinePolicy::default()
+            .force_inline_types(&["image/gif"])
+            .never_inline_types(&["image/gif"]);
+        assert!(!is_inline(&policy.decide(&mime("image/gif"), b"GIF89a")));
+    }
+
+    #[test]
+    fn encoded_limit() {
+        // `data:image/png;base64,` is 22 bytes, and 3 bytes encode to 4.
+        let policy = InlinePolicy::default().max_encoded_bytes(27);
+        assert!(is_inline(&policy.decide(&mime("image/png"), &[0xFF; 3])));
+        assert!(!is_inline(&policy.decide(&mime("image/png"), &[0xFF; 4])));
+    }
+}
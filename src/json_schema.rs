@@ -0,0 +1,95 @@
+#![cfg(feature = "schemars")]
+//! [`JsonSchema`](https://docs.rs/schemars/latest/schemars/trait.JsonSchema.html) implementations, so that config structs holding a `Mime` or a `Dataurl` can be validated by a generated schema. Both are described as the strings they serialize to.
+
+use crate::Mime;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation};
+use schemars::JsonSchema;
+use std::borrow::Cow;
+
+/// A `type/subtype` of RFC 6838 restricted names or `*`, optionally followed by parameters.
+const MIME_PATTERN: &str =
+    r"^([A-Za-z0-9][A-Za-z0-9!#$&^_.+-]*|\*)/([A-Za-z0-9][A-Za-z0-9!#$&^_.+-]*|\*)\s*(;.*)?$";
+
+/// The `data:` scheme, the media type and the encoding up to the comma, as per RFC 2397.
+#[cfg(feature = "dataurl")]
+const DATAURL_PATTERN: &str = r"^data:[^,]*,";
+
+fn string_schema(pattern: &str, description: &str, example: &str) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        string: Some(Box::new(StringValidation {
+            pattern: Some(pattern.into()),
+            ..Default::default()
+        })),
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.into()),
+            examples: vec![example.into()],
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+impl JsonSchema for Mime {
+    fn schema_name() -> String {
+        "Mime".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "mime_more::Mime".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            MIME_PATTERN,
+            "A media type, e.g. `text/html; charset=utf-8`.",
+            "text/html; charset=utf-8",
+        )
+    }
+}
+
+#[cfg(feature = "dataurl")]
+impl JsonSchema for crate::Dataurl {
+    fn schema_name() -> String {
+        "Dataurl".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        "mime_more::Dataurl".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            DATAURL_PATTERN,
+            "A data URL, e.g. `data:text/plain;charset=utf-8,hi`.",
+            "data:text/plain;charset=utf-8,hi",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Schema changes show up as a diff of the snapshot. Run with `UPDATE_SNAPSHOTS=1` to accept them.
+    fn check_snapshot<T: JsonSchema>(snapshot: &str) {
+        let json = serde_json::to_string_pretty(&schemars::schema_for!(T)).unwrap() + "\n";
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(snapshot, &json).unwrap();
+        }
+        assert_eq!(json, std::fs::read_to_string(snapshot).unwrap());
+    }
+
+    #[test]
+    fn mime_snapshot() {
+        check_snapshot::<Mime>("tests/snapshots/mime.schema.json");
+    }
+
+    #[cfg(feature = "dataurl")]
+    #[test]
+    fn dataurl_snapshot() {
+        check_snapshot::<crate::Dataurl>("tests/snapshots/dataurl.schema.json");
+    }
+}
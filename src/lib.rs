@@ -1,65 +1,258 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(warnings)]
 #![deny(missing_debug_implementations)]
 
 //! The crate is a utility crate for MIME type handling. It provides `from_ext`, `from_path` with `extension` feature (implemented via `mime_guess` crate), and `from_content` with `magic` feature (implemented via `infer` crate). We also provide a lighter version of `from_ext` and `from_path` with common extensions.
 //!
 //! This crate was born out of a discussion of the behaviour of MIME inferencing in the [Rolldown](https://github.com/rolldown/rolldown) project Data URLs.
+//!
+//! The `std` feature is enabled by default. Without it, the crate itself is `#![no_std]` (with `alloc`): the light extension table, the texture heuristics, the `Mime` wrapper, and data URL encoding and decoding of in-memory buffers are still available, while everything touching paths and the filesystem is not. Its `mime` dependency, and `urlencoding` for data URLs, still link `std`, so a target with a standard library is required, e.g. `wasm32-unknown-unknown`; bare-metal targets such as `thumbv7em-none-eabi` are not supported.
+
+extern crate alloc;
+
+/// Emits a `tracing` debug event with the `tracing` feature, and expands to nothing otherwise.
+#[allow(unused_macros)]
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    }};
+}
 
+/// Emits a `tracing` trace event with the `tracing` feature, and expands to nothing otherwise.
+#[allow(unused_macros)]
+macro_rules! trace {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        tracing::trace!($($arg)*);
+    }};
+}
+
+pub mod alias;
+mod cache;
+mod charset;
+mod compound;
 #[cfg(feature = "dataurl")]
 pub mod dataurl;
-mod light_guess;
+pub mod db;
+mod dotfile;
+pub mod embed;
+mod error;
+pub mod ffi;
+mod fuzz;
+mod guesser;
+pub mod http;
+pub mod iana;
+mod inline;
+mod json_schema;
+pub mod light_guess;
 mod magic;
+pub mod multipart;
+pub mod napi;
+pub mod negotiate;
+mod pattern;
+mod preferred_ext;
+pub mod responder;
+pub mod serde_ext;
+pub mod sniff;
+pub mod system;
+mod text_sniff;
 pub mod texture;
+pub mod tokio;
 mod utils;
+pub mod wasm;
 
-use std::path::Path;
+pub use crate::charset::Charset;
 #[cfg(feature = "dataurl")]
 pub use crate::dataurl::Dataurl;
+pub use crate::error::MimeError;
+pub use crate::guesser::MimeGuesser;
+#[cfg(feature = "dataurl")]
+pub use crate::inline::{InlineDecision, InlinePolicy};
+pub use crate::pattern::MimePattern;
+#[cfg(feature = "std")]
+use std::path::Path;
 
+use core::str::FromStr;
 use mime::Mime as MimeType;
-use std::str::FromStr;
 
 /// A wrapper around the `mime` crate's `Mime` type, with additional functionality including `from_ext`, `from_path`, and `from_content`, etc.
-#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord)]
+///
+/// `Eq`, `Ord` and `Hash` compare the essence and the parameter names case-insensitively, the `charset` value case-insensitively, and the other parameter values as written, in any order, so `text/plain; format=flowed` differs from `text/plain; format=fixed`. `Ord` is only useful for sorted collections; use `Mime::cmp_specificity` to rank types as `Accept` ranges.
+#[derive(Debug, Clone)]
 pub struct Mime {
+    /// The inner type. Prefer `as_mime`, `into_mime`, or the passthrough accessors such as `type_` and `subtype`.
+    #[doc(hidden)]
     pub mime: MimeType,
 }
 
+/// Displays the essence of a `Mime` without its parameters, as returned by `Mime::essence_display`.
+#[derive(Debug, Clone, Copy)]
+pub struct EssenceDisplay<'a>(&'a Mime);
+
+impl core::fmt::Display for EssenceDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.0.mime.essence_str())
+    }
+}
+
+/// The coarse category of a MIME type, from its top-level type. Structured syntax suffixes are not considered, so `application/ld+json` is `Application` and `image/svg+xml` is `Image`; use `Mime::is_text` to also catch textual application types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MediaCategory {
+    Text,
+    Image,
+    Audio,
+    Video,
+    Font,
+    Application,
+    Multipart,
+    Message,
+    Model,
+    /// Any other top-level type, e.g. `example` or an unregistered `x-` type.
+    Other,
+}
+
+/// The outcome of `Mime::verify`, comparing the type claimed by an extension with the type detected from the content.
+#[cfg(all(
+    feature = "magic",
+    any(feature = "extension", feature = "extension-light")
+))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// The content matches the claimed type, up to aliases such as `image/jpg` for `image/jpeg`.
+    Consistent(Mime),
+    /// The content is of another type than the claimed one, e.g. a PNG named `.txt`.
+    Mismatch { claimed: Mime, detected: Mime },
+    /// No magic number matched the content, as for most text formats, so only the claimed type is known.
+    Unknown(Mime),
+}
+
+/// Defines associated constants wrapping the constants of the `mime` crate.
+macro_rules! mime_constants {
+    ($($name:ident, $source:literal;)*) => {
+        impl Mime {
+            $(
+                #[doc = concat!("`", $source, "`")]
+                pub const $name: Mime = Mime { mime: mime::$name };
+            )*
+        }
+
+        /// The constants by their source, so that `cache` can hand out clones that don't allocate.
+        #[cfg(feature = "std")]
+        pub(crate) const MIME_CONSTANTS: &[(&str, MimeType)] = &[$(($source, mime::$name)),*];
+    };
+}
+
+mime_constants! {
+    STAR_STAR, "*/*";
+    TEXT_STAR, "text/*";
+    TEXT_PLAIN, "text/plain";
+    TEXT_PLAIN_UTF_8, "text/plain; charset=utf-8";
+    TEXT_HTML, "text/html";
+    TEXT_HTML_UTF_8, "text/html; charset=utf-8";
+    TEXT_CSS, "text/css";
+    TEXT_CSS_UTF_8, "text/css; charset=utf-8";
+    TEXT_JAVASCRIPT, "text/javascript";
+    TEXT_XML, "text/xml";
+    TEXT_EVENT_STREAM, "text/event-stream";
+    TEXT_CSV, "text/csv";
+    TEXT_CSV_UTF_8, "text/csv; charset=utf-8";
+    TEXT_TAB_SEPARATED_VALUES, "text/tab-separated-values";
+    TEXT_TAB_SEPARATED_VALUES_UTF_8, "text/tab-separated-values; charset=utf-8";
+    TEXT_VCARD, "text/vcard";
+    IMAGE_STAR, "image/*";
+    IMAGE_JPEG, "image/jpeg";
+    IMAGE_GIF, "image/gif";
+    IMAGE_PNG, "image/png";
+    IMAGE_BMP, "image/bmp";
+    IMAGE_SVG, "image/svg+xml";
+    FONT_WOFF, "font/woff";
+    FONT_WOFF2, "font/woff2";
+    APPLICATION_JSON, "application/json";
+    APPLICATION_JAVASCRIPT, "application/javascript";
+    APPLICATION_JAVASCRIPT_UTF_8, "application/javascript; charset=utf-8";
+    APPLICATION_WWW_FORM_URLENCODED, "application/x-www-form-urlencoded";
+    APPLICATION_OCTET_STREAM, "application/octet-stream";
+    APPLICATION_MSGPACK, "application/msgpack";
+    APPLICATION_PDF, "application/pdf";
+    MULTIPART_FORM_DATA, "multipart/form-data";
+}
+
 impl Mime {
     pub fn new(mime: MimeType) -> Self {
         Self { mime }
     }
 
+    /// Parses a static string like `FromStr`, but only once: later calls clone the parsed type from a process-wide cache. It suits strings parsed over and over on hot paths; every type of the light table is cached up front. Without `std`, it parses every time.
+    pub fn cached(s: &'static str) -> Result<Self, MimeError> {
+        Ok(Self {
+            mime: cache::parse(s)?,
+        })
+    }
+
     #[cfg(feature = "extension")]
     #[cfg_attr(
         feature = "extension",
         doc = "Guesses the MIME type from the content via [`mime_guess`](https://docs.rs/mime_guess/latest/mime_guess/). It is more accurate than `from_ext_light`, but is slower."
     )]
-    pub fn from_path(path: &std::path::Path) -> anyhow::Result<Self> {
-        use mime_guess::from_path;
-        Ok(Self {
-            mime: from_path(path).first().unwrap(),
-        })
+    pub fn from_path(path: &std::path::Path) -> Result<Self, MimeError> {
+        match utils::require_extension(path) {
+            Ok(ext) => Self::from_ext(ext),
+            Err(err) => Self::from_dotfile_path(path).ok_or(err),
+        }
+    }
+
+    #[cfg(feature = "extension")]
+    #[cfg_attr(
+        feature = "extension",
+        doc = "The same as `from_path`, but falls back to `application/octet-stream` when the extension is missing or unknown."
+    )]
+    pub fn from_path_or_octet_stream(path: &std::path::Path) -> Self {
+        Self::from_path_or(path, Self::default())
+    }
+
+    #[cfg(feature = "extension")]
+    #[cfg_attr(
+        feature = "extension",
+        doc = "The same as `from_path`, but returns `fallback` when the extension is missing or unknown."
+    )]
+    pub fn from_path_or(path: &std::path::Path, fallback: Self) -> Self {
+        Self::from_path(path).unwrap_or(fallback)
     }
 
     #[cfg(feature = "extension")]
     #[cfg_attr(
         feature = "extension",
-        doc = "Guesses the MIME type from the extension via [`mime_guess`](https://docs.rs/mime_guess/latest/mime_guess/). It is more accurate than `from_ext_light`, but is slower."
+        doc = "Guesses the MIME type from the extension via [`mime_guess`](https://docs.rs/mime_guess/latest/mime_guess/). It is more accurate than `from_ext_light`, but is slower. The extension is matched case-insensitively, ignoring surrounding whitespace and one leading `.`."
     )]
-    pub fn from_ext(ext: &str) -> anyhow::Result<Self> {
+    pub fn from_ext(ext: &str) -> Result<Self, MimeError> {
         use mime_guess::from_ext;
+        let ext = utils::normalize_extension(ext)?;
+        if let Some(mime) = compound::mime_type_by_extension(ext) {
+            return Self::cached(mime);
+        }
         Ok(Self {
-            mime: from_ext(ext).first().unwrap(),
+            mime: from_ext(ext)
+                .first()
+                .ok_or_else(|| MimeError::UnknownExtension(ext.into()))?,
         })
     }
 
+    #[cfg(feature = "extension")]
+    #[cfg_attr(
+        feature = "extension",
+        doc = "The same as `from_ext`, but returns `fallback` when the extension is unknown."
+    )]
+    pub fn from_ext_or(ext: &str, fallback: Self) -> Self {
+        Self::from_ext(ext).unwrap_or(fallback)
+    }
+
     #[cfg(feature = "extension-light")]
     #[cfg_attr(
         feature = "extension-light",
-        doc = "Guesses the MIME type from the extension. It is more lightweight than `from_ext`, and is suitable for use in performance-critical code, especially bundlers."
+        doc = "Guesses the MIME type from the extension. It is more lightweight than `from_ext`, and is suitable for use in performance-critical code, especially bundlers. The extension is matched case-insensitively, ignoring surrounding whitespace and one leading `.`. The table is public in the `light_guess` module, and `light_guess::try_str_from_ext` returns the type as a `&'static str` without parsing it."
     )]
-    pub fn from_ext_light(ext: &str) -> anyhow::Result<Self> {
+    pub fn from_ext_light(ext: &str) -> Result<Self, MimeError> {
         Ok(Self {
             mime: light_guess::try_from_ext(ext)?,
         })
@@ -68,132 +261,2855 @@ impl Mime {
     #[cfg(feature = "extension-light")]
     #[cfg_attr(
         feature = "extension-light",
+        doc = "The same as `from_ext_light`, but returns `fallback` when the extension is not in the light table."
+    )]
+    pub fn from_ext_light_or(ext: &str, fallback: Self) -> Self {
+        Self::from_ext_light(ext).unwrap_or(fallback)
+    }
+
+    #[cfg(feature = "extension")]
+    #[cfg_attr(
+        feature = "extension",
+        doc = "The same as `from_path`, but takes a plain filename, e.g. from an upload. Both `/` and `\\` are treated as separators, so Windows paths sent by clients work on every platform."
+    )]
+    pub fn from_filename(name: &str) -> Result<Self, MimeError> {
+        let ext = utils::filename_extension(name)
+            .ok_or_else(|| MimeError::NoFilenameExtension(name.into()))?;
+        Self::from_ext(ext)
+    }
+
+    #[cfg(feature = "extension-light")]
+    #[cfg_attr(
+        feature = "extension-light",
+        doc = "The same as `from_filename`, but guesses via `from_ext_light`."
+    )]
+    pub fn from_filename_light(name: &str) -> Result<Self, MimeError> {
+        let ext = utils::filename_extension(name)
+            .ok_or_else(|| MimeError::NoFilenameExtension(name.into()))?;
+        Self::from_ext_light(ext)
+    }
+
+    #[cfg(feature = "extension")]
+    #[cfg_attr(
+        feature = "extension",
+        doc = "Guesses the MIME type from the path of a URL or a relative specifier, e.g. `https://cdn.example.com/app.js?v=123#hash` or `./icon.svg?url`. The query string and fragment are stripped and the last segment is percent-decoded before it is handled like `from_filename`, so an extension that only appears in the query is not used."
+    )]
+    pub fn from_url(url: &str) -> Result<Self, MimeError> {
+        let name = utils::url_file_name(url);
+        let ext = utils::filename_extension(&name)
+            .ok_or_else(|| MimeError::NoUrlExtension(url.into()))?;
+        Self::from_ext(ext)
+    }
+
+    #[cfg(feature = "extension-light")]
+    #[cfg_attr(
+        feature = "extension-light",
+        doc = "The same as `from_url`, but guesses via `from_ext_light`."
+    )]
+    pub fn from_url_light(url: &str) -> Result<Self, MimeError> {
+        let name = utils::url_file_name(url);
+        let ext = utils::filename_extension(&name)
+            .ok_or_else(|| MimeError::NoUrlExtension(url.into()))?;
+        Self::from_ext_light(ext)
+    }
+
+    #[cfg(all(feature = "std", feature = "extension-light"))]
+    #[cfg_attr(
+        all(feature = "std", feature = "extension-light"),
+        doc = "The same as `from_ext_light`, but takes an `OsStr`, e.g. from `Path::extension`. As the light table is all ASCII, an extension that isn't valid UTF-8 can't match, and is reported as `MimeError::UndecodableExtension` rather than as a missing extension."
+    )]
+    pub fn from_os_ext(ext: &std::ffi::OsStr) -> Result<Self, MimeError> {
+        match ext.to_str() {
+            Some(ext) => Self::from_ext_light(ext),
+            None => Err(MimeError::UndecodableExtension(ext.to_os_string())),
+        }
+    }
+
+    #[cfg(all(feature = "std", feature = "extension-light"))]
+    #[cfg_attr(
+        all(feature = "std", feature = "extension-light"),
         doc = "You can refer to `from_ext_light` for more information. This function is the same as `from_ext_light`, but it takes a `std::path::Path` instead of a `&str`."
     )]
-    pub fn from_path_light(path: &std::path::Path) -> anyhow::Result<Self> {
-        if let Some(ext) = utils::get_extension(path) {
-            Ok(Self {
-                mime: light_guess::try_from_ext(ext)?,
-            })
-        } else {
-            anyhow::bail!("No extension found for path: {:?}", path);
+    pub fn from_path_light(path: &std::path::Path) -> Result<Self, MimeError> {
+        match utils::require_extension(path) {
+            Ok(ext) => Self::from_ext_light(ext),
+            Err(err) => Self::from_dotfile_path(path).ok_or(err),
+        }
+    }
+
+    /// Recognizes a well-known dotfile when the path has no extension. It is shared by `from_path` and `from_path_light`.
+    #[cfg(all(
+        feature = "std",
+        any(feature = "extension", feature = "extension-light")
+    ))]
+    fn from_dotfile_path(path: &std::path::Path) -> Option<Self> {
+        Self::from_dotfile(path.file_name()?.to_str()?, false)
+    }
+
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    #[cfg_attr(
+        any(feature = "extension", feature = "extension-light"),
+        doc = "Guesses the MIME type of a dotfile from its whole name, e.g. `application/json` for `.babelrc` or `text/plain` for `.gitignore` and `.env`, matched case-insensitively. `from_path`, `from_path_light` and `MimeGuesser::with_path` consult the same table when a path has no extension. Unknown dotfiles give `None`, or `text/plain` when `unknown_as_text` is set, as they are mostly configuration. Names that aren't dotfiles, including `.eslintrc.json`, which has an extension, give `None`."
+    )]
+    pub fn from_dotfile(name: &str, unknown_as_text: bool) -> Option<Self> {
+        let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+        if !dotfile::is_dotfile(name) {
+            return None;
+        }
+        match dotfile::mime_type_by_name(name) {
+            Some(mime) => Some(Self::cached(mime).expect("dotfile types are valid")),
+            None => unknown_as_text.then_some(Self::TEXT_PLAIN),
+        }
+    }
+
+    #[cfg(all(
+        feature = "std",
+        any(feature = "extension", feature = "extension-light")
+    ))]
+    #[cfg_attr(
+        all(
+            feature = "std",
+            any(feature = "extension", feature = "extension-light")
+        ),
+        doc = "Registers a dotfile for `from_dotfile` and the path helpers, e.g. `.swiftlint` as `application/yaml`, overriding the built-in table, which is fixed at compile time. The name is matched case-insensitively. Registrations are process-wide and are not listed by `db::entries`. A name that isn't a dotfile, e.g. `.eslintrc.json`, is rejected with `MimeError::NotADotfile`, and a type that doesn't parse with `MimeError::Parse`.\n\n# Example\n\n```rust\nuse mime_more::Mime;\n\nMime::register_dotfile(\".swiftlint\", \"application/yaml\").unwrap();\nassert_eq!(Mime::from_dotfile(\"ios/.SwiftLint\", false).unwrap(), \"application/yaml\");\n```"
+    )]
+    pub fn register_dotfile(name: &str, mime: &'static str) -> Result<(), MimeError> {
+        if !dotfile::is_dotfile(name) {
+            return Err(MimeError::NotADotfile(name.into()));
+        }
+        Self::cached(mime)?;
+        dotfile::register(name, mime);
+        Ok(())
+    }
+
+    #[cfg(all(feature = "std", feature = "extension-light"))]
+    #[cfg_attr(
+        all(feature = "std", feature = "extension-light"),
+        doc = "The same as `from_path_light`, but returns `fallback` when the extension is missing or not in the light table."
+    )]
+    pub fn from_path_light_or(path: &std::path::Path, fallback: Self) -> Self {
+        Self::from_path_light(path).unwrap_or(fallback)
+    }
+
+    #[cfg(all(feature = "extension", feature = "camino"))]
+    #[cfg_attr(
+        all(feature = "extension", feature = "camino"),
+        doc = "The same as `from_path`, but takes a `camino::Utf8Path`. As the path is guaranteed to be UTF-8, the extension is read directly without the `OsStr` conversion. Compound extensions such as `d.ts` and well-known dotfiles are recognized alike."
+    )]
+    pub fn from_utf8_path(path: &camino::Utf8Path) -> Result<Self, MimeError> {
+        match path.file_name().and_then(utils::filename_extension) {
+            Some(ext) => Self::from_ext(ext),
+            None => Self::from_utf8_dotfile_path(path),
+        }
+    }
+
+    #[cfg(all(feature = "extension-light", feature = "camino"))]
+    #[cfg_attr(
+        all(feature = "extension-light", feature = "camino"),
+        doc = "The same as `from_path_light`, but takes a `camino::Utf8Path`. As the path is guaranteed to be UTF-8, the extension is read directly without the `OsStr` conversion. Compound extensions such as `d.ts` and well-known dotfiles are recognized alike."
+    )]
+    pub fn from_utf8_path_light(path: &camino::Utf8Path) -> Result<Self, MimeError> {
+        match path.file_name().and_then(utils::filename_extension) {
+            Some(ext) => Self::from_ext_light(ext),
+            None => Self::from_utf8_dotfile_path(path),
         }
     }
 
+    /// Recognizes a well-known dotfile when a `camino::Utf8Path` has no extension, like `from_dotfile_path`. It is shared by `from_utf8_path` and `from_utf8_path_light`.
+    #[cfg(all(
+        feature = "camino",
+        any(feature = "extension", feature = "extension-light")
+    ))]
+    fn from_utf8_dotfile_path(path: &camino::Utf8Path) -> Result<Self, MimeError> {
+        path.file_name()
+            .and_then(|name| Self::from_dotfile(name, false))
+            .ok_or_else(|| MimeError::NoExtension(path.into()))
+    }
+
     #[cfg(feature = "magic")]
     #[cfg_attr(
         feature = "magic",
         doc = "Guesses the MIME type from the content via [`infer`](https://docs.rs/infer/latest/infer/). It infers the MIME code via the Magic Number, which is useful in customized extensions."
     )]
-    pub fn from_content(data: &[u8]) -> anyhow::Result<Self> {
+    pub fn from_content(data: &[u8]) -> Result<Self, MimeError> {
         Ok(Self {
             mime: magic::mime_type_by_content(data)?,
         })
     }
 
+    #[cfg(all(feature = "magic", feature = "texture"))]
+    #[cfg_attr(
+        all(feature = "magic", feature = "texture"),
+        doc = "The same as `from_content`, but sniffs texture (UTF-8) content that no magic number matches, so web assets get a type too: a JSON document is `application/json`, an XML document is `text/xml` (or `image/svg+xml` when its root is `svg`), a leading `<svg` is `image/svg+xml`, a leading doctype or `<html` is `text/html`, and any other text is `text/plain`. Only the leading markup is inspected. It still fails for content that is neither matched nor UTF-8."
+    )]
+    pub fn from_content_with_text_fallback(data: &[u8]) -> Result<Self, MimeError> {
+        match Self::from_content(data) {
+            Ok(inferred) if inferred.essence() != "text/xml" => Ok(inferred),
+            inferred => match core::str::from_utf8(data) {
+                Ok(text) => Ok(text_sniff::mime_type_by_text(text)),
+                Err(_) => inferred,
+            },
+        }
+    }
+
+    #[cfg(all(feature = "std", feature = "magic"))]
+    #[cfg_attr(
+        all(feature = "std", feature = "magic"),
+        doc = "The same as `from_content`, but reads at most `max_bytes` from the reader instead of taking the whole content. 8192 bytes are enough for every matcher in `infer`, including the 262 bytes needed by tar. Short and interrupted reads are retried until the limit or the end of the reader."
+    )]
+    pub fn from_reader(
+        reader: &mut impl std::io::Read,
+        max_bytes: usize,
+    ) -> Result<Self, MimeError> {
+        use std::io::Read;
+        let mut prefix = Vec::new();
+        reader
+            .by_ref()
+            .take(max_bytes as u64)
+            .read_to_end(&mut prefix)?;
+        Self::from_content(&prefix)
+    }
+
+    #[cfg(all(
+        feature = "magic",
+        any(feature = "extension", feature = "extension-light")
+    ))]
+    #[cfg_attr(
+        all(
+            feature = "magic",
+            any(feature = "extension", feature = "extension-light")
+        ),
+        doc = "Checks whether the content looks like what the extension claims, e.g. for upload validation. The claimed type comes from `from_ext`, falling back to `from_ext_light`, and is `application/octet-stream` when neither knows the extension. Both types are canonicalized and compared by essence, so `image/jpg` against `image/jpeg` is consistent. Formats without a magic number, such as CSS or JSON, are `Unknown` rather than a mismatch."
+    )]
+    pub fn verify(ext: &str, data: &[u8]) -> Verification {
+        #[cfg(feature = "extension")]
+        let claimed = Self::from_ext(ext);
+        #[cfg(all(feature = "extension", feature = "extension-light"))]
+        let claimed = claimed.or_else(|_| Self::from_ext_light(ext));
+        #[cfg(not(feature = "extension"))]
+        let claimed = Self::from_ext_light(ext);
+        let claimed = claimed.unwrap_or_default();
+        match Self::from_content(data) {
+            Ok(detected) if detected.canonicalize().eq_essence(&claimed.canonicalize()) => {
+                Verification::Consistent(claimed)
+            }
+            Ok(detected) => Verification::Mismatch { claimed, detected },
+            Err(_) => Verification::Unknown(claimed),
+        }
+    }
+
+    #[cfg(all(
+        feature = "std",
+        feature = "magic",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[cfg_attr(
+        all(
+            feature = "std",
+            feature = "magic",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ),
+        doc = "Opens the file and infers its MIME type from at most `max_bytes` of it, falling back to the extension when no magic number matches. Most formats need only a few bytes (zip needs 4), but tar needs 262, and the Office formats built on zip are told apart within the first 6000; `8192` covers every matcher in `infer`. Unlike `from_file`, the content takes priority over the extension."
+    )]
+    pub fn sniff_path(path: &Path, max_bytes: usize) -> Result<Self, MimeError> {
+        use std::io::Read;
+        let mut prefix = Vec::new();
+        std::fs::File::open(path)?
+            .take(max_bytes as u64)
+            .read_to_end(&mut prefix)?;
+        Self::sniff_prefix(path, &prefix)
+    }
+
+    /// Infers the MIME type from the prefix of the file at the path, falling back to its extension. It is shared by `sniff_path`, `sniff_path_async` and `Dataurl::from_path_sniffed`.
+    #[cfg(all(
+        feature = "std",
+        feature = "magic",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub(crate) fn sniff_prefix(path: &Path, prefix: &[u8]) -> Result<Self, MimeError> {
+        let inferred = Self::from_content(prefix);
+        #[cfg(any(feature = "extension", feature = "extension-light"))]
+        if inferred.is_err() {
+            if let Ok(guessed) = Self::try_from(path) {
+                return Ok(guessed);
+            }
+        }
+        #[cfg(not(any(feature = "extension", feature = "extension-light")))]
+        let _ = path;
+        inferred
+    }
+
+    /// Guesses the MIME type of a file on disk via `from_path_and_content`, reading only the first few kilobytes of the file for content inference.
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    pub fn from_file(path: &Path) -> Result<Self, MimeError> {
+        #[cfg(any(feature = "magic", feature = "texture"))]
+        {
+            use std::io::Read;
+            let mut prefix = Vec::new();
+            std::fs::File::open(path)?
+                .take(utils::SNIFF_LENGTH as u64)
+                .read_to_end(&mut prefix)?;
+            from_path_and_content(path, &prefix)
+        }
+
+        #[cfg(not(any(feature = "magic", feature = "texture")))]
+        {
+            std::fs::metadata(path)?;
+            from_path_and_content(path)
+        }
+    }
+
+    #[cfg(all(
+        feature = "system",
+        any(
+            target_os = "linux",
+            target_os = "freebsd",
+            target_os = "openbsd",
+            target_os = "netbsd",
+            target_os = "dragonfly"
+        )
+    ))]
+    #[cfg_attr(
+        all(
+            feature = "system",
+            any(
+                target_os = "linux",
+                target_os = "freebsd",
+                target_os = "openbsd",
+                target_os = "netbsd",
+                target_os = "dragonfly"
+            )
+        ),
+        doc = "Guesses the MIME type of a file via the system shared-mime-info database (see `system::SystemDatabase`), by its name first and by its content if no glob matches. When there is no database or it doesn't know the file, it falls back to `from_file`."
+    )]
+    pub fn from_path_system(path: &Path) -> Result<Self, MimeError> {
+        if let Some(mime) =
+            system::SystemDatabase::system().and_then(|database| database.lookup(path))
+        {
+            return Ok(mime);
+        }
+        Self::from_file(path)
+    }
+
+    #[cfg(all(
+        feature = "camino",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    #[cfg_attr(
+        all(
+            feature = "camino",
+            not(all(target_arch = "wasm32", target_os = "unknown"))
+        ),
+        doc = "The same as `from_file`, but takes a `camino::Utf8Path`."
+    )]
+    pub fn from_utf8_file(path: &camino::Utf8Path) -> Result<Self, MimeError> {
+        Self::from_file(path.as_std_path())
+    }
+
+    /// Returns a clone of the inner `mime::Mime`. Prefer `as_mime` or `into_mime`, which don't clone.
     pub fn to_mime(&self) -> MimeType {
         self.mime.clone()
     }
 
-    #[cfg(feature = "texture")]
-    #[cfg_attr(
-        feature = "texture",
-        doc = "Check the file is a texture or not. It is useful in the case of handling texture files."
-    )]
-    pub fn is_texture(self, data: &[u8]) -> bool {
-        if data.len() == 0 {
-            true
-        } else if texture::is_texture_mime(&self.mime) {
-            true
-        } else if texture::is_texture_std(data) {
-            true
+    /// Borrows the inner `mime::Mime`. The wrapper also derefs to it, so e.g. `.type_()` works directly.
+    pub fn as_mime(&self) -> &MimeType {
+        &self.mime
+    }
+
+    /// Unwraps the inner `mime::Mime` without cloning.
+    pub fn into_mime(self) -> MimeType {
+        self.mime
+    }
+
+    /// Parses a `Content-Type` header value, tolerating surrounding whitespace, whitespace around `;`, empty parameters, and a trailing `;`. Quoted parameter values may contain spaces and semicolons, and are read without their quotes.
+    pub fn from_content_type_header(value: &str) -> Result<Self, MimeError> {
+        Self::from_str(&http::normalize_content_type(value))
+    }
+
+    /// Parses a header line such as `Content-Type: text/html; charset=utf-8`, or a bare value like `from_content_type_header`. The header name is matched case-insensitively, and whitespace around the value and a trailing `\r\n` are ignored. Any other header name is rejected with `MimeError::UnexpectedHeader`.
+    pub fn from_header(line: &str) -> Result<Self, MimeError> {
+        let value = match line.split_once(':') {
+            Some((name, value))
+                if !name.trim().is_empty() && name.trim().chars().all(http::is_tchar) =>
+            {
+                let name = name.trim();
+                if !name.eq_ignore_ascii_case("content-type") {
+                    return Err(MimeError::UnexpectedHeader(name.into()));
+                }
+                value
+            }
+            _ => line,
+        };
+        Self::from_content_type_header(value)
+    }
+
+    /// Parses a comma-separated list, as in HTTP headers or the HTML `accept` attribute, e.g. `image/png, image/jpeg`. Commas inside quoted parameter values don't split, and each element is parsed like `from_content_type_header`. Empty elements, including a trailing comma, are skipped as RFC 9110 asks of list recipients. The error names the index of the first invalid element, counting the empty ones.
+    pub fn parse_list(s: &str) -> anyhow::Result<alloc::vec::Vec<Self>> {
+        http::split_unquoted(s, ',')
+            .into_iter()
+            .enumerate()
+            .filter(|(_, element)| !element.trim().is_empty())
+            .map(|(index, element)| {
+                Self::from_content_type_header(element).map_err(|err| {
+                    anyhow::anyhow!(
+                        "Invalid element {} of MIME list {:?}: {}",
+                        index,
+                        element,
+                        err
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Builds `multipart/form-data; boundary=...`. A supplied boundary is validated against RFC 2046 (1 to 70 characters of the allowed set), while `None` generates a fresh one.
+    pub fn multipart_form_data(boundary: Option<&str>) -> anyhow::Result<Self> {
+        let boundary = match boundary {
+            Some(boundary) => {
+                multipart::validate_boundary(boundary)?;
+                alloc::string::String::from(boundary)
+            }
+            None => multipart::generate_boundary(),
+        };
+        let mime = if multipart::needs_quoting(&boundary) {
+            alloc::format!("multipart/form-data; boundary=\"{}\"", boundary)
         } else {
-            false
+            alloc::format!("multipart/form-data; boundary={}", boundary)
+        };
+        Self::from_str(&mime).map_err(anyhow::Error::msg)
+    }
+
+    /// The same as `multipart_form_data(Some(boundary))`. See `multipart::generate_boundary` for a fresh boundary.
+    pub fn multipart_form_data_with_boundary(boundary: &str) -> anyhow::Result<Self> {
+        Self::multipart_form_data(Some(boundary))
+    }
+
+    /// Renders the MIME type as a `Content-Type` header value. Parameter values are written as tokens when possible, and quoted (with quotes and backslashes escaped) otherwise, as per [RFC 7231](https://www.rfc-editor.org/rfc/rfc7231#section-3.1.1.1). Non-ASCII values are kept as they are, see `to_header_string_strict` to reject them instead.
+    pub fn to_header_string(&self) -> alloc::string::String {
+        self.header_string(false, None)
+            .expect("Rendering a header without strictness can't fail")
+    }
+
+    /// The same as `to_header_string`, but returns an error for parameter values that can't be represented in a header, i.e. non-ASCII values.
+    pub fn to_header_string_strict(&self) -> anyhow::Result<alloc::string::String> {
+        self.header_string(true, None)
+    }
+
+    /// Renders the header value, leaving out the parameter named `skip` if any.
+    fn header_string(
+        &self,
+        strict: bool,
+        skip: Option<&str>,
+    ) -> anyhow::Result<alloc::string::String> {
+        let mut header = alloc::string::String::from(self.mime.essence_str());
+        for (name, value) in self.mime.params() {
+            if skip.is_some_and(|skip| name.as_str().eq_ignore_ascii_case(skip)) {
+                continue;
+            }
+            header.push_str("; ");
+            header.push_str(name.as_str());
+            header.push('=');
+            http::push_param_value(&mut header, value.as_str(), strict)?;
         }
+        Ok(header)
     }
-}
 
-impl std::fmt::Display for Mime {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.mime)
+    /// Returns the MIME type with the parameter set to `value`, replacing a parameter of the same name (case-insensitively). The value is quoted when it is not a token.
+    ///
+    /// Returns an error if the name is not a token, or if the value holds control or non-ASCII characters, as they can't be written in a header. Quotes and backslashes are rejected as well, as `mime` doesn't unescape quoted strings.
+    pub fn with_param(self, name: &str, value: &str) -> anyhow::Result<Self> {
+        if name.is_empty() || !name.chars().all(http::is_tchar) {
+            anyhow::bail!("Invalid parameter name: {:?}", name);
+        }
+        if let Some(c) = value
+            .chars()
+            .find(|&c| !c.is_ascii() || c.is_ascii_control() || c == '"' || c == '\\')
+        {
+            anyhow::bail!("Invalid character in parameter value: {:?}", c);
+        }
+
+        let mut mime = self.header_string(false, Some(name))?;
+        mime.push_str("; ");
+        mime.push_str(&name.to_ascii_lowercase());
+        mime.push('=');
+        if !value.is_empty() && value.chars().all(http::is_tchar) {
+            mime.push_str(value);
+        } else {
+            mime.push('"');
+            mime.push_str(value);
+            mime.push('"');
+        }
+        Self::from_str(&mime).map_err(anyhow::Error::msg)
     }
-}
 
-impl FromStr for Mime {
-    type Err = anyhow::Error;
+    /// The same as `with_param("charset", "utf-8")`.
+    pub fn with_charset_utf8(self) -> Self {
+        self.with_param("charset", "utf-8")
+            .expect("`charset=utf-8` is a valid parameter")
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            mime: MimeType::from_str(s)?,
-        })
+    /// Returns the MIME type without the parameter, matched case-insensitively.
+    #[cfg(feature = "encoding")]
+    pub(crate) fn without_param(&self, name: &str) -> anyhow::Result<Self> {
+        Self::from_str(&self.header_string(false, Some(name))?).map_err(anyhow::Error::msg)
     }
-}
 
-impl<'a> PartialEq<&'a str> for Mime {
-    fn eq(&self, other: &&'a str) -> bool {
-        self.mime == MimeType::from_str(*other).unwrap()
+    /// Returns the top-level type, e.g. `image` for `image/svg+xml`.
+    pub fn type_(&self) -> mime::Name<'_> {
+        self.mime.type_()
     }
 
-    fn ne(&self, other: &&'a str) -> bool {
-        self.mime != MimeType::from_str(*other).unwrap()
+    /// Returns the subtype without its suffix, e.g. `svg` for `image/svg+xml`; see `suffix` for the rest.
+    pub fn subtype(&self) -> mime::Name<'_> {
+        self.mime.subtype()
     }
-}
 
-/// Guesses the MIME type from the extension and content. It is a combination of `from_ext` and `from_content`, and set the priority of `from_content` higher than `from_ext`.
-/// If the function can't guess the MIME type, it will return `application/octet-stream` if the feature `texture` is disabled, otherwise it will return `text/plain` if the data is a texture or `application/octet-stream` if the data is not a texture.
-pub fn from_ext_and_content(
-    ext: &str,
-    #[cfg(feature = "magic")] data: &[u8],
-) -> anyhow::Result<Mime> {
-    #[cfg(feature = "extension")]
-    if let Ok(guessed) = Mime::from_ext(ext) {
-        return Ok(guessed);
+    /// Returns the essence, i.e. `type/subtype` without parameters, in lowercase.
+    pub fn essence(&self) -> &str {
+        self.mime.essence_str()
     }
 
-    #[cfg(feature = "extension-light")]
-    if let Ok(guessed_light) = Mime::from_ext_light(ext) {
-        return Ok(guessed_light);
+    /// Returns the value of a parameter (without quotes), if any. The name is matched case-insensitively.
+    pub fn get_param(&self, name: &str) -> Option<&str> {
+        self.mime
+            .params()
+            .find(|(param, _)| param.as_str().eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
     }
 
-    #[cfg(feature = "magic")]
-    if let Ok(inferred) = Mime::from_content(data) {
-        return Ok(inferred);
+    /// Returns the structured syntax suffix of the subtype, e.g. `json` for `application/ld+json` or `xml` for `image/svg+xml`.
+    pub fn suffix(&self) -> Option<&str> {
+        self.mime.suffix().map(|suffix| suffix.as_str())
     }
 
-    #[cfg(feature = "texture")]
-    if texture::is_texture_std(data) {
-        return Ok(Mime::new(mime::TEXT_PLAIN));
+    /// Returns `true` for `*/json` and any `+json` subtype, e.g. `application/vnd.api+json`.
+    pub fn is_json_compatible(&self) -> bool {
+        self.mime.subtype() == mime::JSON || self.mime.suffix() == Some(mime::JSON)
     }
 
-    Ok(Mime::new(mime::APPLICATION_OCTET_STREAM))
-}
+    /// Returns `true` for `*/xml` and any `+xml` subtype, e.g. `image/svg+xml`.
+    pub fn is_xml_compatible(&self) -> bool {
+        self.mime.subtype() == mime::XML || self.mime.suffix() == Some(mime::XML)
+    }
 
+    /// Returns `true` if the top-level type is `image`.
+    pub fn is_image(&self) -> bool {
+        self.mime.type_() == mime::IMAGE
+    }
 
-/// Guesses the MIME type from the path and content. It is a combination of `from_path` and `from_content`, and set the priority of `from_content` higher than `from_path`.
-/// If the function can't guess the MIME type, it will return `application/octet-stream` if the feature `texture` is disabled, otherwise it will return `text/plain` if the data is a texture or `application/octet-stream` if the data is not a texture.
-pub fn from_path_and_content(
-    path: &Path,
-    #[cfg(feature = "magic")] data: &[u8],
-) -> anyhow::Result<Mime> {
-    #[cfg(feature = "extension")]
-    if let Ok(guessed) = Mime::from_path(path) {
-        return Ok(guessed);
+    /// Returns `true` if the top-level type is `audio`.
+    pub fn is_audio(&self) -> bool {
+        self.mime.type_() == mime::AUDIO
     }
 
-    #[cfg(feature = "extension-light")]
-    if let Ok(guessed_light) = Mime::from_path_light(path) {
-        return Ok(guessed_light);
+    /// Returns `true` if the top-level type is `video`.
+    pub fn is_video(&self) -> bool {
+        self.mime.type_() == mime::VIDEO
     }
 
-    #[cfg(feature = "magic")]
-    if let Ok(inferred) = Mime::from_content(data) {
-        return Ok(inferred);
+    /// Returns `true` if the top-level type is `font`, or for the legacy `application` font types, e.g. `application/font-woff` or `application/vnd.ms-fontobject`.
+    pub fn is_font(&self) -> bool {
+        self.mime.type_() == mime::FONT
+            || matches!(
+                self.mime.essence_str(),
+                "application/font-woff"
+                    | "application/font-sfnt"
+                    | "application/x-font-ttf"
+                    | "application/x-font-otf"
+                    | "application/vnd.ms-fontobject"
+            )
     }
 
-    #[cfg(feature = "texture")]
-    if texture::is_texture_std(data) {
-        return Ok(Mime::new(mime::TEXT_PLAIN));
+    /// Returns `true` if the top-level type is `text`, or for the textual types `texture::is_texture_mime` accepts, i.e. JSON and XML and their `+json` and `+xml` subtypes.
+    pub fn is_text(&self) -> bool {
+        self.mime.type_() == mime::TEXT || self.is_json_compatible() || self.is_xml_compatible()
+    }
+
+    /// The same as `is_text`, named as the counterpart of `is_binary`. It only looks at the type; see `is_texture` to inspect the data as well.
+    pub fn is_textual(&self) -> bool {
+        self.is_text()
+    }
+
+    /// Returns `true` for every type `is_textual` rejects, e.g. `image/png` or `application/epub+zip`.
+    pub fn is_binary(&self) -> bool {
+        !self.is_textual()
+    }
+
+    /// Returns the essence (type, subtype, and suffix) without parameters, e.g. `text/html` for `text/html; charset=utf-8`. Use `essence_display` to format it without allocating.
+    pub fn to_essence_string(&self) -> alloc::string::String {
+        self.mime.essence_str().into()
+    }
+
+    /// Formats the essence without parameters, e.g. into a cache key with `write!`.
+    pub fn essence_display(&self) -> EssenceDisplay<'_> {
+        EssenceDisplay(self)
+    }
+
+    /// Compares the essences (type, subtype, and suffix) case-insensitively, ignoring parameters. `PartialEq` stays strict and compares the parameter values too, so `text/html; charset=utf-8` is only equal to `text/html` here, and `text/plain; format=flowed` to `text/plain; format=fixed`.
+    pub fn eq_essence(&self, other: &Mime) -> bool {
+        self.mime.essence_str() == other.mime.essence_str()
+    }
+
+    /// The same as `eq_essence`, against a string that may carry parameters. A string that doesn't parse is never equal.
+    pub fn essence_eq_str(&self, s: &str) -> bool {
+        MimeType::from_str(s).is_ok_and(|other| self.mime.essence_str() == other.essence_str())
+    }
+
+    /// Replaces an alias essence with its canonical spelling from `alias::ALIASES`, e.g. `image/jpg` with `image/jpeg` or `text/xml` with `application/xml`, keeping the parameters. Other types are returned unchanged.
+    pub fn canonicalize(&self) -> Mime {
+        let Some(canonical) = alias::canonical_essence(self.mime.essence_str()) else {
+            return self.clone();
+        };
+        let params = &self.mime.as_ref()[self.mime.essence_str().len()..];
+        Self::from_str(&alloc::format!("{}{}", canonical, params))
+            .expect("canonical essences are valid")
+    }
+
+    /// Compares the canonicalized types, parameters included.
+    pub fn eq_canonical(&self, other: &Mime) -> bool {
+        self.canonicalize() == other.canonicalize()
     }
 
-    Ok(Mime::new(mime::APPLICATION_OCTET_STREAM))
+    /// Returns `true` if the essence matches `pattern`, e.g. `image/*` or `*/*`. A pattern that doesn't parse never matches; parse a `MimePattern` once to validate and reuse it.
+    pub fn matches(&self, pattern: &str) -> bool {
+        MimePattern::from_str(pattern).is_ok_and(|pattern| pattern.matches(self))
+    }
+
+    /// Returns `true` for media ranges, i.e. `*/*` and `type/*`, which match types rather than name one.
+    pub fn is_wildcard(&self) -> bool {
+        self.mime.type_() == mime::STAR || self.mime.subtype() == mime::STAR
+    }
+
+    /// The same as `FromStr`, but rejects media ranges such as `image/*` with `MimeError::Wildcard`, for places that need a concrete type.
+    pub fn from_concrete_str(s: &str) -> Result<Self, MimeError> {
+        let mime = Self::from_str(s)?;
+        if mime.is_wildcard() {
+            return Err(MimeError::Wildcard(mime.to_essence_string()));
+        }
+        Ok(mime)
+    }
+
+    /// Ranks the types as media ranges: a concrete type is more specific than `type/*`, which is more specific than `*/*`, and among equally specific types more parameters win. Types that differ only otherwise are `Equal`, so it fits a stable sort, e.g. `text/html; level=1 > text/html > text/* > */*`.
+    pub fn cmp_specificity(&self, other: &Mime) -> core::cmp::Ordering {
+        fn rank(mime: &MimeType) -> (u8, usize) {
+            let wildcards = if mime.type_() == mime::STAR {
+                0
+            } else if mime.subtype() == mime::STAR {
+                1
+            } else {
+                2
+            };
+            (wildcards, mime.params().count())
+        }
+        rank(&self.mime).cmp(&rank(&other.mime))
+    }
+
+    /// Returns `true` if an HTTP response of this type is worth compressing, roughly following the `compressible` flag of [mime-db](https://github.com/jshttp/mime-db): text, JSON, XML, JavaScript, WebAssembly, and uncompressed fonts and bitmaps. Already compressed formats, such as PNG, MP4, WOFF, or ZIP, are not.
+    pub fn is_compressible(&self) -> bool {
+        self.mime.type_() == mime::TEXT
+            || self.is_json_compatible()
+            || self.is_xml_compatible()
+            || matches!(
+                self.mime.essence_str(),
+                "application/javascript"
+                    | "application/x-javascript"
+                    | "application/ecmascript"
+                    | "application/wasm"
+                    | "application/yaml"
+                    | "application/toml"
+                    | "application/postscript"
+                    | "application/rtf"
+                    | "application/x-font-ttf"
+                    | "application/x-font-otf"
+                    | "application/vnd.ms-fontobject"
+                    | "font/ttf"
+                    | "font/otf"
+                    | "font/collection"
+                    | "image/bmp"
+                    | "image/x-icon"
+                    | "image/vnd.microsoft.icon"
+            )
+    }
+
+    /// Returns `true` for `application/octet-stream`, with any parameters, i.e. the fallback of the guessing functions.
+    pub fn is_octet_stream(&self) -> bool {
+        self.mime.essence_str() == "application/octet-stream"
+    }
+
+    /// Returns the coarse category of the MIME type, from its top-level type.
+    pub fn category(&self) -> MediaCategory {
+        match self.mime.type_() {
+            mime::TEXT => MediaCategory::Text,
+            mime::IMAGE => MediaCategory::Image,
+            mime::AUDIO => MediaCategory::Audio,
+            mime::VIDEO => MediaCategory::Video,
+            mime::FONT => MediaCategory::Font,
+            mime::APPLICATION => MediaCategory::Application,
+            mime::MULTIPART => MediaCategory::Multipart,
+            mime::MESSAGE => MediaCategory::Message,
+            ty if ty == "model" => MediaCategory::Model,
+            _ => MediaCategory::Other,
+        }
+    }
+
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    #[cfg_attr(
+        any(feature = "extension", feature = "extension-light"),
+        doc = "Returns the preferred file extension for the MIME type (without the dot), e.g. `webp` for `image/webp`. Types with several extensions resolve to the usual one, e.g. `jpg` for `image/jpeg`. Common types come from a built-in table, and others from [`mime_guess`](https://docs.rs/mime_guess/latest/mime_guess/) with the `extension` feature."
+    )]
+    pub fn preferred_extension(&self) -> Option<&'static str> {
+        preferred_ext::preferred_extension(&self.mime)
+    }
+
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    #[cfg_attr(
+        any(feature = "extension", feature = "extension-light"),
+        doc = "Returns every known extension for the MIME type, from the light table and, with the `extension` feature, from [`mime_guess`](https://docs.rs/mime_guess/latest/mime_guess/). The preferred extension comes first and the others follow in alphabetical order. Unknown types give an empty list."
+    )]
+    pub fn all_extensions(&self) -> alloc::vec::Vec<&'static str> {
+        preferred_ext::all_extensions(&self.mime)
+    }
+
+    /// Returns the `charset` parameter (without quotes), if any.
+    pub fn charset(&self) -> Option<&str> {
+        self.get_param("charset")
+    }
+
+    /// Returns the `charset` parameter normalized by `Charset::from_label`, so that `utf-8`, `UTF8` and `utf_8` are all `Charset::Utf8`.
+    pub fn normalized_charset(&self) -> Option<Charset> {
+        self.charset().map(Charset::from_label)
+    }
+
+    /// Returns `true` if the `charset` parameter names UTF-8, however it is spelled. A missing parameter is `false`; see `default_charset` for the implied one.
+    pub fn charset_is_utf8(&self) -> bool {
+        self.normalized_charset()
+            .is_some_and(|charset| charset.is_utf8())
+    }
+
+    /// Returns the charset a recipient assumes when the `charset` parameter is missing, or `None` if the type has no implied charset. JSON ([RFC 8259](https://www.rfc-editor.org/rfc/rfc8259#section-8.1)), JavaScript ([RFC 9239](https://www.rfc-editor.org/rfc/rfc9239#section-4.2)), CSS, and event streams are UTF-8, and other `text/*` types are US-ASCII ([RFC 2046](https://www.rfc-editor.org/rfc/rfc2046#section-4.1.2)), except HTML and XML, whose documents declare their own encoding.
+    pub fn default_charset(&self) -> Option<&'static str> {
+        match self.mime.essence_str() {
+            "text/html" => None,
+            "text/javascript" | "application/javascript" | "text/css" | "text/event-stream" => {
+                Some("utf-8")
+            }
+            _ if self.is_json_compatible() => Some("utf-8"),
+            _ if self.is_xml_compatible() => None,
+            _ if self.mime.type_() == mime::TEXT => Some("us-ascii"),
+            _ => None,
+        }
+    }
+
+    /// Returns the `boundary` parameter (without quotes), if any.
+    pub fn boundary(&self) -> Option<&str> {
+        self.mime
+            .get_param(mime::BOUNDARY)
+            .map(|boundary| boundary.as_str())
+    }
+
+    #[cfg(feature = "iana")]
+    #[cfg_attr(
+        feature = "iana",
+        doc = "Returns `true` if the essence is a known registration of the IANA registry. See the `iana` module."
+    )]
+    pub fn is_registered(&self) -> bool {
+        self.registration().is_some()
+    }
+
+    #[cfg(feature = "iana")]
+    #[cfg_attr(
+        feature = "iana",
+        doc = "Returns the IANA registration of the essence, if it is known."
+    )]
+    pub fn registration(&self) -> Option<iana::Registration> {
+        iana::registration(self.mime.essence_str())
+    }
+
+    #[cfg(feature = "iana")]
+    #[cfg_attr(
+        feature = "iana",
+        doc = "The same as `from_str`, but fails with `MimeError::Unregistered` unless the essence is a known IANA registration, e.g. for `application/x-my-thing`. Obsolete registrations are accepted; check `registration` to reject them too."
+    )]
+    pub fn from_str_strict(s: &str) -> Result<Self, MimeError> {
+        let mime = Self::from_str(s)?;
+        if !mime.is_registered() {
+            return Err(MimeError::Unregistered(mime.to_essence_string()));
+        }
+        Ok(mime)
+    }
+
+    #[cfg(feature = "iana")]
+    #[cfg_attr(
+        feature = "iana",
+        doc = "Returns the registration tree of the subtype, e.g. `Vendor` for `vnd.` subtypes."
+    )]
+    pub fn tree(&self) -> iana::Tree {
+        iana::Tree::of(self.mime.subtype().as_str())
+    }
+
+    #[cfg(feature = "texture")]
+    #[cfg_attr(
+        feature = "texture",
+        doc = "Check the file is a texture or not. It is useful in the case of handling texture files. Empty data is decided by the type alone, like `is_textual`."
+    )]
+    pub fn is_texture(&self, data: &[u8]) -> bool {
+        texture::is_texture_mime(&self.mime) || (!data.is_empty() && texture::is_texture_std(data))
+    }
+}
+
+impl core::ops::Deref for Mime {
+    type Target = MimeType;
+
+    fn deref(&self) -> &MimeType {
+        &self.mime
+    }
+}
+
+/// The same as `as_mime`, e.g. to look up a `HashMap<mime::Mime, T>` with `map.get(mime.as_ref())`.
+impl AsRef<MimeType> for Mime {
+    fn as_ref(&self) -> &MimeType {
+        &self.mime
+    }
+}
+
+impl core::fmt::Display for Mime {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.mime)
+    }
+}
+
+/// Returns the form `Mime` compares and hashes: the essence, the parameter names and the `charset` value lowercased, and the parameters sorted, so that their order doesn't matter. The inner `mime::Mime` only compares parameter names, in order.
+fn normalized(
+    mime: &MimeType,
+) -> (
+    alloc::string::String,
+    alloc::vec::Vec<(alloc::string::String, alloc::string::String)>,
+) {
+    let mut params: alloc::vec::Vec<_> = mime
+        .params()
+        .map(|(name, value)| {
+            let name = name.as_str().to_ascii_lowercase();
+            let value = if name == "charset" {
+                value.as_str().to_ascii_lowercase()
+            } else {
+                value.as_str().into()
+            };
+            (name, value)
+        })
+        .collect();
+    params.sort_unstable();
+    (mime.essence_str().to_ascii_lowercase(), params)
+}
+
+/// Compares the normalized forms, skipping them when the strings are identical.
+fn mime_eq(a: &MimeType, b: &MimeType) -> bool {
+    a.as_ref() == b.as_ref() || normalized(a) == normalized(b)
+}
+
+impl PartialEq for Mime {
+    fn eq(&self, other: &Self) -> bool {
+        mime_eq(&self.mime, &other.mime)
+    }
+}
+
+impl Eq for Mime {}
+
+impl PartialOrd for Mime {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders the normalized forms, consistent with `Eq`.
+impl Ord for Mime {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        if self.mime.as_ref() == other.mime.as_ref() {
+            return core::cmp::Ordering::Equal;
+        }
+        normalized(&self.mime).cmp(&normalized(&other.mime))
+    }
+}
+
+/// Hashes the normalized form `Eq` compares.
+///
+/// The inner `mime::Mime` hashes its source string instead, so equal types may hash differently there. That is why `Mime` doesn't implement `Borrow<mime::Mime>`: a `HashMap<Mime, T>` looked up by a `mime::Mime` would miss. Maps keyed by `mime::Mime` are looked up with `as_ref()`.
+impl core::hash::Hash for Mime {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        core::hash::Hash::hash(&normalized(&self.mime), state);
+    }
+}
+
+/// Serializes as the canonical string form, e.g. `"image/avif"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Mime {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.mime.as_ref())
+    }
+}
+
+/// Deserializes from a string through `FromStr`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Mime {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MimeVisitor;
+
+        impl serde::de::Visitor<'_> for MimeVisitor {
+            type Value = Mime;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("a media type string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Mime, E> {
+                Mime::from_str(v)
+                    .map_err(|err| E::custom(format_args!("invalid media type {v:?}: {err}")))
+            }
+        }
+
+        deserializer.deserialize_str(MimeVisitor)
+    }
+}
+
+impl FromStr for Mime {
+    type Err = MimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            mime: MimeType::from_str(s)?,
+        })
+    }
+}
+
+/// `application/octet-stream`, the fallback of the guessing functions.
+impl Default for Mime {
+    fn default() -> Self {
+        Self::APPLICATION_OCTET_STREAM
+    }
+}
+
+impl From<MimeType> for Mime {
+    fn from(mime: MimeType) -> Self {
+        Self::new(mime)
+    }
+}
+
+impl From<Mime> for MimeType {
+    fn from(mime: Mime) -> Self {
+        mime.mime
+    }
+}
+
+impl TryFrom<&str> for Mime {
+    type Error = MimeError;
+
+    /// The same as `FromStr`.
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+#[cfg(all(
+    feature = "std",
+    any(feature = "extension", feature = "extension-light")
+))]
+impl TryFrom<&Path> for Mime {
+    type Error = MimeError;
+
+    /// Guesses the MIME type from the extension with `from_path` if the `extension` feature is enabled, then with `from_path_light` if the `extension-light` feature is enabled.
+    #[allow(clippy::let_and_return)]
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        #[cfg(feature = "extension")]
+        let guessed = Self::from_path(path);
+        #[cfg(all(feature = "extension", feature = "extension-light"))]
+        let guessed = guessed.or_else(|_| Self::from_path_light(path));
+        #[cfg(not(feature = "extension"))]
+        let guessed = Self::from_path_light(path);
+        guessed
+    }
+}
+
+#[cfg(feature = "magic")]
+impl TryFrom<&[u8]> for Mime {
+    type Error = MimeError;
+
+    /// The same as `from_content`.
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_content(data)
+    }
+}
+
+/// Parses the right-hand side and compares like `Mime == Mime`, parameter values included, in any order; see `Mime::essence_eq_str` to ignore them. A string that doesn't parse is never equal.
+impl<'a> PartialEq<&'a str> for Mime {
+    fn eq(&self, other: &&'a str) -> bool {
+        MimeType::from_str(other).is_ok_and(|other| mime_eq(&self.mime, &other))
+    }
+}
+
+/// The same as `Mime == &str`, for assertions written the other way around.
+impl PartialEq<Mime> for &str {
+    fn eq(&self, other: &Mime) -> bool {
+        other == self
+    }
+}
+
+/// Compares with an unwrapped `mime::Mime` like `Mime == Mime`, parameter values included, which is stricter than `mime::Mime == mime::Mime`.
+impl PartialEq<MimeType> for Mime {
+    fn eq(&self, other: &MimeType) -> bool {
+        mime_eq(&self.mime, other)
+    }
+}
+
+/// The same as `Mime == mime::Mime`.
+impl PartialEq<Mime> for MimeType {
+    fn eq(&self, other: &Mime) -> bool {
+        mime_eq(self, &other.mime)
+    }
+}
+
+/// Returns the extension the guessing functions use for the path: a compound extension such as `tar.gz` or `d.ts` when the last two segments are known, otherwise the last segment. It tells a tarball from a plain gzip file, which both guess as `application/gzip`.
+#[cfg(all(
+    feature = "std",
+    any(feature = "extension", feature = "extension-light")
+))]
+pub fn full_extension(path: &Path) -> Option<&str> {
+    utils::get_extension(path)
+}
+
+/// Guesses the MIME type from the extension and content. It is a combination of `from_ext` and `from_content`, and consults the extension first: the `mime_guess` table, then the light table, then the magic numbers.
+/// If the function can't guess the MIME type, it will return `application/octet-stream` if the feature `texture` is disabled, otherwise it will return `text/plain` if the data is a texture or `application/octet-stream` if the data is not a texture. Use a `MimeGuesser` to prefer the content or to disable some sources.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(ext = %ext))
+)]
+pub fn from_ext_and_content(
+    ext: &str,
+    #[cfg(any(feature = "magic", feature = "texture"))] data: &[u8],
+) -> Result<Mime, MimeError> {
+    let guesser = MimeGuesser::new();
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    let guesser = guesser.with_extension(ext);
+    #[cfg(not(any(feature = "extension", feature = "extension-light")))]
+    let _ = ext;
+    #[cfg(any(feature = "magic", feature = "texture"))]
+    let guesser = guesser.with_data(data);
+    Ok(guesser.guess())
+}
+
+/// Guesses the MIME type from the path and content. It is a combination of `from_path` and `from_content`, and consults the path first, like `from_ext_and_content`.
+/// If the function can't guess the MIME type, it will return `application/octet-stream` if the feature `texture` is disabled, otherwise it will return `text/plain` if the data is a texture or `application/octet-stream` if the data is not a texture.
+#[cfg(feature = "std")]
+#[cfg_attr(
+    not(any(feature = "extension", feature = "extension-light")),
+    allow(unused_variables)
+)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(path = %path.display()))
+)]
+pub fn from_path_and_content(
+    path: &Path,
+    #[cfg(any(feature = "magic", feature = "texture"))] data: &[u8],
+) -> Result<Mime, MimeError> {
+    let guesser = MimeGuesser::new();
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    let guesser = guesser.with_path(path);
+    #[cfg(any(feature = "magic", feature = "texture"))]
+    let guesser = guesser.with_data(data);
+    Ok(guesser.guess())
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "extension")]
+    mod extension_guessing {
+        use crate::Mime;
+        use std::path::Path;
+
+        #[test]
+        fn no_extension() {
+            assert!(Mime::from_path(Path::new("Makefile")).is_err());
+            assert!(Mime::from_path(Path::new("dir.d/")).is_err());
+            assert_eq!(
+                Mime::from_path_or_octet_stream(Path::new("Makefile")),
+                "application/octet-stream"
+            );
+        }
+
+        #[test]
+        fn unknown_extension() {
+            assert!(Mime::from_path(Path::new("archive.xyzzy")).is_err());
+            assert!(Mime::from_ext("xyzzy").is_err());
+            assert!(Mime::from_ext("").is_err());
+            assert_eq!(
+                Mime::from_path_or_octet_stream(Path::new("archive.xyzzy")),
+                "application/octet-stream"
+            );
+            assert_eq!(
+                Mime::from_path_or_octet_stream(Path::new("archive.zip")),
+                "application/zip"
+            );
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn non_utf8_extension() {
+            use std::ffi::OsStr;
+            use std::os::unix::ffi::OsStrExt;
+
+            let path = Path::new(OsStr::from_bytes(b"image.\xFFpng"));
+            assert!(Mime::from_path(path).is_err());
+            assert_eq!(
+                Mime::from_path_or_octet_stream(path),
+                "application/octet-stream"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_support {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Config {
+            output: Mime,
+            accept: Vec<Mime>,
+        }
+
+        #[test]
+        fn round_trip() {
+            let json = r#"{"output":"image/avif","accept":["text/html; charset=utf-8","image/*"]}"#;
+            let config: Config = serde_json::from_str(json).unwrap();
+            assert_eq!(config.output, Mime::from_str("image/avif").unwrap());
+            assert_eq!(config.accept[0], "text/html; charset=utf-8");
+            assert_eq!(serde_json::to_string(&config).unwrap(), json);
+
+            let value = serde_json::to_value(Mime::new(mime::TEXT_CSS)).unwrap();
+            assert_eq!(value, serde_json::json!("text/css"));
+            assert_eq!(serde_json::from_value::<Mime>(value).unwrap(), "text/css");
+        }
+
+        #[test]
+        fn invalid() {
+            let err = serde_json::from_str::<Mime>(r#""notamime""#).unwrap_err();
+            assert!(err.to_string().contains("invalid media type \"notamime\""));
+            assert!(serde_json::from_str::<Mime>("42").is_err());
+        }
+    }
+
+    mod accessors {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        #[test]
+        fn essence_and_params() {
+            let html = Mime::from_str("Text/HTML; charset=utf-8; Level=\"1\"").unwrap();
+            assert_eq!(html.essence(), "text/html");
+            assert_eq!(html.charset(), Some("utf-8"));
+            assert_eq!(html.get_param("CHARSET"), Some("utf-8"));
+            assert_eq!(html.get_param("level"), Some("1"));
+            assert_eq!(html.get_param("missing"), None);
+
+            let png = Mime::new(mime::IMAGE_PNG);
+            assert_eq!(png.essence(), "image/png");
+            assert_eq!(png.charset(), None);
+        }
+
+        #[test]
+        fn normalized_charset() {
+            use crate::Charset;
+            for (ty, charset) in [
+                ("text/plain; charset=utf-8", Some(Charset::Utf8)),
+                ("text/plain; charset=UTF8", Some(Charset::Utf8)),
+                ("text/plain; charset=\"utf_8\"", Some(Charset::Utf8)),
+                ("text/plain; charset=latin1", Some(Charset::Latin1)),
+                ("text/plain", None),
+            ] {
+                let mime = Mime::from_str(ty).unwrap();
+                assert_eq!(mime.normalized_charset(), charset, "{ty}");
+                assert_eq!(
+                    mime.charset_is_utf8(),
+                    charset == Some(Charset::Utf8),
+                    "{ty}"
+                );
+            }
+        }
+
+        #[test]
+        fn type_and_subtype() {
+            let svg = Mime::from_str("Image/SVG+XML; charset=utf-8").unwrap();
+            assert_eq!(svg.type_(), mime::IMAGE);
+            assert_eq!(svg.subtype(), "svg");
+            assert_eq!(svg.suffix(), Some("xml"));
+            assert_eq!(Mime::TEXT_CSS.type_(), "text");
+            assert_eq!(Mime::TEXT_CSS.subtype(), mime::CSS);
+        }
+    }
+
+    mod suffixes {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        fn mime(s: &str) -> Mime {
+            Mime::from_str(s).unwrap()
+        }
+
+        #[test]
+        fn suffix() {
+            assert_eq!(mime("application/ld+json").suffix(), Some("json"));
+            assert_eq!(mime("image/svg+xml").suffix(), Some("xml"));
+            assert_eq!(mime("application/vnd.api+json").suffix(), Some("json"));
+            assert_eq!(mime("application/epub+zip").suffix(), Some("zip"));
+            assert_eq!(mime("application/json").suffix(), None);
+        }
+
+        #[test]
+        fn compatibility() {
+            for json in [
+                "application/json",
+                "application/ld+json",
+                "application/vnd.api+json; charset=utf-8",
+            ] {
+                assert!(mime(json).is_json_compatible(), "{json}");
+                assert!(!mime(json).is_xml_compatible(), "{json}");
+            }
+            for xml in [
+                "text/xml",
+                "application/xml",
+                "image/svg+xml",
+                "application/atom+xml",
+            ] {
+                assert!(mime(xml).is_xml_compatible(), "{xml}");
+                assert!(!mime(xml).is_json_compatible(), "{xml}");
+            }
+            assert!(!mime("application/jsonx").is_json_compatible());
+            assert!(!mime("text/plain").is_xml_compatible());
+        }
+    }
+
+    mod categories {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        #[test]
+        fn predicates() {
+            // (type, image, audio, video, font, text)
+            let table = [
+                ("image/png", true, false, false, false, false),
+                ("image/jpeg", true, false, false, false, false),
+                ("image/gif", true, false, false, false, false),
+                ("image/webp", true, false, false, false, false),
+                ("image/avif", true, false, false, false, false),
+                ("image/svg+xml", true, false, false, false, true),
+                ("audio/mpeg", false, true, false, false, false),
+                ("audio/ogg", false, true, false, false, false),
+                ("audio/wav", false, true, false, false, false),
+                ("video/mp4", false, false, true, false, false),
+                ("video/webm", false, false, true, false, false),
+                ("font/woff2", false, false, false, true, false),
+                ("font/ttf", false, false, false, true, false),
+                ("application/font-woff", false, false, false, true, false),
+                (
+                    "application/vnd.ms-fontobject",
+                    false,
+                    false,
+                    false,
+                    true,
+                    false,
+                ),
+                (
+                    "text/plain; charset=utf-8",
+                    false,
+                    false,
+                    false,
+                    false,
+                    true,
+                ),
+                ("text/html", false, false, false, false, true),
+                ("text/css", false, false, false, false, true),
+                ("text/javascript", false, false, false, false, true),
+                ("application/json", false, false, false, false, true),
+                ("application/ld+json", false, false, false, false, true),
+                ("application/xml", false, false, false, false, true),
+                ("application/atom+xml", false, false, false, false, true),
+                ("application/pdf", false, false, false, false, false),
+                (
+                    "application/octet-stream",
+                    false,
+                    false,
+                    false,
+                    false,
+                    false,
+                ),
+                ("application/wasm", false, false, false, false, false),
+            ];
+            for (ty, image, audio, video, font, text) in table {
+                let mime = Mime::from_str(ty).unwrap();
+                assert_eq!(
+                    (
+                        mime.is_image(),
+                        mime.is_audio(),
+                        mime.is_video(),
+                        mime.is_font(),
+                        mime.is_text()
+                    ),
+                    (image, audio, video, font, text),
+                    "{ty}"
+                );
+            }
+        }
+
+        #[test]
+        fn essence_display() {
+            let html = Mime::from_str("text/html; charset=utf-8").unwrap();
+            assert_eq!(html.to_essence_string(), "text/html");
+            assert_eq!(
+                alloc::format!("{}.cache", html.essence_display()),
+                "text/html.cache"
+            );
+            let svg = Mime::from_str("IMAGE/SVG+XML; charset=utf-8").unwrap();
+            assert_eq!(svg.to_essence_string(), "image/svg+xml");
+            assert_eq!(svg.essence_display().to_string(), "image/svg+xml");
+        }
+
+        #[test]
+        fn wildcards() {
+            for ty in ["*/*", "image/*", "TEXT/*; charset=utf-8"] {
+                assert!(Mime::from_str(ty).unwrap().is_wildcard(), "{ty}");
+                assert!(
+                    matches!(
+                        Mime::from_concrete_str(ty),
+                        Err(crate::MimeError::Wildcard(_))
+                    ),
+                    "{ty}"
+                );
+            }
+            for ty in ["image/png", "image/svg+xml", "text/plain; charset=utf-8"] {
+                assert!(!Mime::from_str(ty).unwrap().is_wildcard(), "{ty}");
+                assert_eq!(
+                    Mime::from_concrete_str(ty).unwrap(),
+                    Mime::from_str(ty).unwrap()
+                );
+            }
+            assert!(matches!(
+                Mime::from_concrete_str("not a mime"),
+                Err(crate::MimeError::Parse(_))
+            ));
+        }
+
+        #[test]
+        fn specificity() {
+            use core::cmp::Ordering;
+            let mut ranges: Vec<Mime> = ["*/*", "text/html", "text/*", "text/html; level=1"]
+                .into_iter()
+                .map(|ty| Mime::from_str(ty).unwrap())
+                .collect();
+            ranges.sort_by(|a, b| b.cmp_specificity(a));
+            assert_eq!(ranges, ["text/html; level=1", "text/html", "text/*", "*/*"]);
+            assert_eq!(
+                Mime::TEXT_HTML.cmp_specificity(&Mime::IMAGE_PNG),
+                Ordering::Equal
+            );
+            assert_eq!(
+                Mime::TEXT_PLAIN_UTF_8.cmp_specificity(&Mime::TEXT_STAR),
+                Ordering::Greater
+            );
+        }
+
+        #[test]
+        fn textual_and_binary() {
+            for ty in [
+                "text/plain",
+                "TEXT/HTML; charset=utf-8",
+                "application/json",
+                "application/vnd.api+json",
+                "application/xml",
+                "application/atom+xml",
+                "image/svg+xml",
+            ] {
+                let mime = Mime::from_str(ty).unwrap();
+                assert!(mime.is_textual() && !mime.is_binary(), "{ty}");
+                #[cfg(feature = "texture")]
+                assert!(crate::texture::is_texture_mime(&mime), "{ty}");
+            }
+            for ty in [
+                "image/png",
+                "application/octet-stream",
+                "application/epub+zip",
+                "application/pdf",
+                "font/woff2",
+            ] {
+                let mime = Mime::from_str(ty).unwrap();
+                assert!(mime.is_binary() && !mime.is_textual(), "{ty}");
+                #[cfg(feature = "texture")]
+                assert!(!crate::texture::is_texture_mime(&mime), "{ty}");
+            }
+        }
+
+        #[cfg(feature = "texture")]
+        #[test]
+        fn empty_texture() {
+            assert!(!Mime::IMAGE_PNG.is_texture(&[]));
+            assert!(Mime::TEXT_CSS.is_texture(&[]));
+            assert!(Mime::IMAGE_PNG.is_texture(b"plain words"));
+        }
+
+        #[test]
+        fn default_charset() {
+            let table = [
+                ("application/json", Some("utf-8")),
+                ("application/ld+json", Some("utf-8")),
+                ("text/javascript", Some("utf-8")),
+                ("application/javascript", Some("utf-8")),
+                ("text/css", Some("utf-8")),
+                ("text/html", None),
+                ("text/xml", None),
+                ("image/svg+xml", None),
+                ("text/plain", Some("us-ascii")),
+                ("text/csv", Some("us-ascii")),
+                ("image/png", None),
+                ("application/octet-stream", None),
+            ];
+            for (ty, charset) in table {
+                assert_eq!(
+                    Mime::from_str(ty).unwrap().default_charset(),
+                    charset,
+                    "{ty}"
+                );
+            }
+        }
+
+        #[test]
+        fn compressible() {
+            let table = [
+                ("text/html; charset=utf-8", true),
+                ("text/css", true),
+                ("text/javascript", true),
+                ("text/event-stream", true),
+                ("application/json", true),
+                ("application/ld+json", true),
+                ("application/manifest+json", true),
+                ("application/xml", true),
+                ("application/atom+xml", true),
+                ("image/svg+xml", true),
+                ("application/javascript", true),
+                ("application/wasm", true),
+                ("font/ttf", true),
+                ("font/otf", true),
+                ("application/vnd.ms-fontobject", true),
+                ("image/bmp", true),
+                ("image/x-icon", true),
+                ("font/woff", false),
+                ("font/woff2", false),
+                ("image/png", false),
+                ("image/jpeg", false),
+                ("image/webp", false),
+                ("video/mp4", false),
+                ("audio/mpeg", false),
+                ("application/zip", false),
+                ("application/gzip", false),
+                ("application/pdf", false),
+                ("application/octet-stream", false),
+            ];
+            for (ty, compressible) in table {
+                assert_eq!(
+                    Mime::from_str(ty).unwrap().is_compressible(),
+                    compressible,
+                    "{ty}"
+                );
+            }
+        }
+
+        #[test]
+        fn category() {
+            use crate::MediaCategory;
+            let table = [
+                ("text/plain", MediaCategory::Text),
+                ("TEXT/HTML; charset=utf-8", MediaCategory::Text),
+                ("image/png", MediaCategory::Image),
+                ("image/svg+xml", MediaCategory::Image),
+                ("audio/mpeg", MediaCategory::Audio),
+                ("video/mp4", MediaCategory::Video),
+                ("font/woff2", MediaCategory::Font),
+                ("application/octet-stream", MediaCategory::Application),
+                ("application/ld+json", MediaCategory::Application),
+                ("application/atom+xml", MediaCategory::Application),
+                ("multipart/form-data; boundary=x", MediaCategory::Multipart),
+                ("message/rfc822", MediaCategory::Message),
+                ("model/gltf-binary", MediaCategory::Model),
+                ("example/foo", MediaCategory::Other),
+                ("x-custom/bar", MediaCategory::Other),
+            ];
+            for (ty, category) in table {
+                assert_eq!(Mime::from_str(ty).unwrap().category(), category, "{ty}");
+            }
+        }
+
+        #[cfg(feature = "texture")]
+        #[test]
+        fn text_matches_texture() {
+            for ty in [
+                "text/csv",
+                "application/json",
+                "image/svg+xml",
+                "image/png",
+                "application/zip",
+            ] {
+                let mime = Mime::from_str(ty).unwrap();
+                assert_eq!(
+                    mime.is_text(),
+                    crate::texture::is_texture_mime(mime.as_mime()),
+                    "{ty}"
+                );
+            }
+        }
+    }
+
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    mod preferred_extension {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        fn preferred(s: &str) -> Option<&'static str> {
+            Mime::from_str(s).unwrap().preferred_extension()
+        }
+
+        #[test]
+        fn common_types() {
+            assert_eq!(preferred("image/webp"), Some("webp"));
+            assert_eq!(preferred("image/jpeg"), Some("jpg"));
+            assert_eq!(preferred("IMAGE/JPEG; q=0.5"), Some("jpg"));
+            assert_eq!(preferred("text/html; charset=utf-8"), Some("html"));
+            assert_eq!(preferred("audio/midi"), Some("mid"));
+            assert_eq!(preferred("image/svg+xml"), Some("svg"));
+            assert_eq!(preferred("*/*"), None);
+        }
+
+        fn all(s: &str) -> alloc::vec::Vec<&'static str> {
+            Mime::from_str(s).unwrap().all_extensions()
+        }
+
+        #[cfg(feature = "extension-light")]
+        #[test]
+        fn all_extensions_light() {
+            let javascript = all("text/javascript");
+            assert_eq!(javascript[0], "js");
+            for ext in ["jsx", "mjs"] {
+                assert!(javascript.contains(&ext), "{ext}");
+            }
+            let jpeg = all("image/jpeg");
+            assert_eq!(jpeg[0], "jpg");
+            assert!(jpeg.contains(&"jpeg"));
+            assert_eq!(all("text/x-yaml"), ["yaml", "yml"]);
+            assert!(all("application/x-unknown-to-everyone").is_empty());
+        }
+
+        #[cfg(all(feature = "extension-light", not(feature = "extension")))]
+        #[test]
+        fn all_extensions_light_only() {
+            assert_eq!(all("text/javascript"), ["js", "jsx", "mjs"]);
+            assert_eq!(all("image/jpeg"), ["jpg", "jpeg"]);
+            assert_eq!(all("text/x-yaml"), ["yaml", "yml"]);
+        }
+
+        #[cfg(feature = "extension")]
+        #[test]
+        fn all_extensions_mime_guess() {
+            assert_eq!(all("image/jpeg"), ["jpg", "jfif", "jpe", "jpeg"]);
+            let all = all("application/javascript");
+            assert_eq!(all[0], "js");
+            let mut rest = all[1..].to_vec();
+            rest.sort_unstable();
+            assert_eq!(rest, all[1..]);
+        }
+
+        #[cfg(feature = "extension")]
+        #[test]
+        fn mime_guess_fallback() {
+            assert_eq!(
+                preferred("application/vnd.oasis.opendocument.text"),
+                Some("odt")
+            );
+            assert_eq!(preferred("application/x-unknown-to-everyone"), None);
+        }
+
+        #[cfg(not(feature = "extension"))]
+        #[test]
+        fn light_only() {
+            assert_eq!(preferred("application/vnd.oasis.opendocument.text"), None);
+        }
+    }
+
+    mod inner_access {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        #[test]
+        fn borrowed_and_owned() {
+            let mime = Mime::from_str("image/svg+xml; charset=utf-8").unwrap();
+            assert!(core::ptr::eq(mime.as_mime(), &mime.mime));
+            assert_eq!(mime.type_(), mime::IMAGE);
+            assert_eq!(mime.subtype(), mime::SVG);
+            assert_eq!(mime.essence_str(), "image/svg+xml");
+            assert_eq!(mime.params().count(), 1);
+            assert_eq!(mime.to_mime(), mime.clone().into_mime());
+        }
+    }
+
+    mod with_param {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        #[test]
+        fn appends_and_replaces() {
+            let html = Mime::new(mime::TEXT_HTML).with_charset_utf8();
+            assert_eq!(html.to_string(), "text/html; charset=utf-8");
+            assert_eq!(html.charset(), Some("utf-8"));
+
+            let latin1 = Mime::from_str("text/plain; Charset=utf-8; format=flowed")
+                .unwrap()
+                .with_param("charset", "iso-8859-1")
+                .unwrap();
+            assert_eq!(
+                latin1.to_string(),
+                "text/plain; format=flowed; charset=iso-8859-1"
+            );
+        }
+
+        #[test]
+        fn quoting() {
+            let mime = Mime::new(mime::MULTIPART_FORM_DATA)
+                .with_param("boundary", "simple boundary")
+                .unwrap();
+            assert_eq!(mime.boundary(), Some("simple boundary"));
+            assert_eq!(
+                mime.to_string(),
+                r#"multipart/form-data; boundary="simple boundary""#
+            );
+        }
+
+        #[test]
+        fn invalid() {
+            let plain = Mime::new(mime::TEXT_PLAIN);
+            assert!(plain.clone().with_param("", "x").is_err());
+            assert!(plain.clone().with_param("char set", "x").is_err());
+            assert!(plain.clone().with_param("name", "a\r\nb").is_err());
+            assert!(plain.clone().with_param("name", "a\"b").is_err());
+            assert!(plain.with_param("name", "日本").is_err());
+        }
+    }
+
+    mod hashing {
+        use crate::Mime;
+        use core::str::FromStr;
+        use std::collections::HashSet;
+        use std::hash::{BuildHasher, RandomState};
+
+        #[test]
+        fn equal_types_hash_equally() {
+            let state = RandomState::new();
+            let pairs = [
+                ("TEXT/PLAIN", "text/plain"),
+                ("text/plain; charset=UTF-8", "text/plain;charset=utf-8"),
+                (
+                    "Image/SVG+XML; Charset=utf-8",
+                    "image/svg+xml; charset=UTF-8",
+                ),
+            ];
+            for (a, b) in pairs {
+                let (a, b) = (Mime::from_str(a).unwrap(), Mime::from_str(b).unwrap());
+                assert_eq!(a, b);
+                assert_eq!(state.hash_one(&a), state.hash_one(&b));
+            }
+        }
+
+        #[test]
+        fn inner_hash_differs() {
+            let state = RandomState::new();
+            let (a, b) = (
+                Mime::from_str("text/plain; charset=UTF-8").unwrap(),
+                Mime::from_str("text/plain;charset=utf-8").unwrap(),
+            );
+            assert_eq!(a.as_mime(), b.as_mime());
+            assert_ne!(state.hash_one(a.as_mime()), state.hash_one(b.as_mime()));
+            assert_eq!(state.hash_one(&a), state.hash_one(&b));
+        }
+
+        #[test]
+        fn inner_map_lookup() {
+            use std::collections::HashMap;
+            let limits: HashMap<mime::Mime, u32> =
+                [(mime::IMAGE_PNG, 4096), (mime::TEXT_CSS, 1024)].into();
+            let guessed = &Mime::from_str("image/png").unwrap();
+            assert_eq!(limits.get(guessed.as_ref()), Some(&4096));
+            assert_eq!(limits.get(guessed.as_mime()), Some(&4096));
+            assert_eq!(limits.get(Mime::IMAGE_GIF.as_ref()), None);
+        }
+
+        #[test]
+        fn hash_set_round_trip() {
+            let set: HashSet<Mime> = [
+                "TEXT/PLAIN",
+                "text/plain",
+                "image/png",
+                "text/plain; charset=utf-8",
+            ]
+            .into_iter()
+            .map(|s| Mime::from_str(s).unwrap())
+            .collect();
+            assert_eq!(set.len(), 3);
+            assert!(set.contains(&Mime::from_str("Text/Plain").unwrap()));
+            assert!(set.contains(&Mime::new(mime::IMAGE_PNG)));
+            assert!(!set.contains(&Mime::new(mime::IMAGE_JPEG)));
+        }
+
+        #[test]
+        fn parameter_values() {
+            let state = RandomState::new();
+            let flowed = Mime::from_str("text/plain; format=flowed").unwrap();
+            let fixed = Mime::from_str("text/plain; format=fixed").unwrap();
+            assert_ne!(flowed, fixed);
+            let set: HashSet<Mime> = [flowed.clone(), fixed.clone()].into();
+            assert_eq!(set.len(), 2);
+
+            // The order of the parameters doesn't matter.
+            let (a, b) = (
+                Mime::from_str("text/plain; charset=UTF-8; format=flowed").unwrap(),
+                Mime::from_str("text/plain;format=flowed;charset=utf-8").unwrap(),
+            );
+            assert_eq!(a, b);
+            assert_eq!(a.cmp(&b), core::cmp::Ordering::Equal);
+            assert_eq!(state.hash_one(&a), state.hash_one(&b));
+            assert!(set.contains(&Mime::from_str("text/plain;format=fixed").unwrap()));
+            assert_eq!(
+                Mime::from_str("text/plain; format=\"flowed\"").unwrap(),
+                flowed
+            );
+        }
+    }
+
+    mod fallbacks {
+        #[allow(unused_imports)]
+        use crate::Mime;
+
+        #[cfg(feature = "extension")]
+        #[test]
+        fn from_ext_or() {
+            let plain = Mime::new(mime::TEXT_PLAIN);
+            assert_eq!(Mime::from_ext_or("png", plain.clone()), "image/png");
+            assert_eq!(Mime::from_ext_or("xyzzy", plain.clone()), "text/plain");
+            assert_eq!(
+                Mime::from_path_or(std::path::Path::new("a/b.pdf"), plain.clone()),
+                "application/pdf"
+            );
+            assert_eq!(
+                Mime::from_path_or(std::path::Path::new("a/b"), plain),
+                "text/plain"
+            );
+        }
+
+        #[cfg(feature = "extension-light")]
+        #[test]
+        fn from_ext_light_or() {
+            let octet_stream = Mime::new(mime::APPLICATION_OCTET_STREAM);
+            assert_eq!(
+                Mime::from_ext_light_or("json", octet_stream.clone()),
+                "application/json"
+            );
+            assert_eq!(
+                Mime::from_ext_light_or("xyzzy", octet_stream.clone()),
+                "application/octet-stream"
+            );
+            #[cfg(feature = "std")]
+            {
+                use std::path::Path;
+                assert_eq!(
+                    Mime::from_path_light_or(Path::new("style.css"), octet_stream.clone()),
+                    "text/css"
+                );
+                assert_eq!(
+                    Mime::from_path_light_or(Path::new("LICENSE"), octet_stream),
+                    "application/octet-stream"
+                );
+            }
+        }
+    }
+
+    mod conversions {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        #[test]
+        fn into_and_from_mime() {
+            let wrapped: Mime = mime::TEXT_CSS.into();
+            assert_eq!(wrapped, Mime::TEXT_CSS);
+            let inner: mime::Mime = wrapped.into();
+            assert_eq!(inner, mime::TEXT_CSS);
+            let wrapped: Vec<Mime> = [mime::IMAGE_PNG, mime::IMAGE_GIF]
+                .into_iter()
+                .map(Into::into)
+                .collect();
+            assert_eq!(wrapped, [Mime::IMAGE_PNG, Mime::IMAGE_GIF]);
+        }
+
+        #[test]
+        fn eq_str() {
+            assert_ne!(Mime::TEXT_PLAIN, "not a mime");
+            assert_ne!(Mime::TEXT_PLAIN, "");
+            assert_eq!(Mime::TEXT_PLAIN, "TEXT/Plain");
+            assert_eq!(Mime::TEXT_PLAIN_UTF_8, "text/plain; charset=UTF-8");
+            assert_ne!(Mime::TEXT_PLAIN_UTF_8, "text/plain");
+            assert_ne!(Mime::TEXT_PLAIN, "text/plain; charset=utf-8");
+        }
+
+        #[test]
+        fn cross_type_eq() {
+            assert_eq!(Mime::TEXT_CSS, mime::TEXT_CSS);
+            assert_eq!(mime::TEXT_CSS, Mime::TEXT_CSS);
+            assert_ne!(Mime::TEXT_PLAIN_UTF_8, mime::TEXT_PLAIN);
+            assert_ne!(mime::TEXT_PLAIN, Mime::TEXT_PLAIN_UTF_8);
+            assert_eq!(Mime::TEXT_PLAIN_UTF_8, "text/plain; charset=UTF-8");
+            assert_eq!("text/plain; charset=UTF-8", Mime::TEXT_PLAIN_UTF_8);
+            assert_ne!("text/plain", Mime::TEXT_PLAIN_UTF_8);
+            assert_ne!("not a mime", Mime::TEXT_PLAIN);
+            assert_ne!("", Mime::TEXT_PLAIN);
+
+            // `mime::Mime` only compares the parameter names.
+            let flowed = "text/plain; format=flowed";
+            let fixed = mime::Mime::from_str("text/plain; format=fixed").unwrap();
+            assert_eq!(mime::Mime::from_str(flowed).unwrap(), fixed);
+            let flowed = Mime::from_str(flowed).unwrap();
+            assert_ne!(flowed, fixed);
+            assert_ne!(fixed, flowed);
+            assert_ne!(flowed, "text/plain; format=fixed");
+            assert_ne!("text/plain; format=fixed", flowed);
+            assert_eq!(flowed, "text/plain;format=flowed");
+        }
+
+        #[test]
+        fn eq_essence() {
+            let html = Mime::from_str("text/html; charset=utf-8").unwrap();
+            assert_ne!(html, "text/html");
+            assert!(html.eq_essence(&Mime::TEXT_HTML));
+            assert!(Mime::TEXT_HTML.eq_essence(&html));
+            assert!(html.essence_eq_str("text/html"));
+            assert!(html.essence_eq_str("Text/HTML; charset=latin1"));
+            assert!(Mime::TEXT_HTML.essence_eq_str("TEXT/html;q=0.9"));
+            assert!(!html.eq_essence(&Mime::TEXT_PLAIN_UTF_8));
+            assert!(!Mime::IMAGE_SVG.essence_eq_str("image/svg"));
+            assert!(!html.essence_eq_str("text/html garbage"));
+
+            let flowed = Mime::from_str("text/plain; format=flowed").unwrap();
+            let fixed = Mime::from_str("text/plain; format=fixed").unwrap();
+            assert_ne!(flowed, fixed);
+            assert!(flowed.eq_essence(&fixed));
+        }
+
+        #[test]
+        fn matches() {
+            assert!(Mime::IMAGE_PNG.matches("image/*"));
+            assert!(Mime::IMAGE_PNG.matches("*/*"));
+            assert!(Mime::TEXT_HTML_UTF_8.matches("text/html"));
+            assert!(!Mime::IMAGE_PNG.matches("text/*"));
+            assert!(!Mime::IMAGE_PNG.matches("*/png"));
+        }
+
+        #[test]
+        fn try_from_str() {
+            assert_eq!(Mime::try_from("image/png").unwrap(), Mime::IMAGE_PNG);
+            assert!(Mime::try_from("not a mime").is_err());
+        }
+
+        #[cfg(all(
+            feature = "std",
+            any(feature = "extension", feature = "extension-light")
+        ))]
+        #[test]
+        fn try_from_path() {
+            use std::path::Path;
+            assert_eq!(
+                Mime::try_from(Path::new("a/b.png")).unwrap(),
+                Mime::IMAGE_PNG
+            );
+            let error = Mime::try_from(Path::new("a/b.xyzzy")).unwrap_err();
+            assert!(error.to_string().contains("No mime type found"));
+            assert!(Mime::try_from(Path::new("a/b")).is_err());
+        }
+
+        #[cfg(all(feature = "extension", feature = "extension-light"))]
+        #[test]
+        fn try_from_path_prefers_extension() {
+            use std::path::Path;
+            // `mime_guess` knows `.ts` as `video/vnd.dlna.mpeg-tts`, while the light table has `audio/vnd.dlna.mpeg-tts`.
+            assert_eq!(
+                Mime::try_from(Path::new("clip.ts")).unwrap(),
+                "video/vnd.dlna.mpeg-tts"
+            );
+        }
+
+        #[cfg(feature = "magic")]
+        #[test]
+        fn try_from_bytes() {
+            let png = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+            assert_eq!(Mime::try_from(&png[..]).unwrap(), Mime::IMAGE_PNG);
+            assert!(Mime::try_from(&b"no magic here"[..]).is_err());
+        }
+    }
+
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    mod extension_normalization {
+        use crate::{Mime, MimeError};
+
+        fn check(guess: fn(&str) -> Result<Mime, MimeError>) {
+            assert_eq!(guess(".PNG").unwrap(), Mime::IMAGE_PNG);
+            assert_eq!(guess(" jpg").unwrap(), Mime::IMAGE_JPEG);
+            assert_eq!(guess("css\n").unwrap(), Mime::TEXT_CSS);
+            assert!(matches!(guess("."), Err(MimeError::EmptyExtension)));
+            assert!(matches!(guess(""), Err(MimeError::EmptyExtension)));
+            assert!(matches!(guess(" . "), Err(MimeError::EmptyExtension)));
+            assert!(matches!(
+                guess("..png"),
+                Err(MimeError::UnknownExtension(_))
+            ));
+        }
+
+        #[cfg(feature = "extension")]
+        #[test]
+        fn from_ext() {
+            check(Mime::from_ext);
+        }
+
+        #[cfg(feature = "extension-light")]
+        #[test]
+        fn from_ext_light() {
+            check(Mime::from_ext_light);
+        }
+
+        #[cfg(feature = "texture")]
+        #[test]
+        fn from_ext_and_content() {
+            assert_eq!(
+                crate::from_ext_and_content(".PNG", b"not really").unwrap(),
+                Mime::IMAGE_PNG
+            );
+        }
+    }
+
+    #[cfg(all(
+        feature = "std",
+        any(feature = "extension", feature = "extension-light")
+    ))]
+    mod compound_extensions {
+        use crate::{Mime, MimeError};
+        use std::path::Path;
+
+        fn check(guess: fn(&Path) -> Result<Mime, MimeError>) {
+            let guess = |path: &str| guess(Path::new(path)).unwrap().to_string();
+            assert_eq!(guess("dist/archive.tar.gz"), "application/gzip");
+            assert_eq!(guess("backup.TAR.XZ"), "application/x-xz");
+            assert_eq!(guess("types/index.d.ts"), "text/typescript");
+            assert_eq!(guess("index.d.mts"), "text/typescript");
+            assert_eq!(guess("vendor/jquery.min.js"), "text/javascript");
+            assert_eq!(guess("script.user.js"), "text/javascript");
+            assert_eq!(guess("bundle.map.js"), "text/javascript");
+            // Unknown pairs fall back to the last segment.
+            assert_eq!(guess("photo.final.jpg"), "image/jpeg");
+            assert_eq!(guess("app.test.js"), "text/javascript");
+        }
+
+        #[cfg(feature = "extension")]
+        #[test]
+        fn from_path() {
+            check(Mime::from_path);
+            assert_eq!(Mime::from_ext("tar.gz").unwrap(), "application/gzip");
+        }
+
+        #[cfg(feature = "extension-light")]
+        #[test]
+        fn from_path_light() {
+            check(Mime::from_path_light);
+            assert_eq!(Mime::from_ext_light(".d.ts").unwrap(), "text/typescript");
+            // Without the declaration prefix, `ts` is still an MPEG transport stream.
+            assert_eq!(
+                Mime::from_path_light(Path::new("main.ts")).unwrap(),
+                "audio/vnd.dlna.mpeg-tts"
+            );
+        }
+
+        #[test]
+        fn full_extension() {
+            assert_eq!(
+                crate::full_extension(Path::new("archive.tar.gz")),
+                Some("tar.gz")
+            );
+            assert_eq!(crate::full_extension(Path::new("archive.gz")), Some("gz"));
+            assert_eq!(crate::full_extension(Path::new("tar.gz")), Some("gz"));
+            assert_eq!(crate::full_extension(Path::new("Makefile")), None);
+        }
+
+        #[cfg(feature = "extension-light")]
+        #[test]
+        fn from_filename() {
+            assert_eq!(
+                Mime::from_filename_light("C:\\src\\lib.d.cts").unwrap(),
+                "text/typescript"
+            );
+        }
+    }
+
+    #[cfg(all(feature = "std", feature = "extension-light"))]
+    mod os_extensions {
+        use crate::{Mime, MimeError};
+        use std::ffi::OsStr;
+
+        #[test]
+        fn from_os_ext() {
+            assert_eq!(
+                Mime::from_os_ext(OsStr::new("PNG")).unwrap(),
+                Mime::IMAGE_PNG
+            );
+            assert!(matches!(
+                Mime::from_os_ext(OsStr::new("xyzzy")),
+                Err(MimeError::UnknownExtension(_))
+            ));
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn non_utf8() {
+            use std::os::unix::ffi::OsStrExt;
+            use std::path::Path;
+
+            assert!(matches!(
+                Mime::from_os_ext(OsStr::from_bytes(b"p\xe9ng")),
+                Err(MimeError::UndecodableExtension(_))
+            ));
+            // Only the extension has to be decodable.
+            let latin1 = Path::new(OsStr::from_bytes(b"r\xe9sum\xe9.PDF"));
+            assert_eq!(Mime::from_path_light(latin1).unwrap(), "application/pdf");
+            #[cfg(feature = "extension")]
+            assert_eq!(Mime::from_path(latin1).unwrap(), "application/pdf");
+
+            let undecodable = Path::new(OsStr::from_bytes(b"photo.jp\xe9g"));
+            assert!(matches!(
+                Mime::from_path_light(undecodable),
+                Err(MimeError::UndecodableExtension(_))
+            ));
+            #[cfg(feature = "extension")]
+            assert!(matches!(
+                Mime::from_path(undecodable),
+                Err(MimeError::UndecodableExtension(_))
+            ));
+        }
+    }
+
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    mod filenames {
+        use crate::{Mime, MimeError};
+
+        fn check(guess: fn(&str) -> Result<Mime, MimeError>) {
+            assert_eq!(guess("photo.final.JPG").unwrap(), Mime::IMAGE_JPEG);
+            assert_eq!(guess("C:\\Users\\me\\logo.Png").unwrap(), Mime::IMAGE_PNG);
+            assert_eq!(guess("uploads/2024\\style.css").unwrap(), Mime::TEXT_CSS);
+            assert!(matches!(
+                guess("C:\\dir.with.dots\\Makefile"),
+                Err(MimeError::NoFilenameExtension(_))
+            ));
+            assert!(matches!(
+                guess("photo."),
+                Err(MimeError::NoFilenameExtension(_))
+            ));
+            assert!(matches!(
+                guess(".env"),
+                Err(MimeError::NoFilenameExtension(_))
+            ));
+        }
+
+        #[cfg(feature = "extension")]
+        #[test]
+        fn from_filename() {
+            check(Mime::from_filename);
+        }
+
+        #[cfg(feature = "extension-light")]
+        #[test]
+        fn from_filename_light() {
+            check(Mime::from_filename_light);
+        }
+    }
+
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    mod urls {
+        use crate::{Mime, MimeError};
+
+        fn check(guess: fn(&str) -> Result<Mime, MimeError>) {
+            assert_eq!(
+                guess("https://cdn.example.com/app.js?v=123#hash").unwrap(),
+                "text/javascript"
+            );
+            assert_eq!(guess("./icon.svg?url").unwrap(), Mime::IMAGE_SVG);
+            assert_eq!(guess("/img/my%20photo.JPG").unwrap(), Mime::IMAGE_JPEG);
+            assert_eq!(guess("dist/types.d.ts#L1").unwrap(), "text/typescript");
+            for url in [
+                "https://example.com/download?file=logo.png",
+                "https://example.com",
+                "assets/#icon.svg",
+                "/LICENSE",
+            ] {
+                assert!(
+                    matches!(guess(url), Err(MimeError::NoUrlExtension(found)) if found == url),
+                    "{url}"
+                );
+            }
+        }
+
+        #[cfg(feature = "extension")]
+        #[test]
+        fn from_url() {
+            check(Mime::from_url);
+        }
+
+        #[cfg(feature = "extension-light")]
+        #[test]
+        fn from_url_light() {
+            check(Mime::from_url_light);
+        }
+    }
+
+    #[cfg(any(feature = "extension", feature = "extension-light"))]
+    mod dotfiles {
+        use crate::Mime;
+
+        #[test]
+        fn from_dotfile() {
+            assert_eq!(
+                Mime::from_dotfile(".babelrc", false),
+                Some(Mime::APPLICATION_JSON)
+            );
+            assert_eq!(
+                Mime::from_dotfile("app/.ENV", false),
+                Some(Mime::TEXT_PLAIN)
+            );
+            assert_eq!(Mime::from_dotfile(".unknownrc", false), None);
+            assert_eq!(
+                Mime::from_dotfile(".unknownrc", true),
+                Some(Mime::TEXT_PLAIN)
+            );
+            assert_eq!(Mime::from_dotfile(".eslintrc.json", true), None);
+            assert_eq!(Mime::from_dotfile("README", true), None);
+            assert_eq!(Mime::from_dotfile("..", true), None);
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn register_dotfile() {
+            let path = std::path::Path::new("ci/.Registered-RC");
+            let guess = || crate::MimeGuesser::new().with_path(path).guess();
+            assert_eq!(guess(), Mime::APPLICATION_OCTET_STREAM);
+            Mime::register_dotfile(".registered-rc", "application/yaml").unwrap();
+            assert_eq!(guess(), "application/yaml");
+            assert!(matches!(
+                Mime::register_dotfile(".eslintrc.json", "application/json"),
+                Err(crate::MimeError::NotADotfile(_))
+            ));
+            assert!(matches!(
+                Mime::register_dotfile(".bogusrc", "not a mime"),
+                Err(crate::MimeError::Parse(_))
+            ));
+        }
+
+        #[cfg(feature = "std")]
+        fn check(guess: fn(&std::path::Path) -> Result<Mime, crate::MimeError>) {
+            use std::path::Path;
+            assert_eq!(
+                guess(Path::new("app/.babelrc")).unwrap(),
+                Mime::APPLICATION_JSON
+            );
+            assert_eq!(guess(Path::new(".env")).unwrap(), Mime::TEXT_PLAIN);
+            assert_eq!(guess(Path::new(".gitignore")).unwrap(), Mime::TEXT_PLAIN);
+            assert_eq!(
+                guess(Path::new(".eslintrc.json")).unwrap(),
+                Mime::APPLICATION_JSON
+            );
+            assert!(matches!(
+                guess(Path::new(".unknownrc")),
+                Err(crate::MimeError::NoExtension(_))
+            ));
+        }
+
+        #[cfg(feature = "extension")]
+        #[test]
+        fn from_path() {
+            check(Mime::from_path);
+        }
+
+        #[cfg(all(feature = "std", feature = "extension-light"))]
+        #[test]
+        fn from_path_light() {
+            check(Mime::from_path_light);
+        }
+    }
+
+    mod canonical {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        fn mime(s: &str) -> Mime {
+            Mime::from_str(s).unwrap()
+        }
+
+        #[test]
+        fn canonicalize() {
+            assert_eq!(mime("text/xml").canonicalize(), "application/xml");
+            assert_eq!(
+                mime("application/x-javascript").canonicalize(),
+                Mime::TEXT_JAVASCRIPT
+            );
+            assert_eq!(mime("audio/x-wav").canonicalize(), "audio/wav");
+            assert_eq!(mime("IMAGE/JPG").canonicalize(), Mime::IMAGE_JPEG);
+            assert_eq!(
+                mime("text/xml; charset=utf-8").canonicalize().to_string(),
+                "application/xml; charset=utf-8"
+            );
+        }
+
+        #[test]
+        fn unknown_types_pass_through() {
+            for ty in ["image/png", "application/vnd.custom+json; v=2", "x-foo/bar"] {
+                assert_eq!(mime(ty).canonicalize().to_string(), mime(ty).to_string());
+            }
+        }
+
+        #[test]
+        fn eq_canonical() {
+            assert!(mime("image/jpg").eq_canonical(&Mime::IMAGE_JPEG));
+            assert!(mime("text/xml").eq_canonical(&mime("application/xml")));
+            assert!(!mime("text/xml; charset=utf-8").eq_canonical(&mime("application/xml")));
+            assert!(!Mime::IMAGE_PNG.eq_canonical(&Mime::IMAGE_JPEG));
+        }
+    }
+
+    #[cfg(all(
+        feature = "magic",
+        any(feature = "extension", feature = "extension-light")
+    ))]
+    mod verification {
+        use crate::{Mime, Verification};
+
+        const PNG: &[u8] = include_bytes!("../tests/fixtures/pixel.png");
+
+        #[test]
+        fn renamed_png() {
+            assert_eq!(
+                Mime::verify("txt", PNG),
+                Verification::Mismatch {
+                    claimed: Mime::TEXT_PLAIN,
+                    detected: Mime::IMAGE_PNG,
+                }
+            );
+        }
+
+        #[test]
+        fn genuine_css() {
+            let css = include_bytes!("../tests/fixtures/dataurls.css");
+            assert_eq!(
+                Mime::verify("css", css),
+                Verification::Unknown(Mime::TEXT_CSS)
+            );
+        }
+
+        #[test]
+        fn consistent() {
+            assert_eq!(
+                Mime::verify(".PNG", PNG),
+                Verification::Consistent(Mime::IMAGE_PNG)
+            );
+            let jpeg = [0xFF, 0xD8, 0xFF, 0xE0];
+            assert!(matches!(
+                Mime::verify("jpg", &jpeg),
+                Verification::Consistent(claimed) if claimed.eq_canonical(&Mime::IMAGE_JPEG)
+            ));
+        }
+
+        #[test]
+        fn unknown_extension() {
+            assert_eq!(
+                Mime::verify("xyzzy", PNG),
+                Verification::Mismatch {
+                    claimed: Mime::APPLICATION_OCTET_STREAM,
+                    detected: Mime::IMAGE_PNG,
+                }
+            );
+            assert_eq!(
+                Mime::verify("xyzzy", b"plain words"),
+                Verification::Unknown(Mime::APPLICATION_OCTET_STREAM)
+            );
+        }
+    }
+
+    #[cfg(all(feature = "magic", feature = "texture"))]
+    mod text_fallback {
+        use crate::{Mime, MimeError};
+
+        #[test]
+        fn web_assets() {
+            let css = include_bytes!("../tests/fixtures/dataurls.css");
+            assert!(Mime::from_content(css).is_err());
+            for (data, expected) in [
+                (css.as_slice(), Mime::TEXT_PLAIN),
+                (
+                    br#"{"compilerOptions": {"strict": true}}"#,
+                    Mime::APPLICATION_JSON,
+                ),
+                (
+                    include_bytes!("../tests/fixtures/logo.svg"),
+                    Mime::IMAGE_SVG,
+                ),
+                (b"<?xml version=\"1.0\"?>\n<svg/>", Mime::IMAGE_SVG),
+                (b"<?xml version=\"1.0\"?>\n<rss/>", Mime::TEXT_XML),
+                (b"<!DOCTYPE html><p>Test</p>", Mime::TEXT_HTML),
+                (b"See <html> in the spec.", Mime::TEXT_PLAIN),
+                (
+                    include_bytes!("../tests/fixtures/pixel.png"),
+                    Mime::IMAGE_PNG,
+                ),
+            ] {
+                assert_eq!(
+                    Mime::from_content_with_text_fallback(data).unwrap(),
+                    expected,
+                    "{}",
+                    String::from_utf8_lossy(data)
+                );
+            }
+        }
+
+        #[test]
+        fn binary_without_magic() {
+            assert!(matches!(
+                Mime::from_content_with_text_fallback(&[0x00, 0xFF, 0xFE, 0x81]),
+                Err(MimeError::InferenceFailed)
+            ));
+        }
+    }
+
+    #[cfg(all(feature = "extension-light", feature = "magic", feature = "texture"))]
+    mod guesser {
+        use crate::{Mime, MimeGuesser};
+
+        const PNG: &[u8] = include_bytes!("../tests/fixtures/pixel.png");
+
+        #[test]
+        fn priorities() {
+            let guesser = MimeGuesser::new().with_extension("svg").with_data(PNG);
+            assert_eq!(guesser.clone().guess(), "image/svg+xml");
+            assert_eq!(guesser.clone().prefer_content().guess(), Mime::IMAGE_PNG);
+            #[cfg(feature = "extension")]
+            let guesser = guesser.extension(false);
+            assert_eq!(
+                guesser
+                    .extension_light(false)
+                    .magic(false)
+                    .with_fallback(Mime::IMAGE_GIF)
+                    .guess(),
+                Mime::IMAGE_GIF
+            );
+            #[cfg(feature = "extension")]
+            assert_eq!(
+                MimeGuesser::new()
+                    .extension_light(false)
+                    .with_extension("svg")
+                    .guess(),
+                "image/svg+xml"
+            );
+        }
+
+        #[test]
+        fn texture_comes_last() {
+            let guesser = MimeGuesser::new()
+                .prefer_content()
+                .with_extension("css")
+                .with_data(b"body { margin: 0 }");
+            assert_eq!(guesser.clone().guess(), Mime::TEXT_CSS);
+            assert_eq!(
+                guesser.clone().with_extension("xyzzy").guess(),
+                Mime::TEXT_PLAIN
+            );
+            assert_eq!(
+                guesser.with_extension("xyzzy").texture(false).guess(),
+                Mime::APPLICATION_OCTET_STREAM
+            );
+        }
+
+        #[test]
+        fn matches_from_ext_and_content() {
+            for (ext, data) in [("svg", PNG), ("xyzzy", PNG), ("xyzzy", b"text".as_slice())] {
+                assert_eq!(
+                    crate::from_ext_and_content(ext, data).unwrap(),
+                    MimeGuesser::new()
+                        .with_extension(ext)
+                        .with_data(data)
+                        .guess()
+                );
+            }
+        }
+
+        #[test]
+        #[cfg(feature = "std")]
+        fn from_path_and_content() {
+            use std::path::Path;
+            for (path, expected) in [
+                ("pixel.svg", "image/svg+xml"),
+                ("index.d.ts", "text/typescript"),
+                (".babelrc", "application/json"),
+                ("pixel", "image/png"),
+            ] {
+                assert_eq!(
+                    crate::from_path_and_content(Path::new(path), PNG).unwrap(),
+                    expected,
+                    "{path}"
+                );
+            }
+        }
+    }
+
+    #[cfg(all(feature = "std", feature = "magic"))]
+    mod readers {
+        use crate::{Mime, MimeError};
+        use std::io::{self, Read};
+
+        const PNG: &[u8] = &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+
+        /// Hands out one byte per call, failing with `Interrupted` before each.
+        struct Trickle<'a> {
+            data: &'a [u8],
+            interrupt: bool,
+        }
+
+        impl Read for Trickle<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                self.interrupt = !self.interrupt;
+                if self.interrupt {
+                    return Err(io::ErrorKind::Interrupted.into());
+                }
+                let Some((first, rest)) = self.data.split_first() else {
+                    return Ok(0);
+                };
+                buf[0] = *first;
+                self.data = rest;
+                Ok(1)
+            }
+        }
+
+        #[test]
+        fn bounded() {
+            // An endless reader would never finish with `from_content`.
+            let mut endless = PNG.chain(io::repeat(0));
+            assert_eq!(
+                Mime::from_reader(&mut endless, 8192).unwrap(),
+                Mime::IMAGE_PNG
+            );
+            // The rest is left for the caller.
+            let mut rest = [1; 4];
+            endless.read_exact(&mut rest).unwrap();
+            assert_eq!(rest, [0; 4]);
+        }
+
+        #[test]
+        fn short_and_interrupted_reads() {
+            let mut trickle = Trickle {
+                data: PNG,
+                interrupt: false,
+            };
+            assert_eq!(
+                Mime::from_reader(&mut trickle, 8192).unwrap(),
+                Mime::IMAGE_PNG
+            );
+        }
+
+        #[test]
+        fn empty() {
+            assert!(matches!(
+                Mime::from_reader(&mut io::empty(), 8192),
+                Err(MimeError::InferenceFailed)
+            ));
+        }
+
+        #[test]
+        fn limit() {
+            let mut tar = vec![0; 512];
+            tar[257..262].copy_from_slice(b"ustar");
+            assert!(Mime::from_reader(&mut tar.as_slice(), 100).is_err());
+            assert_eq!(
+                Mime::from_reader(&mut tar.as_slice(), 8192).unwrap(),
+                "application/x-tar"
+            );
+        }
+
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        #[test]
+        fn sniff_path() {
+            let sniff =
+                |path: &str, max_bytes| Mime::sniff_path(std::path::Path::new(path), max_bytes);
+            assert_eq!(
+                sniff("tests/fixtures/pixel", 8192).unwrap(),
+                Mime::IMAGE_PNG
+            );
+            assert!(matches!(
+                sniff("tests/fixtures/pixel", 1),
+                Err(MimeError::InferenceFailed)
+            ));
+            assert!(matches!(
+                sniff("does/not/exist.png", 8192),
+                Err(MimeError::Io(_))
+            ));
+        }
+
+        #[cfg(all(
+            not(all(target_arch = "wasm32", target_os = "unknown")),
+            any(feature = "extension", feature = "extension-light")
+        ))]
+        #[test]
+        fn sniff_path_falls_back_to_extension() {
+            let sniff =
+                |path: &str, max_bytes| Mime::sniff_path(std::path::Path::new(path), max_bytes);
+            assert_eq!(
+                sniff("tests/fixtures/notes.txt", 8192).unwrap(),
+                Mime::TEXT_PLAIN
+            );
+            assert_eq!(
+                sniff("tests/fixtures/pixel.png", 1).unwrap(),
+                Mime::IMAGE_PNG
+            );
+        }
+    }
+
+    mod list {
+        use crate::Mime;
+
+        #[test]
+        fn quoted_commas() {
+            let list = Mime::parse_list(
+                r#"image/png, multipart/form-data; boundary="a,b" ,text/plain;charset=utf-8"#,
+            )
+            .unwrap();
+            assert_eq!(list.len(), 3);
+            assert_eq!(list[0], Mime::IMAGE_PNG);
+            assert_eq!(list[1].boundary(), Some("a,b"));
+            assert_eq!(list[2], Mime::TEXT_PLAIN_UTF_8);
+        }
+
+        #[test]
+        fn empty_elements() {
+            assert_eq!(Mime::parse_list("a/b,,c/d, ").unwrap(), ["a/b", "c/d"]);
+            assert_eq!(Mime::parse_list("image/png,").unwrap(), ["image/png"]);
+            assert!(Mime::parse_list("").unwrap().is_empty());
+            assert!(Mime::parse_list(" , ").unwrap().is_empty());
+        }
+
+        #[test]
+        fn invalid_index() {
+            let err = Mime::parse_list("image/png,,image,text/plain").unwrap_err();
+            assert!(
+                err.to_string()
+                    .starts_with("Invalid element 2 of MIME list \"image\""),
+                "{err}"
+            );
+            assert!(Mime::parse_list(r#"a/b; x="unterminated, c/d"#).is_err());
+        }
+    }
+
+    mod content_type_header {
+        use crate::{Mime, MimeError};
+
+        #[test]
+        fn boundaries() {
+            let mime = Mime::from_content_type_header(
+                "multipart/form-data; boundary=----WebKitFormBoundaryX",
+            )
+            .unwrap();
+            assert_eq!(mime.boundary(), Some("----WebKitFormBoundaryX"));
+
+            let mime = Mime::from_content_type_header(
+                r#" multipart/mixed ; boundary="gc0p4Jq0M2Yt08j34c0p; two words" ; "#,
+            )
+            .unwrap();
+            assert_eq!(mime.essence(), "multipart/mixed");
+            assert_eq!(mime.boundary(), Some("gc0p4Jq0M2Yt08j34c0p; two words"));
+        }
+
+        #[test]
+        fn whitespace_and_trailing_semicolons() {
+            let mime = Mime::from_content_type_header("\ttext/html;charset=UTF-8;\r\n").unwrap();
+            assert_eq!(mime, Mime::TEXT_HTML_UTF_8);
+            assert_eq!(mime.boundary(), None);
+            assert_eq!(
+                Mime::from_content_type_header("text/plain;;").unwrap(),
+                Mime::TEXT_PLAIN
+            );
+            assert!(Mime::from_content_type_header("").is_err());
+            assert!(Mime::from_content_type_header("; charset=utf-8").is_err());
+            assert!(Mime::from_content_type_header("text / plain").is_err());
+        }
+
+        #[test]
+        fn header_lines() {
+            for line in [
+                "Content-Type: text/html; charset=utf-8",
+                "content-type:text/html;charset=UTF-8\r\n",
+                "CONTENT-TYPE:    text/html ;  charset=utf-8  ",
+                "text/html; charset=utf-8",
+            ] {
+                assert_eq!(
+                    Mime::from_header(line).unwrap(),
+                    Mime::TEXT_HTML_UTF_8,
+                    "{line}"
+                );
+            }
+            assert_eq!(
+                Mime::from_header(r#"text/plain; source="http://example.com""#)
+                    .unwrap()
+                    .get_param("source"),
+                Some("http://example.com")
+            );
+            assert!(matches!(
+                Mime::from_header("Content-Disposition: attachment; filename=a.png"),
+                Err(MimeError::UnexpectedHeader(name)) if name == "Content-Disposition"
+            ));
+            assert!(Mime::from_header("Content-Type:").is_err());
+            assert!(Mime::from_header(": text/html").is_err());
+        }
+    }
+
+    mod constants {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        #[test]
+        fn mirror_mime() {
+            assert_eq!(Mime::TEXT_PLAIN, Mime::new(mime::TEXT_PLAIN));
+            assert_eq!(Mime::IMAGE_SVG, "image/svg+xml");
+            assert_eq!(Mime::TEXT_HTML_UTF_8.charset(), Some("utf-8"));
+            assert_eq!(
+                Mime::APPLICATION_JSON,
+                Mime::from_str("application/json").unwrap()
+            );
+        }
+
+        #[test]
+        fn default_is_octet_stream() {
+            assert_eq!(Mime::default(), "application/octet-stream");
+            assert!(Mime::default().is_octet_stream());
+            assert!(Mime::from_str("application/octet-stream; name=a.bin")
+                .unwrap()
+                .is_octet_stream());
+            assert!(!Mime::APPLICATION_PDF.is_octet_stream());
+        }
+
+        #[cfg(feature = "texture")]
+        #[test]
+        fn fallbacks_use_constants() {
+            assert_eq!(
+                crate::from_ext_and_content("xyzzy", b"hello").unwrap(),
+                Mime::TEXT_PLAIN
+            );
+            assert_eq!(
+                crate::from_ext_and_content("xyzzy", &[0xFF, 0x00, 0xFE, 0x01]).unwrap(),
+                Mime::APPLICATION_OCTET_STREAM
+            );
+        }
+    }
+
+    #[cfg(all(
+        feature = "camino",
+        any(feature = "extension", feature = "extension-light")
+    ))]
+    mod camino_paths {
+        use crate::Mime;
+        use camino::Utf8Path;
+
+        #[cfg(feature = "extension")]
+        #[test]
+        fn from_utf8_path() {
+            let mime = Mime::from_utf8_path(Utf8Path::new("assets/logo.v2.final.png")).unwrap();
+            assert_eq!(mime, "image/png");
+            assert!(Mime::from_utf8_path(Utf8Path::new("assets/Makefile")).is_err());
+        }
+
+        #[cfg(feature = "extension-light")]
+        #[test]
+        fn same_as_std_paths() {
+            for path in [
+                "types/index.d.ts",
+                "dist/app.min.js",
+                "backup.tar.gz",
+                "config/.babelrc",
+                ".gitignore",
+                "clip.ts",
+            ] {
+                let std_path = std::path::Path::new(path);
+                let utf8_path = Utf8Path::new(path);
+                assert_eq!(
+                    Mime::from_utf8_path_light(utf8_path).unwrap(),
+                    Mime::from_path_light(std_path).unwrap(),
+                    "{path}"
+                );
+                #[cfg(feature = "extension")]
+                assert_eq!(
+                    Mime::from_utf8_path(utf8_path).unwrap(),
+                    Mime::from_path(std_path).unwrap(),
+                    "{path}"
+                );
+            }
+            assert_eq!(
+                Mime::from_utf8_path_light(Utf8Path::new("types/index.d.ts")).unwrap(),
+                "text/typescript"
+            );
+            assert_eq!(
+                Mime::from_utf8_path_light(Utf8Path::new("config/.babelrc")).unwrap(),
+                "application/json"
+            );
+            assert!(Mime::from_utf8_path_light(Utf8Path::new(".unknownrc")).is_err());
+        }
+
+        #[cfg(feature = "extension-light")]
+        #[test]
+        fn from_utf8_path_light() {
+            let mime = Mime::from_utf8_path_light(Utf8Path::new("src/app.config.json")).unwrap();
+            assert_eq!(mime, "application/json");
+            assert!(Mime::from_utf8_path_light(Utf8Path::new("assets/Makefile")).is_err());
+        }
+
+        #[cfg(feature = "extension-light")]
+        #[test]
+        fn from_utf8_file() {
+            let mime = Mime::from_utf8_file(Utf8Path::new("Cargo.toml")).unwrap();
+            assert_eq!(mime, "text/x-toml");
+            assert!(Mime::from_utf8_file(Utf8Path::new("does/not/exist.toml")).is_err());
+        }
+    }
+
+    #[cfg(all(
+        feature = "tracing",
+        feature = "std",
+        any(feature = "magic", feature = "texture")
+    ))]
+    mod tracing_events {
+        use std::fmt::Write;
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// Records every span and event as a line of `name field=value ...`.
+        #[derive(Debug, Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<String>>>);
+
+        struct Line(String);
+
+        impl Visit for Line {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    write!(self.0, " {value:?}").unwrap();
+                } else {
+                    write!(self.0, " {}={value:?}", field.name()).unwrap();
+                }
+            }
+        }
+
+        impl Subscriber for Capture {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut line = Line(format!("span {}", span.metadata().name()));
+                span.record(&mut line);
+                self.0.lock().unwrap().push(line.0);
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                let mut line = Line("event".to_string());
+                event.record(&mut line);
+                self.0.lock().unwrap().push(line.0);
+            }
+
+            fn enter(&self, _: &Id) {}
+
+            fn exit(&self, _: &Id) {}
+        }
+
+        #[test]
+        fn from_ext_and_content_events() {
+            let capture = Capture::default();
+            let mime = tracing::subscriber::with_default(capture.clone(), || {
+                crate::from_ext_and_content("unknown", b"Hello, World!").unwrap()
+            });
+
+            let mut expected = vec!["span from_ext_and_content ext=unknown".to_string()];
+            if cfg!(feature = "extension") {
+                expected.push("event extension table miss source=\"extension\"".to_string());
+            }
+            if cfg!(feature = "extension-light") {
+                expected.push("event light table miss source=\"extension-light\"".to_string());
+            }
+            if cfg!(feature = "magic") {
+                expected.push("event no magic matcher matched source=\"magic\"".to_string());
+            }
+            if cfg!(feature = "texture") {
+                assert_eq!(mime, "text/plain");
+                expected.push("event content is texture source=\"texture\"".to_string());
+            } else {
+                assert_eq!(mime, "application/octet-stream");
+                expected.push("event fallback taken source=\"fallback\"".to_string());
+            }
+            assert_eq!(*capture.0.lock().unwrap(), expected);
+        }
+    }
+
+    mod header_string {
+        use crate::Mime;
+        use core::str::FromStr;
+
+        fn params(mime: &Mime) -> Vec<(String, String)> {
+            mime.mime
+                .params()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        }
+
+        #[test]
+        fn quotes_when_needed() {
+            let mime = Mime::from_str(
+                r#"text/plain; charset=utf-8; name="a b"; path="a\\b"; plain="abc""#,
+            )
+            .unwrap();
+            let header = mime.to_header_string();
+            assert_eq!(
+                header,
+                r#"text/plain; charset=utf-8; name="a b"; path="a\\b"; plain=abc"#
+            );
+            let reparsed = Mime::from_str(&header).unwrap();
+            assert_eq!(params(&reparsed), params(&mime));
+        }
+
+        #[test]
+        fn strict_rejects_non_ascii() {
+            let mime = Mime::from_str(r#"text/plain; name="日本.txt""#).unwrap();
+            assert_eq!(mime.to_header_string(), r#"text/plain; name="日本.txt""#);
+            assert!(mime.to_header_string_strict().is_err());
+            let ascii = Mime::from_str("image/svg+xml; charset=utf-8").unwrap();
+            assert_eq!(
+                ascii.to_header_string_strict().unwrap(),
+                "image/svg+xml; charset=utf-8"
+            );
+        }
+    }
 }
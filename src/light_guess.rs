@@ -1,8 +1,22 @@
 #![cfg(feature = "extension-light")]
+
+//! The extension table behind `Mime::from_ext_light`, for callers that only need the MIME type as a `&'static str`, e.g. to write it into a bundle, and never a parsed `mime::Mime`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::light_guess::{mime_type_by_extension, try_str_from_ext};
+//!
+//! assert_eq!(mime_type_by_extension("svg"), Some("image/svg+xml"));
+//! assert_eq!(mime_type_by_extension("SVG"), None);
+//! assert_eq!(try_str_from_ext(".SVG").unwrap(), "image/svg+xml");
+//! assert_eq!(try_str_from_ext("tar.gz").unwrap(), "application/gzip");
+//! ```
+
 use mime::Mime;
 use phf::{phf_map, Map};
-use std::str::FromStr;
 
+/// The light table, from lowercase extensions without a leading `.` to MIME types.
 pub static MIME_TYPES: Map<&'static str, &'static str> = phf_map! {
     // Text
     "txt" => "text/plain",
@@ -70,21 +84,33 @@ pub static MIME_TYPES: Map<&'static str, &'static str> = phf_map! {
 /// Adapted from:
 /// - https://github.com/rolldown/rolldown/pull/1406/files#diff-4b612e077c82ae0e05e50eb0d419e02c05a04b83c6ac5440c0d0c9d0c38af942
 /// - https://github.com/evanw/esbuild/blob/fc37c2fa9de2ad77476a6d4a8f1516196b90187e/internal/helpers/mime.go#L5
+///
 /// Thanks to @ikkz and @evanw for the inspiration.
+///
+/// Looks up `MIME_TYPES` as is: the extension must be lowercase and without a leading `.`, and compound extensions such as `tar.gz` are not known. Use `try_str_from_ext` for the lookup of `Mime::from_ext_light`.
 pub fn mime_type_by_extension(ext: &str) -> Option<&'static str> {
-    if let Some(mime) = MIME_TYPES.get(ext) {
-        Some(*mime)
-    } else {
-        None
-    }
+    MIME_TYPES.get(ext).copied()
 }
 
-pub fn try_from_ext(ext: &str) -> anyhow::Result<Mime> {
-    if let Some(mime) = mime_type_by_extension(ext) {
-        Ok(Mime::from_str(mime)?)
-    } else {
-        anyhow::bail!("No mime type found for extension: {}", ext);
+/// Looks up the extension like `Mime::from_ext_light`, but returns the MIME type as a `&'static str` without parsing it. The extension is matched case-insensitively, ignoring surrounding whitespace and one leading `.`, and compound extensions such as `tar.gz` are tried first. Only an extension with uppercase letters allocates.
+pub fn try_str_from_ext(ext: &str) -> Result<&'static str, crate::MimeError> {
+    let ext = crate::utils::normalize_extension(ext)?;
+    if let Some(mime) = crate::compound::mime_type_by_extension(ext) {
+        return Ok(mime);
+    }
+    match mime_type_by_extension(ext) {
+        Some(mime) => Some(mime),
+        None if ext.bytes().any(|byte| byte.is_ascii_uppercase()) => {
+            mime_type_by_extension(&ext.to_ascii_lowercase())
+        }
+        None => None,
     }
+    .ok_or_else(|| crate::MimeError::UnknownExtension(ext.into()))
+}
+
+/// The same as `try_str_from_ext`, but parses the MIME type, reusing the parse cache with the `std` feature. `Mime::from_ext_light` wraps it.
+pub fn try_from_ext(ext: &str) -> Result<Mime, crate::MimeError> {
+    crate::cache::parse(try_str_from_ext(ext)?)
 }
 
 #[cfg(test)]
@@ -115,6 +141,36 @@ mod tests {
         assert!(mime_type_by_extension("unknown").is_none());
     }
 
+    #[test]
+    fn exact_lookup() {
+        assert_eq!(mime_type_by_extension("PNG"), None);
+        assert_eq!(mime_type_by_extension(".png"), None);
+        assert_eq!(mime_type_by_extension("tar.gz"), None);
+    }
+
+    #[test]
+    fn str_lookup() {
+        assert_eq!(try_str_from_ext("png").unwrap(), "image/png");
+        assert_eq!(try_str_from_ext(" .PnG ").unwrap(), "image/png");
+        assert_eq!(try_str_from_ext("D.TS").unwrap(), "text/typescript");
+        assert!(matches!(
+            try_str_from_ext("unknown"),
+            Err(crate::MimeError::UnknownExtension(ext)) if ext == "unknown"
+        ));
+        assert!(matches!(
+            try_str_from_ext("."),
+            Err(crate::MimeError::EmptyExtension)
+        ));
+    }
+
+    #[test]
+    fn every_entry_parses() {
+        for (ext, mime) in MIME_TYPES.entries() {
+            assert_eq!(ext.to_ascii_lowercase(), *ext);
+            assert_eq!(try_from_ext(ext).unwrap(), *mime, "{ext}");
+        }
+    }
+
     #[test]
     fn try_from_exts() {
         assert!(matches!(try_from_ext("png").unwrap().subtype(), mime::PNG));
@@ -1,11 +1,12 @@
 #![cfg(feature = "magic")]
 
 /// Guesses the MIME type from the content via [`infer`](https://docs.rs/infer/latest/infer/).
-pub fn mime_type_by_content(data: &[u8]) -> anyhow::Result<mime::Mime> {
+pub fn mime_type_by_content(data: &[u8]) -> Result<mime::Mime, crate::MimeError> {
+    use core::str::FromStr;
     use infer::get;
     use mime::Mime;
-    use std::str::FromStr;
-    let inferred = get(data).ok_or_else(|| anyhow::anyhow!("Failed to infer mime type"))?;
+    let inferred = get(data).ok_or(crate::MimeError::InferenceFailed)?;
+    trace!(matcher = ?inferred.matcher_type(), mime = inferred.mime_type(), "infer matcher matched");
     Ok(Mime::from_str(inferred.mime_type())?)
 }
 
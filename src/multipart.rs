@@ -0,0 +1,175 @@
+//! Boundary validation and generation for `multipart/*` types, as defined in [RFC 2046](https://www.rfc-editor.org/rfc/rfc2046#section-5.1.1).
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::{multipart, Mime};
+//!
+//! let boundary = multipart::generate_boundary();
+//! let mime = Mime::multipart_form_data_with_boundary(&boundary).unwrap();
+//! assert_eq!(mime.boundary(), Some(boundary.as_str()));
+//! assert!(multipart::validate_boundary("semi;colon").is_err());
+//! ```
+
+use alloc::string::String;
+use core::fmt::Write;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The maximum length of a boundary.
+pub const MAX_BOUNDARY_LENGTH: usize = 70;
+
+/// Returns `true` if the character is a `bcharsnospace` of RFC 2046.
+fn is_bchar_nospace(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "'()+_,-./:=?".contains(c)
+}
+
+/// Checks that the boundary is 1 to 70 characters of the RFC 2046 `bchars`, not ending with a space.
+pub fn validate_boundary(boundary: &str) -> anyhow::Result<()> {
+    if boundary.is_empty() || boundary.len() > MAX_BOUNDARY_LENGTH {
+        anyhow::bail!(
+            "Boundary must be 1 to {} characters long, got {}",
+            MAX_BOUNDARY_LENGTH,
+            boundary.len()
+        );
+    }
+    if let Some(c) = boundary.chars().find(|&c| c != ' ' && !is_bchar_nospace(c)) {
+        anyhow::bail!("Invalid character in boundary: {:?}", c);
+    }
+    if boundary.ends_with(' ') {
+        anyhow::bail!("Boundary must not end with a space");
+    }
+    Ok(())
+}
+
+/// Returns `true` if the boundary has to be quoted in a `Content-Type` parameter.
+pub(crate) fn needs_quoting(boundary: &str) -> bool {
+    !boundary
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "'+_-.".contains(c))
+}
+
+/// A step of [SplitMix64](https://prng.di.unimi.it/splitmix64.c), used to spread the seed over the boundary.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Generates a boundary from a process-wide counter, mixed with the current time when `std` is available. It is unique within the process and unlikely to appear in a payload, but it is not cryptographically random.
+pub fn generate_boundary() -> String {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let mut state = COUNTER.fetch_add(1, Ordering::Relaxed) as u64;
+    #[cfg(all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    if let Ok(elapsed) = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        state ^= (elapsed.as_nanos() as u64).rotate_left(32);
+    }
+
+    let mut boundary = String::with_capacity(42);
+    boundary.push_str("mime_more-");
+    for _ in 0..2 {
+        write!(boundary, "{:016x}", splitmix64(&mut state)).unwrap();
+    }
+    boundary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mime;
+    use core::str::FromStr;
+
+    #[test]
+    fn validation_failures() {
+        assert!(validate_boundary("").is_err());
+        assert!(validate_boundary(&"a".repeat(71)).is_err());
+        assert!(validate_boundary("semi;colon").is_err());
+        assert!(validate_boundary("quote\"d").is_err());
+        assert!(validate_boundary("ends with space ").is_err());
+        assert!(validate_boundary("日本").is_err());
+        assert!(Mime::multipart_form_data(Some("bad\r\nboundary")).is_err());
+    }
+
+    #[test]
+    fn validation_successes() {
+        assert!(validate_boundary(&"a".repeat(70)).is_ok());
+        assert!(validate_boundary("simple boundary").is_ok());
+        assert!(validate_boundary("----WebKitFormBoundary7MA4YWxkTrZu0gW").is_ok());
+    }
+
+    #[test]
+    fn generated_boundaries_are_valid_and_distinct() {
+        let first = generate_boundary();
+        let second = generate_boundary();
+        assert!(validate_boundary(&first).is_ok());
+        assert!(!needs_quoting(&first));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mime =
+            Mime::multipart_form_data(Some("----WebKitFormBoundary7MA4YWxkTrZu0gW")).unwrap();
+        let string = mime.to_string();
+        assert_eq!(
+            string,
+            "multipart/form-data; boundary=----WebKitFormBoundary7MA4YWxkTrZu0gW"
+        );
+        let parsed = Mime::from_str(&string).unwrap();
+        assert_eq!(parsed, mime);
+        assert_eq!(
+            parsed.boundary(),
+            Some("----WebKitFormBoundary7MA4YWxkTrZu0gW")
+        );
+    }
+
+    #[test]
+    fn round_trip_quoted() {
+        let mime = Mime::multipart_form_data(Some("simple boundary")).unwrap();
+        let string = mime.to_string();
+        assert_eq!(string, "multipart/form-data; boundary=\"simple boundary\"");
+        assert_eq!(
+            Mime::from_str(&string).unwrap().boundary(),
+            Some("simple boundary")
+        );
+    }
+
+    #[test]
+    fn generated_round_trip() {
+        let mime = Mime::multipart_form_data(None).unwrap();
+        let boundary = mime.boundary().unwrap();
+        assert!(boundary.starts_with("mime_more-"));
+        assert_eq!(
+            Mime::from_str(&mime.to_string()).unwrap().boundary(),
+            Some(boundary)
+        );
+    }
+
+    #[test]
+    fn with_boundary_limits() {
+        let longest = "a".repeat(MAX_BOUNDARY_LENGTH);
+        assert_eq!(
+            Mime::multipart_form_data_with_boundary(&longest)
+                .unwrap()
+                .boundary(),
+            Some(longest.as_str())
+        );
+        assert!(
+            Mime::multipart_form_data_with_boundary(&"a".repeat(MAX_BOUNDARY_LENGTH + 1)).is_err()
+        );
+        assert!(Mime::multipart_form_data_with_boundary("").is_err());
+        assert!(Mime::multipart_form_data_with_boundary("a@b").is_err());
+        assert!(Mime::multipart_form_data_with_boundary("trailing ").is_err());
+        assert!(Mime::multipart_form_data_with_boundary(&generate_boundary()).is_ok());
+    }
+
+    #[test]
+    fn boundary_missing() {
+        assert_eq!(Mime::from_str("text/plain").unwrap().boundary(), None);
+    }
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "napi")]
+//! [`napi-rs`](https://napi.rs) bindings, so that Node.js tooling (e.g. Rolldown) can load the crate as a native addon instead of re-wrapping its API.
+//!
+//! The crate isn't built as a `cdylib` by default, so build the addon with `cargo rustc --lib --features napi --crate-type cdylib` and rename the library to `mime_more.node`.
+//!
+//! Failures are thrown as JS errors whose `code` is one of `UNKNOWN_TYPE` (the type can't be guessed) or `IO_ERROR` (the file can't be read).
+//!
+//! # Example
+//!
+//! ```js
+//! const { fromExt, fromPath, fromContent, toDataurl } = require("./mime_more.node");
+//!
+//! fromExt("png"); // "image/png"
+//! fromContent(Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a])); // "image/png"
+//! try {
+//!     fromExt("unknown");
+//! } catch (err) {
+//!     err.code; // "UNKNOWN_TYPE"
+//! }
+//! ```
+
+use crate::{Dataurl, Mime, MimeError};
+use napi::bindgen_prelude::Buffer;
+use napi::Error;
+use napi_derive::napi;
+use std::path::Path;
+
+/// The `code` of errors thrown when the type can't be guessed.
+const UNKNOWN_TYPE: &str = "UNKNOWN_TYPE";
+/// The `code` of errors thrown when the file can't be read.
+const IO_ERROR: &str = "IO_ERROR";
+
+type Result<T> = napi::Result<T, &'static str>;
+
+/// Converts an error into a JS error, using `IO_ERROR` for IO failures and `UNKNOWN_TYPE` otherwise.
+fn to_js_error(err: MimeError) -> Error<&'static str> {
+    let code = if matches!(err, MimeError::Io(_)) {
+        IO_ERROR
+    } else {
+        UNKNOWN_TYPE
+    };
+    Error::new(code, err.to_string())
+}
+
+/// Guesses the MIME type from the extension (without the leading dot).
+#[napi(js_name = "fromExt")]
+pub fn from_ext(ext: String) -> Result<String> {
+    #[cfg(feature = "extension")]
+    if let Ok(guessed) = Mime::from_ext(&ext) {
+        return Ok(guessed.to_string());
+    }
+
+    Mime::from_ext_light(&ext)
+        .map(|mime| mime.to_string())
+        .map_err(to_js_error)
+}
+
+/// Guesses the MIME type from the extension of the path. The file itself isn't read.
+#[napi(js_name = "fromPath")]
+pub fn from_path(path: String) -> Result<String> {
+    let path = Path::new(&path);
+
+    #[cfg(feature = "extension")]
+    if let Ok(guessed) = Mime::from_path(path) {
+        return Ok(guessed.to_string());
+    }
+
+    Mime::from_path_light(path)
+        .map(|mime| mime.to_string())
+        .map_err(to_js_error)
+}
+
+/// Infers the MIME type from the magic number of the buffer. The buffer is borrowed from JavaScript, not copied.
+#[napi(js_name = "fromContent")]
+pub fn from_content(data: Buffer) -> Result<String> {
+    sniff(&data)
+}
+
+/// Kept apart from `from_content`, so that it can be tested without a Node.js runtime to release the `Buffer`.
+fn sniff(data: &[u8]) -> Result<String> {
+    Mime::from_content(data)
+        .map(|mime| mime.to_string())
+        .map_err(to_js_error)
+}
+
+/// Reads the file at the path and encodes it as a data URL.
+#[napi(js_name = "toDataurl")]
+pub fn to_dataurl(path: String) -> Result<String> {
+    Dataurl::from_path(Path::new(&path))
+        .map(|dataurl| dataurl.to_string())
+        .map_err(to_js_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_ext_known_and_unknown() {
+        assert_eq!(from_ext("png".into()).unwrap(), "image/png");
+        let err = from_ext("unknown".into()).unwrap_err();
+        assert_eq!(err.status, UNKNOWN_TYPE);
+    }
+
+    #[test]
+    fn from_path_uses_extension() {
+        assert_eq!(from_path("assets/logo.png".into()).unwrap(), "image/png");
+        assert_eq!(
+            from_path("LICENSE".into()).unwrap_err().status,
+            UNKNOWN_TYPE
+        );
+    }
+
+    #[test]
+    fn from_content_png() {
+        let data = &[
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x11, 0x45, 0x14, 0x19, 0x19, 0x81, 0x0,
+        ];
+        assert_eq!(sniff(data).unwrap(), "image/png");
+        assert_eq!(sniff(&[]).unwrap_err().status, UNKNOWN_TYPE);
+    }
+
+    #[test]
+    fn to_dataurl_reads_file() {
+        let dataurl = to_dataurl("tests/fixtures/pixel.png".into()).unwrap();
+        assert!(dataurl.starts_with("data:image/png;base64,iVBORw0KGgo"));
+        let err = to_dataurl("does/not/exist.png".into()).unwrap_err();
+        assert_eq!(err.status, IO_ERROR);
+    }
+}
@@ -0,0 +1,386 @@
+//! Content negotiation against an `Accept` header, following [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-accept).
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::{negotiate::negotiate, Mime};
+//!
+//! let accept = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+//! let available = [Mime::APPLICATION_JSON, Mime::TEXT_HTML];
+//! assert_eq!(negotiate(&available, accept), Some(Mime::TEXT_HTML));
+//! assert_eq!(negotiate(&available, "image/*"), None);
+//! ```
+
+use crate::http::split_unquoted;
+use crate::{Mime, MimePattern};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::str::FromStr;
+
+/// A media range of an `Accept` header, with its quality and parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MimeRange {
+    pub pattern: MimePattern,
+    /// The `q` parameter, clamped to `[0, 1]`. It is `1` when missing or unparseable.
+    pub quality: f32,
+    /// The parameters before `q`, with lowercase names and unquoted values. Those after `q` are extensions of the header and are dropped.
+    pub params: Vec<(String, String)>,
+}
+
+impl MimeRange {
+    /// Returns `true` if `mime` matches the pattern and carries every parameter of the range. Parameter values are compared case-insensitively only for `charset`.
+    pub fn matches(&self, mime: &Mime) -> bool {
+        self.pattern.matches(mime)
+            && self.params.iter().all(|(name, value)| {
+                mime.get_param(name).is_some_and(|actual| {
+                    if name == "charset" {
+                        actual.eq_ignore_ascii_case(value)
+                    } else {
+                        actual == value
+                    }
+                })
+            })
+    }
+
+    /// Ranks ranges with more specific patterns, then more parameters, higher.
+    fn specificity(&self) -> (u8, usize) {
+        (self.pattern.specificity(), self.params.len())
+    }
+}
+
+/// A MIME type with a strict `q` parameter, e.g. `application/json;q=0.5`.
+///
+/// Values order by quality, then by specificity (`*/*`, then `type/*`, then `type/subtype`, then by parameter count), and finally by MIME type so that the order is total.
+#[derive(Debug, Clone)]
+pub struct MimeWithQuality {
+    /// The MIME type, with the parameters before `q`.
+    pub mime: Mime,
+    pub q: f32,
+}
+
+/// Parses a `qvalue` of RFC 9110: `0` or `1`, with at most three decimals, and no more than `1.000`.
+fn parse_qvalue(value: &str) -> anyhow::Result<f32> {
+    let (integer, decimals) = value.split_once('.').unwrap_or((value, ""));
+    let valid = match integer {
+        "0" => decimals.bytes().all(|byte| byte.is_ascii_digit()),
+        "1" => decimals.bytes().all(|byte| byte == b'0'),
+        _ => false,
+    };
+    if !valid || decimals.len() > 3 || value.ends_with('.') {
+        anyhow::bail!("Invalid q-value: {:?}", value);
+    }
+    value.parse().map_err(anyhow::Error::msg)
+}
+
+impl FromStr for MimeWithQuality {
+    type Err = anyhow::Error;
+
+    /// Parses `type/subtype[;params][;q=value]`, where the q-value is optional (defaulting to 1) and follows RFC 9110. Parameters after `q` are rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut q = None;
+        let mut essence_and_params = String::new();
+        for (index, part) in split_unquoted(s, ';').into_iter().enumerate() {
+            let part = part.trim();
+            if let Some(("q" | "Q", value)) = part
+                .split_once('=')
+                .map(|(name, value)| (name.trim(), value.trim()))
+            {
+                if index == 0 {
+                    anyhow::bail!("Missing MIME type before q-value: {:?}", s);
+                }
+                q = Some(parse_qvalue(value)?);
+                continue;
+            }
+            if q.is_some() {
+                anyhow::bail!("Unexpected parameter after q-value: {:?}", s);
+            }
+            if index > 0 {
+                essence_and_params.push_str("; ");
+            }
+            essence_and_params.push_str(part);
+        }
+        Ok(Self {
+            mime: Mime::from_str(&essence_and_params).map_err(anyhow::Error::msg)?,
+            q: q.unwrap_or(1.0),
+        })
+    }
+}
+
+/// Emits the q-value with at most three decimals, and omits it when it is 1.
+impl fmt::Display for MimeWithQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mime)?;
+        if self.q < 1.0 {
+            let q = alloc::format!("{:.3}", self.q);
+            write!(f, ";q={}", q.trim_end_matches('0').trim_end_matches('.'))?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for MimeWithQuality {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.q
+            .total_cmp(&other.q)
+            .then_with(|| self.mime.cmp_specificity(&other.mime))
+            .then_with(|| self.mime.cmp(&other.mime))
+    }
+}
+
+impl PartialOrd for MimeWithQuality {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for MimeWithQuality {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MimeWithQuality {}
+
+/// Parses an `Accept` header into its media ranges, in order. Malformed ranges are skipped.
+pub fn parse_accept(header: &str) -> Vec<MimeRange> {
+    split_unquoted(header, ',')
+        .into_iter()
+        .filter_map(parse_range)
+        .collect()
+}
+
+fn parse_range(range: &str) -> Option<MimeRange> {
+    let mut parts = split_unquoted(range, ';').into_iter();
+    let pattern = MimePattern::from_str(parts.next()?).ok()?;
+    let mut quality = 1.0;
+    let mut params = Vec::new();
+    for part in parts {
+        let Some((name, value)) = part.split_once('=') else {
+            continue;
+        };
+        let name = name.trim().to_ascii_lowercase();
+        let value = value.trim().trim_matches('"');
+        if name == "q" {
+            quality = value
+                .parse::<f32>()
+                .ok()
+                .filter(|quality| !quality.is_nan())
+                .map_or(1.0, |quality| quality.clamp(0.0, 1.0));
+            break;
+        }
+        params.push((name, value.to_string()));
+    }
+    Some(MimeRange {
+        pattern,
+        quality,
+        params,
+    })
+}
+
+/// Picks the best of `available` for an `Accept` header.
+///
+/// Each type takes the quality of the most specific range it matches, and the type with the highest non-zero quality wins, earlier types of `available` winning ties. A blank header accepts everything, and `*/*;q=0` rejects every type that no other range names.
+pub fn negotiate(available: &[Mime], accept: &str) -> Option<Mime> {
+    if accept.trim().is_empty() {
+        return available.first().cloned();
+    }
+    let ranges = parse_accept(accept);
+    let mut best: Option<(&Mime, f32)> = None;
+    for mime in available {
+        let quality = ranges
+            .iter()
+            .filter(|range| range.matches(mime))
+            .max_by_key(|range| range.specificity())
+            .map_or(0.0, |range| range.quality);
+        if quality > 0.0 && best.is_none_or(|(_, best)| quality > best) {
+            best = Some((mime, quality));
+        }
+    }
+    best.map(|(mime, _)| mime.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BROWSER: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+
+    fn mime(s: &str) -> Mime {
+        Mime::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let ranges = parse_accept(BROWSER);
+        assert_eq!(ranges.len(), 4);
+        assert_eq!(ranges[0].pattern.to_string(), "text/html");
+        assert_eq!(ranges[2].quality, 0.9);
+        assert_eq!(ranges[3].quality, 0.8);
+        assert!(ranges[3].pattern.is_any());
+
+        let ranges = parse_accept(
+            r#"text/plain; Charset="UTF-8"; q=0.5; ext=1, bogus, image/*;q=7, */*;q=-1, a/b;q=x"#,
+        );
+        assert_eq!(ranges.len(), 4);
+        assert_eq!(
+            ranges[0].params,
+            [("charset".to_string(), "UTF-8".to_string())]
+        );
+        assert_eq!(ranges[0].quality, 0.5);
+        assert_eq!(ranges[1].quality, 1.0);
+        assert_eq!(ranges[2].quality, 0.0);
+        assert_eq!(ranges[3].quality, 1.0);
+    }
+
+    #[test]
+    fn quoted_parameters() {
+        let ranges = parse_accept(r#"text/html;foo="a,b";q=0.5, text/plain;bar="c;d""#);
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0].params, [("foo".to_string(), "a,b".to_string())]);
+        assert_eq!(ranges[0].quality, 0.5);
+        assert_eq!(ranges[1].params, [("bar".to_string(), "c;d".to_string())]);
+
+        let html = mime(r#"text/html; foo="a,b""#);
+        assert_eq!(
+            negotiate(&[Mime::TEXT_PLAIN, html.clone()], r#"text/html;foo="a,b""#),
+            Some(html)
+        );
+
+        let quoted = MimeWithQuality::from_str(r#"text/plain; bar="c;d"; q=0.5"#).unwrap();
+        assert_eq!(quoted.mime.get_param("bar"), Some("c;d"));
+        assert_eq!(quoted.q, 0.5);
+    }
+
+    #[test]
+    fn specificity_ranks_patterns_first() {
+        let ranges = parse_accept("text/*;a=1;b=2;q=0.1, text/plain;q=0.9");
+        let best = |mime: &Mime| {
+            ranges
+                .iter()
+                .filter(|range| range.matches(mime))
+                .max_by_key(|range| range.specificity())
+                .map(|range| range.quality)
+        };
+        assert_eq!(best(&mime("text/plain; a=1; b=2")), Some(0.9));
+    }
+
+    #[test]
+    fn mime_with_quality() {
+        let json = MimeWithQuality::from_str("application/json;q=0.5").unwrap();
+        assert_eq!(json.mime, Mime::APPLICATION_JSON);
+        assert_eq!(json.q, 0.5);
+        assert_eq!(json.to_string(), "application/json;q=0.5");
+
+        let html = MimeWithQuality::from_str("text/html; charset=utf-8; Q=0.125").unwrap();
+        assert_eq!(html.mime, Mime::TEXT_HTML_UTF_8);
+        assert_eq!(html.to_string(), "text/html; charset=utf-8;q=0.125");
+
+        let any = MimeWithQuality::from_str("*/*").unwrap();
+        assert_eq!(any.q, 1.0);
+        assert_eq!(any.to_string(), "*/*");
+        assert_eq!(
+            MimeWithQuality::from_str("image/png;q=1.000").unwrap().q,
+            1.0
+        );
+        assert_eq!(
+            MimeWithQuality::from_str("image/png;q=0")
+                .unwrap()
+                .to_string(),
+            "image/png;q=0"
+        );
+    }
+
+    #[test]
+    fn mime_with_quality_rejects() {
+        for invalid in [
+            "image/png;q=1.5",
+            "image/png;q=-0.1",
+            "image/png;q=2",
+            "image/png;q=0.1234",
+            "image/png;q=1.001",
+            "image/png;q=.5",
+            "image/png;q=0.",
+            "image/png;q=abc",
+            "image/png;q=0.5;level=1",
+            "not a mime;q=0.5",
+        ] {
+            assert!(MimeWithQuality::from_str(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn mime_with_quality_ordering() {
+        let parse = |s: &str| MimeWithQuality::from_str(s).unwrap();
+        let mut ranges = [
+            parse("*/*;q=0.8"),
+            parse("text/html"),
+            parse("text/*"),
+            parse("application/xml;q=0.9"),
+            parse("text/html;level=1"),
+        ];
+        ranges.sort_by(|a, b| b.cmp(a));
+        let sorted: Vec<_> = ranges.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            sorted,
+            [
+                "text/html; level=1",
+                "text/html",
+                "text/*",
+                "application/xml;q=0.9",
+                "*/*;q=0.8"
+            ]
+        );
+    }
+
+    #[test]
+    fn browser_accept() {
+        let available = [Mime::APPLICATION_JSON, Mime::TEXT_HTML];
+        assert_eq!(negotiate(&available, BROWSER), Some(Mime::TEXT_HTML));
+        let available = [Mime::APPLICATION_JSON, mime("application/xml")];
+        assert_eq!(
+            negotiate(&available, BROWSER),
+            Some(mime("application/xml"))
+        );
+        assert_eq!(
+            negotiate(&[Mime::IMAGE_PNG], BROWSER),
+            Some(Mime::IMAGE_PNG)
+        );
+    }
+
+    #[test]
+    fn specificity_and_ties() {
+        // The specific range overrides the wildcard, whatever the order.
+        let accept = "text/*;q=0.2, text/plain;q=0.9";
+        assert_eq!(
+            negotiate(&[Mime::TEXT_CSS, Mime::TEXT_PLAIN], accept),
+            Some(Mime::TEXT_PLAIN)
+        );
+        let accept = "text/plain;q=0.1, text/plain;charset=utf-8";
+        assert_eq!(
+            negotiate(&[Mime::TEXT_PLAIN, Mime::TEXT_PLAIN_UTF_8], accept),
+            Some(Mime::TEXT_PLAIN_UTF_8)
+        );
+        // Ties go to the first available type.
+        assert_eq!(
+            negotiate(&[Mime::IMAGE_GIF, Mime::IMAGE_PNG], "image/*"),
+            Some(Mime::IMAGE_GIF)
+        );
+        assert_eq!(
+            negotiate(&[Mime::IMAGE_GIF, Mime::IMAGE_PNG], ""),
+            Some(Mime::IMAGE_GIF)
+        );
+    }
+
+    #[test]
+    fn rejections() {
+        assert_eq!(negotiate(&[Mime::TEXT_HTML], "*/*;q=0"), None);
+        assert_eq!(
+            negotiate(&[Mime::IMAGE_PNG, Mime::TEXT_HTML], "text/html, */*;q=0"),
+            Some(Mime::TEXT_HTML)
+        );
+        assert_eq!(negotiate(&[Mime::TEXT_HTML], "text/html;q=0, */*"), None);
+        assert_eq!(negotiate(&[], "*/*"), None);
+    }
+}
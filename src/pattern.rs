@@ -0,0 +1,145 @@
+//! Patterns such as `image/*` or `*/*`, as found in `Accept` headers and upload allow-lists.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::{Mime, MimePattern};
+//! use std::str::FromStr;
+//!
+//! let images = MimePattern::from_str("image/*").unwrap();
+//! assert!(images.matches(&Mime::IMAGE_PNG));
+//! assert!(!images.matches(&Mime::TEXT_PLAIN));
+//! assert!(MimePattern::from_str("image/").is_err());
+//! ```
+
+use crate::http::is_tchar;
+use crate::Mime;
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+
+/// A parsed `type/subtype` pattern, where the subtype, or both parts, may be `*`. Matching is case-insensitive and ignores parameters, and the suffix is part of the subtype, so `application/ld+json` matches `application/*` but not `application/json`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MimePattern {
+    /// The lowercase top-level type, or `None` for `*`.
+    type_: Option<String>,
+    /// The lowercase subtype, or `None` for `*`.
+    subtype: Option<String>,
+}
+
+impl MimePattern {
+    /// Returns `true` if the essence of `mime` matches the pattern.
+    pub fn matches(&self, mime: &Mime) -> bool {
+        let matches = |pattern: &Option<String>, name: &str| {
+            pattern.as_deref().is_none_or(|pattern| pattern == name)
+        };
+        matches(&self.type_, mime.type_().as_str())
+            && matches(&self.subtype, subtype_with_suffix(mime))
+    }
+
+    /// Returns `true` for `*/*`.
+    pub fn is_any(&self) -> bool {
+        self.type_.is_none()
+    }
+
+    /// Ranks `*/*` below `type/*` below `type/subtype`, as for `Accept` header precedence.
+    pub(crate) fn specificity(&self) -> u8 {
+        u8::from(self.type_.is_some()) + u8::from(self.subtype.is_some())
+    }
+}
+
+/// Returns the subtype including its `+suffix`, as `mime` splits them.
+fn subtype_with_suffix(mime: &Mime) -> &str {
+    let essence = mime.essence();
+    &essence[mime.type_().as_str().len() + 1..]
+}
+
+fn parse_part(part: &str) -> anyhow::Result<Option<String>> {
+    match part {
+        "*" => Ok(None),
+        "" => anyhow::bail!("Empty part in MIME pattern"),
+        _ if part.chars().all(|c| is_tchar(c) && c != '*') => Ok(Some(part.to_ascii_lowercase())),
+        _ => anyhow::bail!("Invalid character in MIME pattern part: {:?}", part),
+    }
+}
+
+impl FromStr for MimePattern {
+    type Err = anyhow::Error;
+
+    /// Parses `type/subtype`, `type/*`, or `*/*`. Parameters are accepted and ignored, while `*/subtype` is rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let essence = s.split(';').next().unwrap_or_default().trim();
+        let (type_, subtype) = essence
+            .split_once('/')
+            .ok_or_else(|| anyhow::anyhow!("Missing `/` in MIME pattern: {:?}", s))?;
+        let type_ = parse_part(type_)?;
+        let subtype = parse_part(subtype)?;
+        if type_.is_none() && subtype.is_some() {
+            anyhow::bail!("A wildcard type needs a wildcard subtype: {:?}", s);
+        }
+        Ok(Self { type_, subtype })
+    }
+}
+
+impl fmt::Display for MimePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            self.type_.as_deref().unwrap_or("*"),
+            self.subtype.as_deref().unwrap_or("*")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn pattern(s: &str) -> MimePattern {
+        MimePattern::from_str(s).unwrap()
+    }
+
+    fn mime(s: &str) -> Mime {
+        Mime::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn matching() {
+        assert!(pattern("*/*").matches(&mime("application/octet-stream")));
+        assert!(pattern("image/*").matches(&mime("image/png")));
+        assert!(pattern("IMAGE/*").matches(&mime("Image/SVG+XML")));
+        assert!(!pattern("image/*").matches(&mime("text/plain")));
+        assert!(pattern("text/html").matches(&mime("text/html; charset=utf-8")));
+        assert!(pattern("text/html; q=0.9").matches(&mime("TEXT/HTML")));
+        assert!(!pattern("text/html").matches(&mime("text/plain")));
+    }
+
+    #[test]
+    fn suffixes() {
+        assert!(pattern("application/*").matches(&mime("application/ld+json")));
+        assert!(pattern("application/ld+json").matches(&mime("application/ld+json")));
+        assert!(!pattern("application/json").matches(&mime("application/ld+json")));
+        assert!(pattern("image/svg+xml").matches(&Mime::IMAGE_SVG));
+        assert!(!pattern("image/svg").matches(&Mime::IMAGE_SVG));
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        for invalid in [
+            "",
+            "image",
+            "image/",
+            "/png",
+            "*/png",
+            "im*ge/png",
+            "image/p ng",
+            "a/b/c",
+        ] {
+            assert!(MimePattern::from_str(invalid).is_err(), "{invalid:?}");
+        }
+        assert!(pattern("*/*").is_any());
+        assert_eq!(pattern(" Image/* ; q=1").to_string(), "image/*");
+    }
+}
@@ -0,0 +1,121 @@
+#![cfg(any(feature = "extension", feature = "extension-light"))]
+//! The reverse of the extension tables: the extension a file of a given MIME type should be written with, and every extension it may have.
+
+use alloc::vec::Vec;
+use phf::{phf_map, Map};
+
+/// The preferred extension of every type in the light table, plus a few common ones. It is consulted first, so that types with several extensions (e.g. `jpg`, `jpeg`, and `jpe` for `image/jpeg`) resolve to the usual one rather than the first one `mime_guess` lists.
+pub static PREFERRED_EXTENSIONS: Map<&'static str, &'static str> = phf_map! {
+    // Text
+    "text/plain" => "txt",
+    "text/css" => "css",
+    "text/html" => "html",
+    "text/javascript" => "js",
+    "application/javascript" => "js",
+    "application/json" => "json",
+    "text/x-yaml" => "yaml",
+    "text/x-toml" => "toml",
+    "text/markdown" => "md",
+    "text/xml" => "xml",
+    "application/xml" => "xml",
+    "text/csv" => "csv",
+    "text/tab-separated-values" => "tsv",
+    // Images
+    "image/bmp" => "bmp",
+    "image/avif" => "avif",
+    "image/gif" => "gif",
+    "image/x-icon" => "ico",
+    "image/vnd.microsoft.icon" => "ico",
+    "image/x-icns" => "icns",
+    "image/jpeg" => "jpg",
+    "image/png" => "png",
+    "image/svg+xml" => "svg",
+    "image/webp" => "webp",
+    "image/tiff" => "tiff",
+    // Fonts
+    "font/otf" => "otf",
+    "font/ttf" => "ttf",
+    "font/collection" => "ttc",
+    "font/woff" => "woff",
+    "font/woff2" => "woff2",
+    // Audios
+    "audio/aac" => "aac",
+    "audio/midi" => "mid",
+    "audio/mpeg" => "mp3",
+    "audio/ogg" => "ogg",
+    "audio/wav" => "wav",
+    "audio/webm" => "weba",
+    "audio/flac" => "flac",
+    "audio/x-mpegurl" => "m3u8",
+    "audio/m4a" => "m4a",
+    "audio/vnd.dlna.mpeg-tts" => "ts",
+    // Videos
+    "video/x-msvideo" => "avi",
+    "video/mpeg" => "mpeg",
+    "video/ogg" => "ogv",
+    "video/x-ivf" => "ivf",
+    "video/webm" => "webm",
+    "video/mp4" => "mp4",
+    "video/x-flv" => "flv",
+    "video/quicktime" => "mov",
+    "video/x-ms-wmv" => "wmv",
+    // Other
+    "application/pdf" => "pdf",
+    "application/wasm" => "wasm",
+    "application/manifest+json" => "webmanifest",
+    "application/octet-stream" => "bin",
+    "application/zip" => "zip",
+    "application/gzip" => "gz",
+};
+
+/// Looks up `essence` in `PREFERRED_EXTENSIONS`, then in `mime_guess` with the `extension` feature.
+pub fn preferred_extension(mime: &mime::Mime) -> Option<&'static str> {
+    if let Some(ext) = PREFERRED_EXTENSIONS.get(mime.essence_str()) {
+        return Some(*ext);
+    }
+    #[cfg(feature = "extension")]
+    if mime.type_() != mime::STAR {
+        return mime_guess::get_mime_extensions(mime).and_then(|exts| exts.first().copied());
+    }
+    None
+}
+
+/// Collects the extensions of `mime` from the light table and from `mime_guess` with the `extension` feature. The preferred extension always comes first, and the others follow in alphabetical order.
+pub fn all_extensions(mime: &mime::Mime) -> Vec<&'static str> {
+    #[allow(unused_mut)]
+    let mut extensions: Vec<&'static str> = Vec::new();
+    #[cfg(feature = "extension-light")]
+    extensions.extend(
+        crate::light_guess::MIME_TYPES
+            .entries()
+            .filter(|(_, ty)| **ty == mime.essence_str())
+            .map(|(ext, _)| *ext),
+    );
+    #[cfg(feature = "extension")]
+    if mime.type_() != mime::STAR {
+        extensions.extend(mime_guess::get_mime_extensions(mime).unwrap_or_default());
+    }
+    extensions.sort_unstable();
+    extensions.dedup();
+    if let Some(preferred) = preferred_extension(mime) {
+        match extensions.iter().position(|ext| *ext == preferred) {
+            Some(index) => extensions[..=index].rotate_right(1),
+            None => extensions.insert(0, preferred),
+        }
+    }
+    extensions
+}
+
+#[cfg(all(test, feature = "extension-light"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_the_light_table() {
+        for (ext, mime) in crate::light_guess::MIME_TYPES.entries() {
+            let preferred = preferred_extension(&mime.parse().unwrap())
+                .unwrap_or_else(|| panic!("no preferred extension for {mime} (.{ext})"));
+            assert_eq!(crate::light_guess::MIME_TYPES.get(preferred), Some(mime));
+        }
+    }
+}
@@ -0,0 +1,245 @@
+#![cfg(any(feature = "axum", feature = "actix"))]
+//! Thin responder helpers for web frameworks. With the `axum` feature, the types here implement `IntoResponse`; with the `actix` feature, they implement `Responder`. Either way the `Content-Type` header is filled in from the guessed MIME type.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use mime_more::responder::ServeGuessed;
+//!
+//! async fn logo() -> ServeGuessed {
+//!     ServeGuessed("assets/logo.png".into())
+//! }
+//! ```
+
+use crate::Mime;
+use bytes::Bytes;
+use std::path::PathBuf;
+
+/// A response body with a known MIME type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedBytes {
+    pub mime: Mime,
+    pub body: Bytes,
+}
+
+impl TypedBytes {
+    pub fn new(mime: Mime, body: impl Into<Bytes>) -> Self {
+        Self {
+            mime,
+            body: body.into(),
+        }
+    }
+}
+
+#[cfg(feature = "dataurl")]
+impl From<crate::Dataurl> for TypedBytes {
+    fn from(dataurl: crate::Dataurl) -> Self {
+        Self::new(dataurl.mime, dataurl.data)
+    }
+}
+
+/// Serves the file at the given path, guessing its MIME type via `Mime::from_file`. Missing files respond with `404 Not Found`, and files whose type can't be guessed are served as `application/octet-stream`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServeGuessed(pub PathBuf);
+
+/// An opened file together with its guessed MIME type, or the reason it couldn't be opened.
+enum Opened {
+    Found(Mime, tokio::fs::File),
+    NotFound,
+    Failed,
+}
+
+impl ServeGuessed {
+    fn open(&self) -> Opened {
+        match std::fs::File::open(&self.0) {
+            Ok(file) => {
+                let mime = Mime::from_file(&self.0).unwrap_or(Mime::APPLICATION_OCTET_STREAM);
+                Opened::Found(mime, tokio::fs::File::from_std(file))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Opened::NotFound,
+            Err(_) => Opened::Failed,
+        }
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum_impl {
+    use super::{Opened, ServeGuessed, TypedBytes};
+    use axum::body::Body;
+    use axum::http::{header, StatusCode};
+    use axum::response::{IntoResponse, Response};
+    use tokio_util::io::ReaderStream;
+
+    impl IntoResponse for TypedBytes {
+        fn into_response(self) -> Response {
+            ([(header::CONTENT_TYPE, self.mime.to_string())], self.body).into_response()
+        }
+    }
+
+    #[cfg(feature = "dataurl")]
+    impl IntoResponse for crate::Dataurl {
+        fn into_response(self) -> Response {
+            TypedBytes::from(self).into_response()
+        }
+    }
+
+    impl IntoResponse for ServeGuessed {
+        fn into_response(self) -> Response {
+            match self.open() {
+                Opened::Found(mime, file) => (
+                    [(header::CONTENT_TYPE, mime.to_string())],
+                    Body::from_stream(ReaderStream::new(file)),
+                )
+                    .into_response(),
+                Opened::NotFound => StatusCode::NOT_FOUND.into_response(),
+                Opened::Failed => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "actix")]
+mod actix_impl {
+    use super::{Opened, ServeGuessed, TypedBytes};
+    use actix_web::body::BoxBody;
+    use actix_web::{HttpRequest, HttpResponse, Responder};
+    use tokio_util::io::ReaderStream;
+
+    impl Responder for TypedBytes {
+        type Body = BoxBody;
+
+        fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+            HttpResponse::Ok()
+                .content_type(self.mime.to_string())
+                .body(self.body)
+        }
+    }
+
+    #[cfg(feature = "dataurl")]
+    impl Responder for crate::Dataurl {
+        type Body = BoxBody;
+
+        fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+            TypedBytes::from(self).respond_to(req)
+        }
+    }
+
+    impl Responder for ServeGuessed {
+        type Body = BoxBody;
+
+        fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+            match self.open() {
+                Opened::Found(mime, file) => HttpResponse::Ok()
+                    .content_type(mime.to_string())
+                    .streaming(ReaderStream::new(file)),
+                Opened::NotFound => HttpResponse::NotFound().finish(),
+                Opened::Failed => HttpResponse::InternalServerError().finish(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    /// The type `ServeGuessed` gives `Cargo.toml` with the enabled guessers.
+    fn cargo_toml_type() -> &'static str {
+        if cfg!(any(feature = "extension", feature = "extension-light")) {
+            "text/x-toml"
+        } else if cfg!(feature = "texture") {
+            "text/plain"
+        } else {
+            "application/octet-stream"
+        }
+    }
+
+    #[cfg(feature = "axum")]
+    mod axum_tests {
+        use super::*;
+        use axum::body::to_bytes;
+        use axum::http::{header, StatusCode};
+        use axum::response::IntoResponse;
+
+        #[tokio::test]
+        async fn typed_bytes_sets_content_type() {
+            let typed = TypedBytes::new(Mime::from_str("text/css").unwrap(), "body{}");
+            let response = typed.into_response();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[header::CONTENT_TYPE], "text/css");
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert_eq!(&body[..], b"body{}");
+        }
+
+        #[tokio::test]
+        async fn serve_guessed_streams_file() {
+            let response = ServeGuessed("Cargo.toml".into()).into_response();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[header::CONTENT_TYPE], cargo_toml_type());
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert_eq!(&body[..], std::fs::read("Cargo.toml").unwrap());
+        }
+
+        #[tokio::test]
+        async fn serve_guessed_falls_back_to_octet_stream() {
+            let response = ServeGuessed("LICENSE".into()).into_response();
+            assert_eq!(response.status(), StatusCode::OK);
+            let expected = if cfg!(feature = "texture") {
+                "text/plain"
+            } else {
+                "application/octet-stream"
+            };
+            assert_eq!(response.headers()[header::CONTENT_TYPE], expected);
+        }
+
+        #[tokio::test]
+        async fn serve_guessed_missing_file() {
+            let response = ServeGuessed("does/not/exist.png".into()).into_response();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+    }
+
+    #[cfg(feature = "actix")]
+    mod actix_tests {
+        use super::*;
+        use actix_web::body::to_bytes;
+        use actix_web::http::{header, StatusCode};
+        use actix_web::test::TestRequest;
+        use actix_web::Responder;
+
+        #[tokio::test]
+        async fn typed_bytes_sets_content_type() {
+            let req = TestRequest::default().to_http_request();
+            let typed = TypedBytes::new(Mime::from_str("text/css").unwrap(), "body{}");
+            let response = typed.respond_to(&req);
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers().get(header::CONTENT_TYPE).unwrap(),
+                "text/css"
+            );
+            let body = to_bytes(response.into_body()).await.unwrap();
+            assert_eq!(&body[..], b"body{}");
+        }
+
+        #[tokio::test]
+        async fn serve_guessed_streams_file() {
+            let req = TestRequest::default().to_http_request();
+            let response = ServeGuessed("Cargo.toml".into()).respond_to(&req);
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers().get(header::CONTENT_TYPE).unwrap(),
+                cargo_toml_type()
+            );
+            let body = to_bytes(response.into_body()).await.unwrap();
+            assert_eq!(&body[..], std::fs::read("Cargo.toml").unwrap());
+        }
+
+        #[tokio::test]
+        async fn serve_guessed_missing_file() {
+            let req = TestRequest::default().to_http_request();
+            let response = ServeGuessed("does/not/exist.png".into()).respond_to(&req);
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+#![cfg(all(
+    feature = "serde",
+    any(feature = "extension", feature = "extension-light")
+))]
+
+//! Serde helpers for fields of type `Mime`, for use with `#[serde(with = "...")]`.
+
+/// Deserializes either a MIME type (`"image/png"`) or a bare extension (`"png"`, or `".png"`), and serializes the MIME type. A string with a `/` is parsed like `Mime::from_str`, and any other string is guessed as an extension via `Mime::from_ext`, falling back to `Mime::from_ext_light`.
+///
+/// # Example
+///
+/// ```rust
+/// use mime_more::Mime;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Asset {
+///     #[serde(with = "mime_more::serde_ext::lenient")]
+///     mime: Mime,
+/// }
+///
+/// let asset: Asset = serde_json::from_str(r#"{"mime":"svg"}"#).unwrap();
+/// assert_eq!(asset.mime, Mime::IMAGE_SVG);
+/// assert_eq!(serde_json::to_string(&asset).unwrap(), r#"{"mime":"image/svg+xml"}"#);
+/// ```
+pub mod lenient {
+    use crate::{Mime, MimeError};
+    use core::str::FromStr;
+
+    /// Serializes the MIME type as its string form, like `Mime`'s own `Serialize`.
+    pub fn serialize<S: serde::Serializer>(mime: &Mime, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(mime, serializer)
+    }
+
+    /// Deserializes a MIME type or a bare extension.
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Mime, D::Error> {
+        struct LenientVisitor;
+
+        impl serde::de::Visitor<'_> for LenientVisitor {
+            type Value = Mime;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("a media type or a file extension")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Mime, E> {
+                parse(v).map_err(|err| {
+                    E::custom(format_args!("invalid media type or extension {v:?}: {err}"))
+                })
+            }
+        }
+
+        deserializer.deserialize_str(LenientVisitor)
+    }
+
+    fn parse(s: &str) -> Result<Mime, MimeError> {
+        if s.contains('/') {
+            return Mime::from_str(s);
+        }
+        #[cfg(feature = "extension")]
+        let guessed = Mime::from_ext(s);
+        #[cfg(all(feature = "extension", feature = "extension-light"))]
+        let guessed = guessed.or_else(|_| Mime::from_ext_light(s));
+        #[cfg(not(feature = "extension"))]
+        let guessed = Mime::from_ext_light(s);
+        guessed
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::Mime;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Asset {
+            #[serde(with = "super")]
+            mime: Mime,
+        }
+
+        fn asset(json: &str) -> Result<Asset, serde_json::Error> {
+            serde_json::from_str(json)
+        }
+
+        #[test]
+        fn full_types() {
+            let parsed = asset(r#"{"mime":"text/html; charset=utf-8"}"#).unwrap();
+            assert_eq!(parsed.mime, Mime::TEXT_HTML_UTF_8);
+            assert_eq!(
+                serde_json::to_string(&parsed).unwrap(),
+                r#"{"mime":"text/html; charset=utf-8"}"#
+            );
+        }
+
+        #[test]
+        fn extensions() {
+            assert_eq!(asset(r#"{"mime":"png"}"#).unwrap().mime, Mime::IMAGE_PNG);
+            assert_eq!(asset(r#"{"mime":".CSS"}"#).unwrap().mime, Mime::TEXT_CSS);
+            assert_eq!(
+                serde_json::to_string(&asset(r#"{"mime":"json"}"#).unwrap()).unwrap(),
+                r#"{"mime":"application/json"}"#
+            );
+        }
+
+        #[test]
+        fn invalid() {
+            let err = asset(r#"{"mime":"xyzzy"}"#).unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("invalid media type or extension \"xyzzy\""));
+            assert!(asset(r#"{"mime":"image png/x"}"#).is_err());
+            assert!(asset(r#"{"mime":""}"#).is_err());
+            assert!(asset(r#"{"mime":42}"#).is_err());
+        }
+    }
+}
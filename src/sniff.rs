@@ -0,0 +1,388 @@
+//! Reproduces how a browser picks the type of an HTTP response, following the [WHATWG MIME Sniffing Standard](https://mimesniff.spec.whatwg.org/) and the `nosniff` checks of [Fetch](https://fetch.spec.whatwg.org/#x-content-type-options-header).
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::sniff::effective_type;
+//!
+//! let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+//! let sniffed = effective_type(Some("image/jpeg"), false, png);
+//! assert_eq!(sniffed.mime, "image/png");
+//! assert!(sniffed.blocks_script);
+//!
+//! let trusted = effective_type(Some("text/plain"), true, png);
+//! assert_eq!(trusted.mime, "text/plain");
+//! ```
+
+use crate::Mime;
+use core::str::FromStr;
+
+/// How many leading bytes of the body are inspected, as per the standard.
+pub const RESOURCE_HEADER_LENGTH: usize = 1445;
+
+/// The type a browser would use for a response, with the blocking decisions for `<script>` and `<link rel="stylesheet">` destinations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectiveType {
+    pub mime: Mime,
+    /// Whether the type was taken from the body rather than from the `Content-Type` header.
+    pub sniffed: bool,
+    /// Whether a browser refuses to run the response as a script: always for image, audio, video, and `text/csv` types, and for any non-JavaScript type under `nosniff`.
+    pub blocks_script: bool,
+    /// Whether a browser refuses to apply the response as a stylesheet under `nosniff`, i.e. when the type is not `text/css`.
+    pub blocks_style: bool,
+}
+
+/// Computes the type a browser would use for a response.
+///
+/// `declared` is the `Content-Type` header value, if any, and `nosniff` is whether `X-Content-Type-Options: nosniff` was sent. Only the first `RESOURCE_HEADER_LENGTH` bytes of `body` are inspected. An unparsable header is handled like a missing one.
+pub fn effective_type(declared: Option<&str>, nosniff: bool, body: &[u8]) -> EffectiveType {
+    let header = &body[..body.len().min(RESOURCE_HEADER_LENGTH)];
+    let supplied = declared
+        .and_then(|declared| Mime::from_str(declared.trim()).ok())
+        .filter(|mime| {
+            !matches!(
+                mime.essence(),
+                "unknown/unknown" | "application/unknown" | "*/*"
+            )
+        });
+
+    let (mime, sniffed) = match supplied {
+        None => (identify_unknown(header, !nosniff), true),
+        Some(supplied) if nosniff => (supplied, false),
+        Some(supplied) => {
+            match sniff_supplied(declared.unwrap_or_default().trim(), &supplied, header) {
+                Some(mime) => (mime, true),
+                None => (supplied, false),
+            }
+        }
+    };
+    debug!(mime = %mime, sniffed, nosniff, "effective type computed");
+
+    let essence = mime.essence();
+    let blocks_script = matches!(mime.type_(), mime::IMAGE | mime::AUDIO | mime::VIDEO)
+        || essence == "text/csv"
+        || (nosniff && !is_javascript(essence));
+    let blocks_style = nosniff && essence != "text/css";
+    EffectiveType {
+        mime,
+        sniffed,
+        blocks_script,
+        blocks_style,
+    }
+}
+
+/// Sniffs when a type was supplied without `nosniff`. Returns `None` when the supplied type stands.
+fn sniff_supplied(raw: &str, supplied: &Mime, header: &[u8]) -> Option<Mime> {
+    // Servers such as Apache label everything `text/plain` by default, so these exact values are checked for binary content.
+    if matches!(
+        raw,
+        "text/plain"
+            | "text/plain; charset=ISO-8859-1"
+            | "text/plain; charset=iso-8859-1"
+            | "text/plain; charset=UTF-8"
+    ) {
+        return Some(distinguish_text_or_binary(header));
+    }
+
+    let essence = supplied.essence();
+    if essence == "text/html"
+        || essence == "text/xml"
+        || essence == "application/xml"
+        || essence.ends_with("+xml")
+    {
+        return None;
+    }
+    match supplied.type_() {
+        mime::IMAGE => match_image(header).map(known),
+        mime::AUDIO | mime::VIDEO => match_audio_video(header).map(known),
+        _ => None,
+    }
+}
+
+/// The rules for distinguishing if a resource is text or binary.
+fn distinguish_text_or_binary(header: &[u8]) -> Mime {
+    if has_bom(header) {
+        return known("text/plain");
+    }
+    if !header.iter().copied().any(is_binary_data_byte) {
+        return known("text/plain");
+    }
+    identify_unknown(header, false)
+}
+
+/// The rules for identifying an unknown MIME type. HTML, XML, and PDF are only recognized when `sniff_scriptable` is set.
+fn identify_unknown(header: &[u8], sniff_scriptable: bool) -> Mime {
+    if sniff_scriptable {
+        if let Some(mime) = match_scriptable(header) {
+            return known(mime);
+        }
+    }
+    if header.starts_with(b"%!PS-Adobe-") {
+        return known("application/postscript");
+    }
+    if has_bom(header) {
+        return known("text/plain");
+    }
+    if let Some(mime) = match_image(header)
+        .or_else(|| match_audio_video(header))
+        .or_else(|| match_archive(header))
+    {
+        return known(mime);
+    }
+    if !header.iter().copied().any(is_binary_data_byte) {
+        return known("text/plain");
+    }
+    known("application/octet-stream")
+}
+
+fn match_scriptable(header: &[u8]) -> Option<&'static str> {
+    const HTML_TAGS: [&[u8]; 17] = [
+        b"<!DOCTYPE HTML",
+        b"<HTML",
+        b"<HEAD",
+        b"<SCRIPT",
+        b"<IFRAME",
+        b"<H1",
+        b"<DIV",
+        b"<FONT",
+        b"<TABLE",
+        b"<A",
+        b"<STYLE",
+        b"<TITLE",
+        b"<B",
+        b"<BODY",
+        b"<BR",
+        b"<P",
+        b"<!--",
+    ];
+
+    let start = header
+        .iter()
+        .position(|byte| !matches!(byte, b'\t' | b'\n' | b'\x0C' | b'\r' | b' '))
+        .unwrap_or(header.len());
+    let trimmed = &header[start..];
+    let is_html = HTML_TAGS.iter().any(|tag| {
+        trimmed.len() > tag.len()
+            && trimmed[..tag.len()].eq_ignore_ascii_case(tag)
+            && matches!(trimmed[tag.len()], b' ' | b'>')
+    });
+    if is_html {
+        Some("text/html")
+    } else if trimmed.starts_with(b"<?xml") {
+        Some("text/xml")
+    } else if header.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else {
+        None
+    }
+}
+
+fn match_image(header: &[u8]) -> Option<&'static str> {
+    if header.starts_with(b"\0\0\x01\0") || header.starts_with(b"\0\0\x02\0") {
+        Some("image/x-icon")
+    } else if header.starts_with(b"BM") {
+        Some("image/bmp")
+    } else if header.starts_with(b"GIF87a") || header.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if riff(header, b"WEBP") && header.get(12..14) == Some(b"VP") {
+        Some("image/webp")
+    } else if header.starts_with(b"\x89PNG\r\n\x1A\n") {
+        Some("image/png")
+    } else if header.starts_with(b"\xFF\xD8\xFF") {
+        Some("image/jpeg")
+    } else {
+        None
+    }
+}
+
+fn match_audio_video(header: &[u8]) -> Option<&'static str> {
+    if header.starts_with(b"FORM") && header.get(8..12) == Some(b"AIFF") {
+        Some("audio/aiff")
+    } else if header.starts_with(b"ID3") {
+        Some("audio/mpeg")
+    } else if header.starts_with(b"OggS\0") {
+        Some("application/ogg")
+    } else if header.starts_with(b"MThd\0\0\0\x06") {
+        Some("audio/midi")
+    } else if riff(header, b"AVI ") {
+        Some("video/avi")
+    } else if riff(header, b"WAVE") {
+        Some("audio/wave")
+    } else if is_mp4(header) {
+        Some("video/mp4")
+    } else if header.starts_with(b"\x1A\x45\xDF\xA3")
+        && contains(&header[..header.len().min(38)], b"webm")
+    {
+        Some("video/webm")
+    } else {
+        None
+    }
+}
+
+fn match_archive(header: &[u8]) -> Option<&'static str> {
+    if header.starts_with(b"\x1F\x8B\x08") {
+        Some("application/x-gzip")
+    } else if header.starts_with(b"PK\x03\x04") {
+        Some("application/zip")
+    } else if header.starts_with(b"Rar!\x1A\x07\0") {
+        Some("application/x-rar-compressed")
+    } else {
+        None
+    }
+}
+
+/// Checks for a UTF-16BE, UTF-16LE, or UTF-8 byte order mark.
+fn has_bom(header: &[u8]) -> bool {
+    header.starts_with(b"\xFE\xFF")
+        || header.starts_with(b"\xFF\xFE")
+        || header.starts_with(b"\xEF\xBB\xBF")
+}
+
+fn riff(header: &[u8], format: &[u8]) -> bool {
+    header.starts_with(b"RIFF") && header.get(8..12) == Some(format)
+}
+
+/// Looks for an `mp4` brand in the `ftyp` box, which has to start the body.
+fn is_mp4(header: &[u8]) -> bool {
+    let Some(size) = header.get(..4) else {
+        return false;
+    };
+    let size = u32::from_be_bytes([size[0], size[1], size[2], size[3]]) as usize;
+    if size < 12 || !size.is_multiple_of(4) || header.len() < size || &header[4..8] != b"ftyp" {
+        return false;
+    }
+    header[8..size]
+        .chunks_exact(4)
+        .enumerate()
+        // The second word is the minor version, not a brand.
+        .any(|(index, brand)| index != 1 && brand.starts_with(b"mp4"))
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+fn is_binary_data_byte(byte: u8) -> bool {
+    matches!(byte, 0x00..=0x08 | 0x0B | 0x0E..=0x1A | 0x1C..=0x1F)
+}
+
+fn is_javascript(essence: &str) -> bool {
+    matches!(
+        essence,
+        "application/ecmascript"
+            | "application/javascript"
+            | "application/x-ecmascript"
+            | "application/x-javascript"
+            | "text/ecmascript"
+            | "text/javascript"
+            | "text/javascript1.0"
+            | "text/javascript1.1"
+            | "text/javascript1.2"
+            | "text/javascript1.3"
+            | "text/javascript1.4"
+            | "text/javascript1.5"
+            | "text/jscript"
+            | "text/livescript"
+            | "text/x-ecmascript"
+            | "text/x-javascript"
+    )
+}
+
+fn known(mime: &str) -> Mime {
+    Mime::from_str(mime).expect("The sniffing tables only hold valid MIME types")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1A\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01";
+
+    #[test]
+    fn nosniff_keeps_wrong_type() {
+        let sniffed = effective_type(Some("text/plain"), true, PNG);
+        assert_eq!(sniffed.mime, "text/plain");
+        assert!(!sniffed.sniffed);
+        assert!(sniffed.blocks_script);
+        assert!(sniffed.blocks_style);
+
+        let css = effective_type(Some("text/css; charset=utf-8"), true, b"a{}");
+        assert!(!css.blocks_style);
+        assert!(css.blocks_script);
+        let js = effective_type(Some("text/javascript"), true, b"alert(1)");
+        assert!(!js.blocks_script);
+        assert!(js.blocks_style);
+    }
+
+    #[test]
+    fn missing_header() {
+        let html = effective_type(None, false, b"\n  <html><body></body></html>");
+        assert_eq!(html.mime, "text/html");
+        assert!(html.sniffed);
+
+        // HTML is never sniffed under `nosniff`.
+        let html = effective_type(None, true, b"<html><body></body></html>");
+        assert_eq!(html.mime, "text/plain");
+
+        assert_eq!(effective_type(None, false, PNG).mime, "image/png");
+        assert_eq!(effective_type(Some("*/*"), false, PNG).mime, "image/png");
+        assert_eq!(
+            effective_type(Some("not a type"), false, b"%PDF-1.7").mime,
+            "application/pdf"
+        );
+        assert_eq!(
+            effective_type(None, false, b"\0\x01binary").mime,
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn octet_stream_over_png() {
+        // `application/octet-stream` is a real type, so browsers download it instead of sniffing.
+        let sniffed = effective_type(Some("application/octet-stream"), false, PNG);
+        assert_eq!(sniffed.mime, "application/octet-stream");
+        assert!(!sniffed.sniffed);
+        assert!(!sniffed.blocks_script);
+    }
+
+    #[test]
+    fn supplied_type_sniffing() {
+        let sniffed = effective_type(Some("image/jpeg"), false, PNG);
+        assert_eq!(sniffed.mime, "image/png");
+        assert!(sniffed.sniffed);
+
+        // The Apache bug check only sniffs binary content, and never as HTML.
+        assert_eq!(
+            effective_type(Some("text/plain; charset=ISO-8859-1"), false, PNG).mime,
+            "image/png"
+        );
+        assert_eq!(
+            effective_type(Some("text/plain"), false, b"<html>").mime,
+            "text/plain"
+        );
+        assert_eq!(
+            effective_type(Some("text/html; charset=utf-8"), false, PNG).mime,
+            "text/html; charset=utf-8"
+        );
+    }
+
+    #[test]
+    fn media_patterns() {
+        assert_eq!(
+            match_audio_video(b"\0\0\0\x18ftypmp42\0\0\0\0mp42isom"),
+            Some("video/mp4")
+        );
+        assert_eq!(
+            match_audio_video(b"\0\0\0\x18ftypisom\0\0\0\0isomiso2"),
+            None
+        );
+        assert_eq!(
+            match_audio_video(b"RIFF\0\0\0\0WAVEfmt "),
+            Some("audio/wave")
+        );
+        assert_eq!(match_image(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(match_archive(b"PK\x03\x04"), Some("application/zip"));
+    }
+}
@@ -0,0 +1,481 @@
+#![cfg(all(
+    feature = "system",
+    any(
+        target_os = "linux",
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly"
+    )
+))]
+//! Lookups in the system MIME database ([shared-mime-info](https://specifications.freedesktop.org/shared-mime-info-spec/latest/)), so that desktop applications get the same answers as `xdg-mime`.
+//!
+//! The `globs2` and `magic` files are read from the `mime` directory of `$XDG_DATA_HOME` and every entry of `$XDG_DATA_DIRS`. The database is optional: when none is installed, `Mime::from_path_system` falls back to the built-in logic.
+//!
+//! # Example
+//!
+//! ```rust
+//! use mime_more::system::SystemDatabase;
+//!
+//! let database = SystemDatabase::load(["tests/fixtures/xdg"]).unwrap();
+//! assert_eq!(database.glob("Makefile.am").unwrap(), "text/x-makefile");
+//! ```
+
+use crate::Mime;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// A pattern of the `globs2` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Glob {
+    weight: u32,
+    mime: Mime,
+    pattern: Vec<char>,
+    case_sensitive: bool,
+}
+
+impl Glob {
+    fn is_literal(&self) -> bool {
+        !self.pattern.iter().any(|c| matches!(c, '*' | '?' | '['))
+    }
+}
+
+/// A line of a `magic` section. Children follow their parent with an `indent` one higher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Matchlet {
+    indent: u32,
+    offset: usize,
+    value: Vec<u8>,
+    mask: Option<Vec<u8>>,
+    range: usize,
+}
+
+impl Matchlet {
+    fn matches(&self, data: &[u8]) -> bool {
+        (self.offset..self.offset + self.range).any(|start| {
+            let Some(window) = data.get(start..start + self.value.len()) else {
+                return false;
+            };
+            match &self.mask {
+                Some(mask) => window
+                    .iter()
+                    .zip(&self.value)
+                    .zip(mask)
+                    .all(|((byte, value), mask)| byte & mask == value & mask),
+                None => window == self.value.as_slice(),
+            }
+        })
+    }
+}
+
+/// A `[priority:mime]` section of the `magic` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MagicRule {
+    priority: u32,
+    mime: Mime,
+    matchlets: Vec<Matchlet>,
+}
+
+impl MagicRule {
+    /// Returns `true` if a matchlet at `indent` from `start` on matches, together with one of its children if it has any.
+    fn matches_from(&self, start: usize, indent: u32, data: &[u8]) -> bool {
+        let mut i = start;
+        while i < self.matchlets.len() && self.matchlets[i].indent >= indent {
+            let matchlet = &self.matchlets[i];
+            if matchlet.indent == indent && matchlet.matches(data) {
+                let has_children = self
+                    .matchlets
+                    .get(i + 1)
+                    .is_some_and(|child| child.indent > indent);
+                if !has_children || self.matches_from(i + 1, indent + 1, data) {
+                    return true;
+                }
+            }
+            i += 1;
+        }
+        false
+    }
+
+    fn matches(&self, data: &[u8]) -> bool {
+        self.matches_from(0, 0, data)
+    }
+}
+
+/// The glob patterns and magic rules of one or more shared-mime-info directories.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SystemDatabase {
+    globs: Vec<Glob>,
+    magic: Vec<MagicRule>,
+}
+
+impl SystemDatabase {
+    /// Loads the `mime/globs2` and `mime/magic` files under each of the data directories. Returns `None` when none of them has a database.
+    pub fn load<P: AsRef<Path>>(data_dirs: impl IntoIterator<Item = P>) -> Option<Self> {
+        let mut database = Self::default();
+        let mut found = false;
+        for dir in data_dirs {
+            let dir = dir.as_ref().join("mime");
+            if let Ok(globs) = std::fs::read_to_string(dir.join("globs2")) {
+                database.globs.extend(parse_globs2(&globs));
+                found = true;
+            }
+            if let Ok(magic) = std::fs::read(dir.join("magic")) {
+                database.magic.extend(parse_magic(&magic));
+                found = true;
+            }
+        }
+        database
+            .magic
+            .sort_by_key(|rule| core::cmp::Reverse(rule.priority));
+        found.then_some(database)
+    }
+
+    /// The database of the system, loaded from `$XDG_DATA_HOME` and `$XDG_DATA_DIRS` on first use and cached afterwards.
+    pub fn system() -> Option<&'static Self> {
+        static SYSTEM: OnceLock<Option<SystemDatabase>> = OnceLock::new();
+        SYSTEM.get_or_init(|| Self::load(data_dirs())).as_ref()
+    }
+
+    /// Matches the file name against the glob patterns. Literal patterns win over wildcards, then the highest weight, then case-sensitive patterns, then the longest pattern.
+    pub fn glob(&self, file_name: &str) -> Option<Mime> {
+        let name: Vec<char> = file_name.chars().collect();
+        let lowercase: Vec<char> = file_name.to_lowercase().chars().collect();
+        self.globs
+            .iter()
+            .filter(|glob| {
+                let name = if glob.case_sensitive {
+                    &name
+                } else {
+                    &lowercase
+                };
+                glob_match(&glob.pattern, name)
+            })
+            .max_by_key(|glob| {
+                (
+                    glob.is_literal(),
+                    glob.weight,
+                    glob.case_sensitive,
+                    glob.pattern.len(),
+                )
+            })
+            .map(|glob| glob.mime.clone())
+    }
+
+    /// Matches the data against the magic rules, by descending priority.
+    pub fn magic(&self, data: &[u8]) -> Option<Mime> {
+        self.magic
+            .iter()
+            .find(|rule| rule.matches(data))
+            .map(|rule| rule.mime.clone())
+    }
+
+    /// Guesses the MIME type of a file by its name, and by the magic rules if no glob matches.
+    pub fn lookup(&self, path: &Path) -> Option<Mime> {
+        if let Some(mime) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| self.glob(name))
+        {
+            return Some(mime);
+        }
+
+        use std::io::Read;
+        let mut prefix = Vec::new();
+        std::fs::File::open(path)
+            .and_then(|file| {
+                file.take(crate::utils::SNIFF_LENGTH as u64)
+                    .read_to_end(&mut prefix)
+            })
+            .ok()?;
+        self.magic(&prefix)
+    }
+}
+
+/// The data directories of the XDG Base Directory Specification, from the most to the least important.
+fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    match std::env::var_os("XDG_DATA_HOME") {
+        Some(home) if !home.is_empty() => dirs.push(PathBuf::from(home)),
+        _ => {
+            if let Some(home) = std::env::var_os("HOME") {
+                dirs.push(Path::new(&home).join(".local/share"));
+            }
+        }
+    }
+    match std::env::var("XDG_DATA_DIRS") {
+        Ok(data_dirs) if !data_dirs.is_empty() => dirs.extend(
+            data_dirs
+                .split(':')
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from),
+        ),
+        _ => dirs.extend([
+            PathBuf::from("/usr/local/share"),
+            PathBuf::from("/usr/share"),
+        ]),
+    }
+    dirs
+}
+
+/// Parses the `weight:mime:pattern[:flags]` lines of a `globs2` file, skipping comments and invalid lines.
+fn parse_globs2(globs: &str) -> impl Iterator<Item = Glob> + '_ {
+    globs
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let weight = fields.next()?.parse().ok()?;
+            let mime = Mime::from_str(fields.next()?).ok()?;
+            let pattern = fields.next()?;
+            let case_sensitive = fields
+                .next()
+                .is_some_and(|flags| flags.split(',').any(|flag| flag == "cs"));
+            let pattern = if case_sensitive {
+                pattern.chars().collect()
+            } else {
+                pattern.to_lowercase().chars().collect()
+            };
+            Some(Glob {
+                weight,
+                mime,
+                pattern,
+                case_sensitive,
+            })
+        })
+}
+
+/// Reads an ASCII decimal number, stopping at the first non-digit.
+fn parse_number(data: &[u8], pos: &mut usize) -> Option<usize> {
+    let start = *pos;
+    while data.get(*pos).is_some_and(u8::is_ascii_digit) {
+        *pos += 1;
+    }
+    std::str::from_utf8(&data[start..*pos]).ok()?.parse().ok()
+}
+
+/// Parses a `[indent]>offset=length value[&mask][~word-size][+range]\n` line.
+fn parse_matchlet(data: &[u8], pos: &mut usize) -> Option<Matchlet> {
+    let indent = if data.get(*pos) == Some(&b'>') {
+        0
+    } else {
+        parse_number(data, pos)? as u32
+    };
+    (data.get(*pos) == Some(&b'>')).then_some(())?;
+    *pos += 1;
+    let offset = parse_number(data, pos)?;
+    (data.get(*pos) == Some(&b'=')).then_some(())?;
+    let length = u16::from_be_bytes([*data.get(*pos + 1)?, *data.get(*pos + 2)?]) as usize;
+    *pos += 3;
+    let mut value = data.get(*pos..*pos + length)?.to_vec();
+    *pos += length;
+
+    let mut mask = None;
+    if data.get(*pos) == Some(&b'&') {
+        mask = Some(data.get(*pos + 1..*pos + 1 + length)?.to_vec());
+        *pos += 1 + length;
+    }
+    let mut word_size = 1;
+    if data.get(*pos) == Some(&b'~') {
+        *pos += 1;
+        word_size = parse_number(data, pos)?;
+    }
+    let mut range = 1;
+    if data.get(*pos) == Some(&b'+') {
+        *pos += 1;
+        range = parse_number(data, pos)?;
+    }
+    (data.get(*pos) == Some(&b'\n')).then_some(())?;
+    *pos += 1;
+
+    // Values with a word size are stored big-endian, and compared in host byte order.
+    if cfg!(target_endian = "little") && word_size > 1 {
+        for chunk in value.chunks_mut(word_size) {
+            chunk.reverse();
+        }
+        if let Some(mask) = &mut mask {
+            for chunk in mask.chunks_mut(word_size) {
+                chunk.reverse();
+            }
+        }
+    }
+
+    Some(Matchlet {
+        indent,
+        offset,
+        value,
+        mask,
+        range: range.max(1),
+    })
+}
+
+/// Parses the sections of a `magic` file, stopping at the first malformed line.
+fn parse_magic(data: &[u8]) -> Vec<MagicRule> {
+    const HEADER: &[u8] = b"MIME-Magic\0\n";
+
+    let mut rules = Vec::new();
+    let Some(mut pos) = data.starts_with(HEADER).then_some(HEADER.len()) else {
+        return rules;
+    };
+    while data.get(pos) == Some(&b'[') {
+        let Some(end) = data[pos..].iter().position(|&b| b == b'\n') else {
+            break;
+        };
+        let header = std::str::from_utf8(&data[pos + 1..pos + end]).unwrap_or_default();
+        pos += end + 1;
+        let Some((priority, mime)) = header.strip_suffix(']').and_then(|h| h.split_once(':'))
+        else {
+            break;
+        };
+
+        let mut matchlets = Vec::new();
+        while pos < data.len() && data[pos] != b'[' {
+            match parse_matchlet(data, &mut pos) {
+                Some(matchlet) => matchlets.push(matchlet),
+                None => return rules,
+            }
+        }
+        if let (Ok(priority), Ok(mime)) = (priority.parse(), Mime::from_str(mime)) {
+            rules.push(MagicRule {
+                priority,
+                mime,
+                matchlets,
+            });
+        }
+    }
+    rules
+}
+
+/// Matches a name against a glob pattern with `*`, `?`, and `[...]` (with ranges and `!` negation).
+fn glob_match(pattern: &[char], name: &[char]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+        Some('*') => (0..=name.len()).any(|skip| glob_match(&pattern[1..], &name[skip..])),
+        Some('?') => !name.is_empty() && glob_match(&pattern[1..], &name[1..]),
+        Some('[') => {
+            let Some(&c) = name.first() else {
+                return false;
+            };
+            let Some(close) = pattern
+                .iter()
+                .skip(2)
+                .position(|&p| p == ']')
+                .map(|i| i + 2)
+            else {
+                return pattern.first() == name.first() && glob_match(&pattern[1..], &name[1..]);
+            };
+            let (negated, set) = match pattern[1] {
+                '!' => (true, &pattern[2..close]),
+                _ => (false, &pattern[1..close]),
+            };
+            let mut matched = false;
+            let mut i = 0;
+            while i < set.len() {
+                if i + 2 < set.len() && set[i + 1] == '-' {
+                    matched |= (set[i]..=set[i + 2]).contains(&c);
+                    i += 3;
+                } else {
+                    matched |= set[i] == c;
+                    i += 1;
+                }
+            }
+            matched != negated && glob_match(&pattern[close + 1..], &name[1..])
+        }
+        Some(&p) => name.first() == Some(&p) && glob_match(&pattern[1..], &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> SystemDatabase {
+        SystemDatabase::load(["tests/fixtures/xdg"]).unwrap()
+    }
+
+    #[test]
+    fn missing_database() {
+        assert_eq!(SystemDatabase::load(["does/not/exist"]), None);
+    }
+
+    #[test]
+    fn globs() {
+        let database = fixture();
+        assert_eq!(database.glob("logo.png").unwrap(), "image/png");
+        assert_eq!(database.glob("LOGO.PNG").unwrap(), "image/png");
+        assert_eq!(database.glob("Makefile").unwrap(), "text/x-makefile");
+        assert_eq!(database.glob("Makefile.in").unwrap(), "text/x-makefile");
+        assert_eq!(database.glob("main.c").unwrap(), "text/x-csrc");
+        assert_eq!(database.glob("main.C").unwrap(), "text/x-c++src");
+        assert_eq!(
+            database.glob("archive.tar.gz").unwrap(),
+            "application/x-compressed-tar"
+        );
+        assert_eq!(database.glob("notes.gz").unwrap(), "application/gzip");
+        assert_eq!(database.glob("photo1.jpg").unwrap(), "image/jpeg");
+        assert_eq!(database.glob("unknown.zzz"), None);
+    }
+
+    #[test]
+    fn magic() {
+        let database = fixture();
+        let png = std::fs::read("tests/fixtures/pixel").unwrap();
+        assert_eq!(database.magic(&png).unwrap(), "image/png");
+        assert_eq!(
+            database.magic(b"%!PS-Adobe-3.0").unwrap(),
+            "application/postscript"
+        );
+        assert_eq!(database.magic(b"  <svg xmlns=").unwrap(), "image/svg+xml");
+        assert_eq!(
+            database.magic(&[0xF5, 0x12]).unwrap(),
+            "application/x-weird"
+        );
+        assert_eq!(database.magic(b"plain text"), None);
+    }
+
+    #[test]
+    fn lookup_falls_back_to_magic() {
+        let database = fixture();
+        assert_eq!(
+            database.lookup(Path::new("tests/fixtures/pixel")).unwrap(),
+            "image/png"
+        );
+        assert_eq!(database.lookup(Path::new("tests/fixtures/blob.bin")), None);
+    }
+
+    #[test]
+    fn from_path_system() {
+        std::env::set_var("XDG_DATA_HOME", "tests/fixtures/xdg");
+        std::env::set_var("XDG_DATA_DIRS", "does/not/exist");
+        assert_eq!(
+            Mime::from_path_system(Path::new("tests/fixtures/Makefile.am")).unwrap(),
+            "text/x-makefile"
+        );
+        assert_eq!(
+            Mime::from_path_system(Path::new("tests/fixtures/pixel")).unwrap(),
+            "image/png"
+        );
+        // Not in the database, so the built-in logic decides.
+        let notes = Path::new("tests/fixtures/notes.txt");
+        assert_eq!(
+            Mime::from_path_system(notes).unwrap(),
+            Mime::from_file(notes).unwrap()
+        );
+    }
+
+    #[test]
+    fn glob_patterns() {
+        let matches = |pattern: &str, name: &str| {
+            glob_match(
+                &pattern.chars().collect::<Vec<_>>(),
+                &name.chars().collect::<Vec<_>>(),
+            )
+        };
+        assert!(matches("*.[ch]", "main.c"));
+        assert!(!matches("*.[ch]", "main.o"));
+        assert!(matches("photo[0-9].jpg", "photo7.jpg"));
+        assert!(matches("file[!a].txt", "fileb.txt"));
+        assert!(!matches("file[!a].txt", "filea.txt"));
+        assert!(matches("?akefile", "Makefile"));
+    }
+}
@@ -0,0 +1,304 @@
+#![cfg(all(feature = "magic", feature = "texture"))]
+
+//! Lightweight sniffing of textual formats that `infer` has no magic number for.
+
+use crate::Mime;
+
+/// The deepest nesting `is_json` follows before giving up.
+const MAX_JSON_DEPTH: usize = 128;
+
+/// Guesses the type of UTF-8 text from how it starts: a JSON document, an SVG image, an XML document, an HTML document, or else plain text. Only the leading markup is inspected, so a document that merely mentions `<html` later on stays `text/plain`.
+pub(crate) fn mime_type_by_text(text: &str) -> Mime {
+    let text = text.trim_start_matches('\u{FEFF}').trim_start();
+    if text.starts_with(['{', '[']) && is_json(text) {
+        Mime::APPLICATION_JSON
+    } else if text.starts_with("<?xml") {
+        match root_element(text) {
+            Some("svg") => Mime::IMAGE_SVG,
+            _ => Mime::TEXT_XML,
+        }
+    } else if starts_with_tag(text, "svg") {
+        Mime::IMAGE_SVG
+    } else if starts_with_ignore_case(text, "<!doctype html")
+        || starts_with_tag_ignore_case(text, "html")
+    {
+        Mime::TEXT_HTML
+    } else {
+        Mime::TEXT_PLAIN
+    }
+}
+
+/// Returns the name of the first element after the XML prolog, skipping processing instructions, comments and the doctype.
+fn root_element(mut text: &str) -> Option<&str> {
+    loop {
+        text = &text[text.find('<')?..];
+        if let Some(rest) = text.strip_prefix("<!--") {
+            text = &rest[rest.find("-->")? + 3..];
+        } else if text.starts_with("<?") || text.starts_with("<!") {
+            text = &text[text.find('>')? + 1..];
+        } else {
+            let name = &text[1..];
+            let end = name
+                .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                .unwrap_or(name.len());
+            return Some(&name[..end]);
+        }
+    }
+}
+
+fn starts_with_tag(text: &str, name: &str) -> bool {
+    text.strip_prefix('<')
+        .and_then(|rest| rest.strip_prefix(name))
+        .is_some_and(ends_tag_name)
+}
+
+fn starts_with_tag_ignore_case(text: &str, name: &str) -> bool {
+    starts_with_ignore_case(text, "<")
+        && starts_with_ignore_case(&text[1..], name)
+        && ends_tag_name(&text[1 + name.len()..])
+}
+
+fn ends_tag_name(rest: &str) -> bool {
+    rest.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.as_bytes()
+        .get(..prefix.len())
+        .is_some_and(|head| head.eq_ignore_ascii_case(prefix.as_bytes()))
+}
+
+/// Returns `true` if the text is exactly one JSON value, surrounded by optional whitespace.
+fn is_json(text: &str) -> bool {
+    let mut parser = JsonParser {
+        bytes: text.as_bytes(),
+        pos: 0,
+    };
+    parser.value(0) && {
+        parser.skip_whitespace();
+        parser.pos == parser.bytes.len()
+    }
+}
+
+/// A validating JSON parser that builds nothing.
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn literal(&mut self, literal: &[u8]) -> bool {
+        let found = self.bytes[self.pos..].starts_with(literal);
+        if found {
+            self.pos += literal.len();
+        }
+        found
+    }
+
+    fn value(&mut self, depth: usize) -> bool {
+        if depth > MAX_JSON_DEPTH {
+            return false;
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.object(depth),
+            Some(b'[') => self.array(depth),
+            Some(b'"') => self.string(),
+            Some(b't') => self.literal(b"true"),
+            Some(b'f') => self.literal(b"false"),
+            Some(b'n') => self.literal(b"null"),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => false,
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> bool {
+        self.pos += 1;
+        if self.eat(b'}') {
+            return true;
+        }
+        loop {
+            self.skip_whitespace();
+            if !(self.peek() == Some(b'"') && self.string() && self.eat(b':')) {
+                return false;
+            }
+            if !self.value(depth + 1) {
+                return false;
+            }
+            if self.eat(b'}') {
+                return true;
+            }
+            if !self.eat(b',') {
+                return false;
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> bool {
+        self.pos += 1;
+        if self.eat(b']') {
+            return true;
+        }
+        loop {
+            if !self.value(depth + 1) {
+                return false;
+            }
+            if self.eat(b']') {
+                return true;
+            }
+            if !self.eat(b',') {
+                return false;
+            }
+        }
+    }
+
+    fn string(&mut self) -> bool {
+        self.pos += 1;
+        while let Some(byte) = self.peek() {
+            self.pos += 1;
+            match byte {
+                b'"' => return true,
+                b'\\' => {
+                    let Some(escaped) = self.peek() else {
+                        return false;
+                    };
+                    self.pos += 1;
+                    match escaped {
+                        b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => {}
+                        b'u' => {
+                            let hex = self.bytes.get(self.pos..self.pos + 4);
+                            if !hex.is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) {
+                                return false;
+                            }
+                            self.pos += 4;
+                        }
+                        _ => return false,
+                    }
+                }
+                0x00..=0x1F => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    fn number(&mut self) -> bool {
+        let start = self.pos;
+        self.literal(b"-");
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return false,
+        }
+        if self.literal(b".") && !self.digits_required() {
+            return false;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !self.digits_required() {
+                return false;
+            }
+        }
+        self.pos > start
+    }
+
+    fn digits(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+
+    fn digits_required(&mut self) -> bool {
+        let start = self.pos;
+        self.digits();
+        self.pos > start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json() {
+        for text in [
+            r#"{"name": "mime_more", "keywords": ["mime", "dataurl"]}"#,
+            "\u{FEFF}  [1, -2.5e3, true, false, null, \"\\u00e9\\n\"]\n",
+            "{}",
+        ] {
+            assert_eq!(mime_type_by_text(text), Mime::APPLICATION_JSON, "{text}");
+        }
+        for text in [
+            "{ margin: 0 }",
+            "[section]\nkey = value",
+            "[1, 2,]",
+            "{\"a\": 1} trailing",
+            "[\"unterminated]",
+            "[01]",
+        ] {
+            assert_eq!(mime_type_by_text(text), Mime::TEXT_PLAIN, "{text}");
+        }
+        let deep = "[".repeat(MAX_JSON_DEPTH + 2) + &"]".repeat(MAX_JSON_DEPTH + 2);
+        assert_eq!(mime_type_by_text(&deep), Mime::TEXT_PLAIN);
+    }
+
+    #[test]
+    fn svg() {
+        for text in [
+            r#"<svg xmlns="http://www.w3.org/2000/svg"/>"#,
+            "<?xml version=\"1.0\"?>\n<!-- logo -->\n<!DOCTYPE svg>\n<svg>\n</svg>",
+        ] {
+            assert_eq!(mime_type_by_text(text), Mime::IMAGE_SVG, "{text}");
+        }
+        assert_eq!(mime_type_by_text("<svgfont/>"), Mime::TEXT_PLAIN);
+    }
+
+    #[test]
+    fn xml() {
+        assert_eq!(
+            mime_type_by_text("<?xml version=\"1.0\"?><feed><title>svg</title></feed>"),
+            Mime::TEXT_XML
+        );
+        assert_eq!(mime_type_by_text("<?xml version=\"1.0\"?>"), Mime::TEXT_XML);
+    }
+
+    #[test]
+    fn html() {
+        for text in [
+            "<!DOCTYPE html><title>Test</title>",
+            "\n  <!doctype HTML>",
+            "<html lang=\"en\">",
+            "<HTML>",
+        ] {
+            assert_eq!(mime_type_by_text(text), Mime::TEXT_HTML, "{text}");
+        }
+        for text in [
+            "Paste this into the page: <html><body></body></html>",
+            "<htmlish>",
+        ] {
+            assert_eq!(mime_type_by_text(text), Mime::TEXT_PLAIN, "{text}");
+        }
+    }
+}
@@ -22,7 +22,7 @@
     doc = "Checks if the data is a texture via `from_utf8`. This function performs better in small files."
 )]
 pub fn is_texture_std(data: &[u8]) -> bool {
-    std::str::from_utf8(data).is_ok()
+    core::str::from_utf8(data).is_ok()
 }
 
 #[cfg_attr(
@@ -30,7 +30,7 @@ pub fn is_texture_std(data: &[u8]) -> bool {
     doc = "Checks if the data is a texture. This function is faster in large files than `is_texture_std`, but it can also be slower in some cases."
 )]
 pub fn is_texture_manual(data: &[u8], min_infer_length: i32) -> bool {
-    use std::cmp::min;
+    use core::cmp::min;
     let min_infer_length = min_infer_length as usize;
     let mut i = 0;
     while i < min(min_infer_length, data.len()) {
@@ -75,13 +75,14 @@ pub fn is_texture_manual(data: &[u8], min_infer_length: i32) -> bool {
 #[cfg(feature = "texture")]
 #[cfg_attr(
     feature = "texture",
-    doc = "Check if the MIME is a texture. This function uses `mime`, and returns if the MIME type is a texture (type is `text`, or is `json`, `svg`, or `xml`, etc.)."
+    doc = "Check if the MIME is a texture. This function uses `mime`, and returns if the MIME type is a texture (type is `text`, or the subtype is `json` or `xml`, or has a `+json` or `+xml` suffix, e.g. `image/svg+xml`)."
 )]
 pub fn is_texture_mime(mime: &mime::Mime) -> bool {
     mime.type_() == mime::TEXT
         || mime.subtype() == mime::JSON
-        || mime.subtype() == mime::SVG
         || mime.subtype() == mime::XML
+        || mime.suffix() == Some(mime::JSON)
+        || mime.suffix() == Some(mime::XML)
 }
 
 #[cfg(feature = "texture")]
@@ -95,6 +96,12 @@ mod tests {
         assert!(is_texture_mime(&mime::APPLICATION_JSON));
         assert!(is_texture_mime(&mime::IMAGE_SVG));
         assert!(is_texture_mime(&mime::TEXT_HTML));
+        assert!(is_texture_mime(&"application/ld+json".parse().unwrap()));
+        assert!(is_texture_mime(
+            &"application/vnd.api+json".parse().unwrap()
+        ));
+        assert!(is_texture_mime(&"application/atom+xml".parse().unwrap()));
+        assert!(!is_texture_mime(&"application/epub+zip".parse().unwrap()));
         assert!(!is_texture_mime(&mime::APPLICATION_OCTET_STREAM));
         assert!(!is_texture_mime(&mime::APPLICATION_PDF));
         assert!(!is_texture_mime(&mime::IMAGE_PNG));
@@ -0,0 +1,285 @@
+#![cfg(feature = "tokio")]
+//! Async utilities for [`tokio`](https://docs.rs/tokio/latest/tokio/) servers.
+//!
+//! Besides sniffing streams, it adds `async` counterparts of the methods that touch the filesystem, reading through `tokio::fs` so that the runtime isn't blocked. They share the guessing logic with the blocking methods.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use mime_more::{tokio::sniff_stream, Mime};
+//!
+//! let (mime, body) = sniff_stream(request_body).await;
+//! // `body` still yields every byte of `request_body`, including the sniffed prefix.
+//!
+//! let mime = Mime::from_path_async(Path::new("assets/logo.png")).await?;
+//! ```
+
+#[cfg(any(feature = "magic", feature = "texture"))]
+use crate::utils::SNIFF_LENGTH;
+use crate::{Mime, MimeError};
+#[cfg(feature = "magic")]
+use ::tokio::io::AsyncRead;
+#[cfg(any(feature = "magic", feature = "texture"))]
+use ::tokio::io::AsyncReadExt;
+use ::tokio::io::Chain;
+use std::io::Cursor;
+use std::path::Path;
+
+/// A reader that replays the sniffed prefix before the rest of the stream.
+pub type ChainedReader<R> = Chain<Cursor<Vec<u8>>, R>;
+
+/// Returns `true` if the prefix is valid UTF-8, allowing a multi-byte character to be cut off at the end of the prefix.
+#[cfg(all(feature = "magic", feature = "texture"))]
+fn is_texture_prefix(prefix: &[u8]) -> bool {
+    match std::str::from_utf8(prefix) {
+        Ok(_) => true,
+        Err(err) => err.error_len().is_none(),
+    }
+}
+
+#[cfg(feature = "magic")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(len = prefix.len()))
+)]
+fn sniff(prefix: &[u8]) -> anyhow::Result<Mime> {
+    if let Ok(inferred) = Mime::from_content(prefix) {
+        debug!(source = "magic", mime = %inferred, "magic matcher matched");
+        return Ok(inferred);
+    }
+
+    #[cfg(feature = "texture")]
+    if !prefix.is_empty() && is_texture_prefix(prefix) {
+        debug!(source = "texture", "prefix is texture");
+        return Ok(Mime::TEXT_PLAIN);
+    }
+
+    debug!("no type could be sniffed");
+    anyhow::bail!("Failed to infer mime type")
+}
+
+/// Classifies a stream without consuming it. At most 8 KiB are buffered and inspected via `Mime::from_content` (with a `text/plain` fallback for textures when the `texture` feature is enabled). The returned reader yields exactly the bytes of the original stream.
+#[cfg(feature = "magic")]
+pub async fn sniff_stream<R: AsyncRead + Unpin>(
+    mut reader: R,
+) -> (anyhow::Result<Mime>, ChainedReader<R>) {
+    let mut prefix = Vec::with_capacity(SNIFF_LENGTH);
+    let result = (&mut reader)
+        .take(SNIFF_LENGTH as u64)
+        .read_to_end(&mut prefix)
+        .await;
+    let mime = match result {
+        Ok(_) => sniff(&prefix),
+        Err(err) => Err(err.into()),
+    };
+    (mime, Cursor::new(prefix).chain(reader))
+}
+
+/// Reads at most `max_bytes` from the start of the file.
+#[cfg(any(feature = "magic", feature = "texture"))]
+async fn read_prefix(path: &Path, max_bytes: usize) -> std::io::Result<Vec<u8>> {
+    let mut prefix = Vec::new();
+    ::tokio::fs::File::open(path)
+        .await?
+        .take(max_bytes as u64)
+        .read_to_end(&mut prefix)
+        .await?;
+    Ok(prefix)
+}
+
+impl Mime {
+    /// The async counterpart of `from_file`: guesses the MIME type via `from_path_and_content`, reading only the first few kilobytes of the file.
+    pub async fn from_path_async(path: &Path) -> Result<Self, MimeError> {
+        #[cfg(any(feature = "magic", feature = "texture"))]
+        {
+            let prefix = read_prefix(path, SNIFF_LENGTH).await?;
+            crate::from_path_and_content(path, &prefix)
+        }
+
+        #[cfg(not(any(feature = "magic", feature = "texture")))]
+        {
+            ::tokio::fs::metadata(path).await?;
+            crate::from_path_and_content(path)
+        }
+    }
+
+    /// The async counterpart of `sniff_path`: infers the MIME type from at most `max_bytes` of the file, falling back to the extension.
+    #[cfg(feature = "magic")]
+    pub async fn sniff_path_async(path: &Path, max_bytes: usize) -> Result<Self, MimeError> {
+        Self::sniff_prefix(path, &read_prefix(path, max_bytes).await?)
+    }
+}
+
+#[cfg(feature = "dataurl")]
+impl crate::Dataurl {
+    /// The async counterpart of `from_path`, reading the file with `tokio::fs::read`. The type and the encoding are chosen by the same code as `from_path`, and a failed read names the path too.
+    pub async fn from_path_async(path: &Path) -> Result<Self, MimeError> {
+        let data = ::tokio::fs::read(path)
+            .await
+            .map_err(|err| crate::dataurl::io_error(path, err))?;
+        Self::from_path_and_data(path, data)
+    }
+}
+
+#[cfg(test)]
+mod fs_tests {
+    use crate::{Mime, MimeError};
+    use std::path::Path;
+
+    #[::tokio::test]
+    async fn from_path_async() {
+        assert_eq!(
+            Mime::from_path_async(Path::new("tests/fixtures/pixel.png"))
+                .await
+                .unwrap(),
+            Mime::from_file(Path::new("tests/fixtures/pixel.png")).unwrap()
+        );
+        assert!(matches!(
+            Mime::from_path_async(Path::new("does/not/exist.png")).await,
+            Err(MimeError::Io(_))
+        ));
+    }
+
+    #[cfg(feature = "magic")]
+    #[::tokio::test]
+    async fn sniff_path_async() {
+        let pixel = Path::new("tests/fixtures/pixel");
+        assert_eq!(
+            Mime::sniff_path_async(pixel, 8192).await.unwrap(),
+            Mime::IMAGE_PNG
+        );
+        assert!(matches!(
+            Mime::sniff_path_async(pixel, 1).await,
+            Err(MimeError::InferenceFailed)
+        ));
+        assert!(matches!(
+            Mime::sniff_path_async(Path::new("does/not/exist"), 8192).await,
+            Err(MimeError::Io(_))
+        ));
+    }
+
+    #[cfg(feature = "dataurl")]
+    #[::tokio::test]
+    async fn dataurl_from_path_async() {
+        use crate::dataurl::{Dataurl, DataurlCharset};
+        for (path, mime, charset) in [
+            (
+                "tests/fixtures/logo.svg",
+                Mime::IMAGE_SVG,
+                DataurlCharset::Utf8,
+            ),
+            (
+                "tests/fixtures/notes.txt",
+                Mime::TEXT_PLAIN,
+                DataurlCharset::Utf8,
+            ),
+            (
+                "tests/fixtures/pixel.png",
+                Mime::IMAGE_PNG,
+                DataurlCharset::Base64,
+            ),
+            (
+                "tests/fixtures/blob.bin",
+                Mime::APPLICATION_OCTET_STREAM,
+                DataurlCharset::Base64,
+            ),
+        ] {
+            let path = Path::new(path);
+            let dataurl = Dataurl::from_path_async(path).await.unwrap();
+            assert_eq!(dataurl.mime, mime, "{}", path.display());
+            assert_eq!(dataurl.charset, charset, "{}", path.display());
+            assert_eq!(dataurl, Dataurl::from_path(path).unwrap());
+        }
+        let err = Dataurl::from_path_async(Path::new("does/not/exist.svg"))
+            .await
+            .unwrap_err();
+        assert!(matches!(&err, MimeError::Io(io) if io.kind() == std::io::ErrorKind::NotFound));
+        assert!(err.to_string().contains("does/not/exist.svg"), "{err}");
+    }
+}
+
+#[cfg(all(test, feature = "magic"))]
+mod tests {
+    use super::*;
+    use ::tokio::io::ReadBuf;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// Yields the data a few bytes at a time, returning `Pending` on every other poll.
+    struct Trickle {
+        data: Vec<u8>,
+        position: usize,
+        pending: bool,
+    }
+
+    impl AsyncRead for Trickle {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<std::io::Result<()>> {
+            self.pending = !self.pending;
+            if self.pending {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            let len = 7.min(self.data.len() - self.position).min(buf.remaining());
+            let start = self.position;
+            buf.put_slice(&self.data[start..start + len]);
+            self.position += len;
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn trickle(data: Vec<u8>) -> Trickle {
+        Trickle {
+            data,
+            position: 0,
+            pending: false,
+        }
+    }
+
+    #[::tokio::test]
+    async fn sniff_large_body() {
+        let mut body = vec![
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x11, 0x45, 0x14, 0x19, 0x19, 0x81, 0x0,
+        ];
+        body.extend((0..256 * 1024).map(|i| (i % 251) as u8));
+
+        let (mime, mut reader) = sniff_stream(trickle(body.clone())).await;
+        assert_eq!(mime.unwrap(), "image/png");
+
+        let mut reassembled = Vec::new();
+        reader.read_to_end(&mut reassembled).await.unwrap();
+        assert_eq!(reassembled, body);
+    }
+
+    #[::tokio::test]
+    async fn sniff_short_body() {
+        let body = b"%PDF-1.7".to_vec();
+        let (mime, mut reader) = sniff_stream(trickle(body.clone())).await;
+        assert_eq!(mime.unwrap(), "application/pdf");
+
+        let mut reassembled = Vec::new();
+        reader.read_to_end(&mut reassembled).await.unwrap();
+        assert_eq!(reassembled, body);
+    }
+
+    #[cfg(feature = "texture")]
+    #[::tokio::test]
+    async fn sniff_text_body() {
+        let body = "こんにちは、世界！".repeat(1024).into_bytes();
+        let (mime, _) = sniff_stream(trickle(body)).await;
+        assert_eq!(mime.unwrap(), "text/plain");
+    }
+
+    #[::tokio::test]
+    async fn sniff_empty_body() {
+        let (mime, mut reader) = sniff_stream(trickle(Vec::new())).await;
+        assert!(mime.is_err());
+
+        let mut reassembled = Vec::new();
+        reader.read_to_end(&mut reassembled).await.unwrap();
+        assert!(reassembled.is_empty());
+    }
+}
@@ -1,8 +1,105 @@
+/// How many leading bytes of a file are read when its content is inspected. It is enough for every matcher in `infer`, including the 262 bytes needed by tar.
+#[cfg(all(
+    feature = "std",
+    any(feature = "magic", feature = "texture", feature = "system"),
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub const SNIFF_LENGTH: usize = 8192;
+
+/// Trims ASCII whitespace and one leading `.` from an extension, as found in configuration files or on the command line.
+#[cfg(any(feature = "extension", feature = "extension-light"))]
+pub fn normalize_extension(ext: &str) -> Result<&str, crate::MimeError> {
+    let ext = ext.trim_ascii();
+    let ext = ext.strip_prefix('.').unwrap_or(ext);
+    if ext.is_empty() {
+        return Err(crate::MimeError::EmptyExtension);
+    }
+    Ok(ext)
+}
+
+/// Extracts the extension of an uploaded filename, splitting on both `/` and `\\` so that Windows paths are handled on every platform. Like `Path::extension`, a leading dot starts a hidden name rather than an extension.
+#[cfg(any(feature = "extension", feature = "extension-light"))]
+pub fn filename_extension(name: &str) -> Option<&str> {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    if let Some(ext) = crate::compound::split(name) {
+        return Some(ext);
+    }
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && !ext.is_empty() => Some(ext),
+        _ => None,
+    }
+}
+
+/// Returns the percent-decoded last path segment of a URL or a relative specifier, without its query string and fragment, e.g. `my icon.svg` for `https://cdn.example.com/my%20icon.svg?v=1#top`. Invalid UTF-8 after decoding is replaced rather than rejected.
+#[cfg(any(feature = "extension", feature = "extension-light"))]
+pub fn url_file_name(url: &str) -> alloc::borrow::Cow<'_, str> {
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |start| &rest[start..]),
+        None => url,
+    };
+    percent_decode(path.rsplit('/').next().unwrap_or(path))
+}
+
+/// Decodes `%XX` escapes, keeping malformed ones as they are.
+#[cfg(any(feature = "extension", feature = "extension-light"))]
+fn percent_decode(s: &str) -> alloc::borrow::Cow<'_, str> {
+    use alloc::borrow::Cow;
+    if !s.contains('%') {
+        return Cow::Borrowed(s);
+    }
+    let bytes = s.as_bytes();
+    let mut decoded = alloc::vec::Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes.get(i + 1..i + 3) {
+            Some(&[high, low])
+                if bytes[i] == b'%' && high.is_ascii_hexdigit() && low.is_ascii_hexdigit() =>
+            {
+                let hex = |digit: u8| (digit as char).to_digit(16).unwrap_or(0) as u8;
+                decoded.push(hex(high) << 4 | hex(low));
+                i += 3;
+            }
+            _ => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    match alloc::string::String::from_utf8(decoded) {
+        Ok(decoded) => Cow::Owned(decoded),
+        Err(err) => Cow::Owned(alloc::string::String::from_utf8_lossy(err.as_bytes()).into_owned()),
+    }
+}
+
+#[cfg(all(
+    feature = "std",
+    any(feature = "extension", feature = "extension-light")
+))]
+/// Returns the extension of the path, or its compound extension such as `tar.gz` when the last two segments are known.
 pub fn get_extension(path: &std::path::Path) -> Option<&str> {
-    path.extension().and_then(|ext| ext.to_str())
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(crate::compound::split)
+        .or_else(|| path.extension().and_then(|ext| ext.to_str()))
+}
+
+/// The same as `get_extension`, but tells a missing extension apart from one that isn't valid UTF-8.
+#[cfg(all(
+    feature = "std",
+    any(feature = "extension", feature = "extension-light")
+))]
+pub fn require_extension(path: &std::path::Path) -> Result<&str, crate::MimeError> {
+    if let Some(ext) = get_extension(path) {
+        return Ok(ext);
+    }
+    Err(match path.extension() {
+        Some(ext) => crate::MimeError::UndecodableExtension(ext.to_os_string()),
+        None => crate::MimeError::NoExtension(path.to_path_buf()),
+    })
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std", feature = "extension-light"))]
 mod tests {
     use super::*;
 
@@ -10,5 +107,41 @@ mod tests {
     fn test_get_extension() {
         assert_eq!(get_extension(std::path::Path::new("test.txt")), Some("txt"));
         assert_eq!(get_extension(std::path::Path::new("test")), None);
+        assert_eq!(
+            get_extension(std::path::Path::new("dist/archive.tar.gz")),
+            Some("tar.gz")
+        );
+        assert_eq!(
+            get_extension(std::path::Path::new("photo.final.jpg")),
+            Some("jpg")
+        );
+    }
+
+    #[test]
+    fn test_filename_extension() {
+        assert_eq!(filename_extension("photo.final.JPG"), Some("JPG"));
+        assert_eq!(filename_extension("C:\\Users\\me\\report.pdf"), Some("pdf"));
+        assert_eq!(filename_extension("dir.d/archive.tar.gz"), Some("tar.gz"));
+        assert_eq!(filename_extension("folder.v2\\README"), None);
+        assert_eq!(filename_extension("photo."), None);
+        assert_eq!(filename_extension(".gitignore"), None);
+        assert_eq!(filename_extension(""), None);
+    }
+
+    #[test]
+    fn test_url_file_name() {
+        assert_eq!(
+            url_file_name("https://cdn.example.com/assets/app.js?v=123#hash"),
+            "app.js"
+        );
+        assert_eq!(url_file_name("./icon.svg?url"), "icon.svg");
+        assert_eq!(
+            url_file_name("../fonts/Source%20Han%20Sans.woff2"),
+            "Source Han Sans.woff2"
+        );
+        assert_eq!(url_file_name("caf%C3%A9%2Epng#%zz"), "café.png");
+        assert_eq!(url_file_name("100%.txt"), "100%.txt");
+        assert_eq!(url_file_name("https://example.com"), "");
+        assert_eq!(url_file_name("https://example.com/?file=logo.png"), "");
     }
 }
@@ -0,0 +1,109 @@
+#![cfg(feature = "wasm")]
+//! [`wasm-bindgen`](https://docs.rs/wasm-bindgen/latest/wasm_bindgen/) bindings, so that JavaScript tooling (e.g. Rolldown's JS plugins) can share the exact same MIME logic as the Rust core.
+//!
+//! Build the module with `cargo rustc --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib`, then run `wasm-bindgen` on the resulting `.wasm` file.
+//!
+//! # Example
+//!
+//! ```js
+//! import { fromExt, fromContent, encodeDataurl, parseDataurl } from "mime_more";
+//!
+//! fromExt("png"); // "image/png"
+//! const url = encodeDataurl("text/plain", new TextEncoder().encode("Hello"));
+//! const { mime, data } = parseDataurl(url);
+//! ```
+
+use crate::{Dataurl, Mime};
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// Guesses the MIME type from the extension, returning `undefined` when it is unknown.
+#[wasm_bindgen(js_name = fromExt)]
+pub fn from_ext(ext: &str) -> Option<String> {
+    #[cfg(feature = "extension")]
+    if let Ok(guessed) = Mime::from_ext(ext) {
+        return Some(guessed.to_string());
+    }
+
+    Mime::from_ext_light(ext).ok().map(|mime| mime.to_string())
+}
+
+/// Infers the MIME type from the magic number of the data, returning `undefined` when it is unknown.
+#[wasm_bindgen(js_name = fromContent)]
+pub fn from_content(data: &[u8]) -> Option<String> {
+    Mime::from_content(data).ok().map(|mime| mime.to_string())
+}
+
+/// Encodes the data as a data URL with the given MIME type. Throws if the MIME type is invalid.
+#[wasm_bindgen(js_name = encodeDataurl)]
+pub fn encode_dataurl(mime: &str, data: &[u8]) -> Result<String, JsError> {
+    let mime = Mime::from_str(mime).map_err(|err| JsError::new(&err.to_string()))?;
+    Dataurl::new(mime, data.to_vec())
+        .map(|dataurl| dataurl.to_string())
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// The result of `parseDataurl`, exposing `mime` and `data` to JavaScript.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedDataurl {
+    mime: String,
+    data: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl ParsedDataurl {
+    #[wasm_bindgen(getter)]
+    pub fn mime(&self) -> String {
+        self.mime.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn data(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+}
+
+/// Parses a data URL into its MIME type and decoded data. Throws if the data URL is invalid.
+#[wasm_bindgen(js_name = parseDataurl)]
+pub fn parse_dataurl(s: &str) -> Result<ParsedDataurl, JsError> {
+    let dataurl = Dataurl::from_str(s).map_err(|err| JsError::new(&err.to_string()))?;
+    Ok(ParsedDataurl {
+        mime: dataurl.mime.to_string(),
+        data: dataurl.data.into_vec(),
+    })
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn from_ext_known_and_unknown() {
+        assert_eq!(from_ext("png").as_deref(), Some("image/png"));
+        assert_eq!(from_ext("unknown"), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn from_content_png() {
+        let data = &[
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x11, 0x45, 0x14, 0x19, 0x19, 0x81, 0x0,
+        ];
+        assert_eq!(from_content(data).as_deref(), Some("image/png"));
+        assert_eq!(from_content(&[]), None);
+    }
+
+    #[wasm_bindgen_test]
+    fn dataurl_round_trip() {
+        let data = &[
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x11, 0x45, 0x14, 0x19, 0x19, 0x81, 0x0,
+        ];
+        let encoded = encode_dataurl("image/png", data).unwrap();
+        assert_eq!(encoded, "data:image/png;base64,iVBORw0KGgoRRRQZGYEA");
+
+        let parsed = parse_dataurl(&encoded).unwrap();
+        assert_eq!(parsed.mime(), "image/png");
+        assert_eq!(parsed.data(), data.to_vec());
+    }
+}
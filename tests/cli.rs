@@ -0,0 +1,96 @@
+use assert_cmd::Command;
+
+fn mime_more() -> Command {
+    Command::cargo_bin("mime-more").unwrap()
+}
+
+#[test]
+fn guess_by_extension() {
+    let source = if cfg!(feature = "extension") {
+        "extension"
+    } else {
+        "extension-light"
+    };
+    mime_more()
+        .args(["guess", "tests/fixtures/pixel.png"])
+        .assert()
+        .success()
+        .stdout(format!("image/png ({source})\n"));
+}
+
+#[test]
+fn guess_by_magic() {
+    mime_more()
+        .args(["guess", "tests/fixtures/pixel"])
+        .assert()
+        .success()
+        .stdout("image/png (magic)\n");
+}
+
+// `mime_guess` knows `.bin` as `application/octet-stream`.
+#[cfg(not(feature = "extension"))]
+#[test]
+fn guess_unknown_type() {
+    mime_more()
+        .args(["guess", "tests/fixtures/blob.bin"])
+        .assert()
+        .code(3)
+        .stdout("application/octet-stream (fallback)\n");
+}
+
+#[test]
+fn guess_missing_file() {
+    mime_more()
+        .args(["guess", "tests/fixtures/missing.png"])
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn sniff_stdin() {
+    mime_more()
+        .args(["sniff", "-"])
+        .write_stdin(std::fs::read("tests/fixtures/pixel.png").unwrap())
+        .assert()
+        .success()
+        .stdout("image/png (magic)\n");
+
+    mime_more()
+        .args(["sniff", "-"])
+        .write_stdin("Hello, world!")
+        .assert()
+        .success()
+        .stdout("text/plain (texture)\n");
+}
+
+#[test]
+fn dataurl_round_trip() {
+    let encoded = mime_more()
+        .args(["dataurl", "encode", "tests/fixtures/pixel.png"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let url = String::from_utf8(encoded).unwrap();
+    assert!(url.starts_with("data:image/png;base64,"));
+
+    mime_more()
+        .args(["dataurl", "decode", url.trim()])
+        .assert()
+        .success()
+        .stdout(std::fs::read("tests/fixtures/pixel.png").unwrap());
+}
+
+#[test]
+fn dataurl_decode_invalid() {
+    mime_more()
+        .args(["dataurl", "decode", "data:nonsense"])
+        .assert()
+        .code(4);
+}
+
+#[test]
+fn usage_error() {
+    mime_more().args(["frobnicate"]).assert().code(2);
+}
@@ -0,0 +1,5 @@
+export interface Circle {
+  radius: number;
+}
+
+export declare function area(circle: Circle): number;
@@ -0,0 +1,37 @@
+//! Checks that the core crate still builds without the `std` feature, i.e. that it uses nothing from `std` itself. The host target has a standard library, which the `mime` and `urlencoding` dependencies link, so this doesn't prove a bare-metal build; CI checks the same features against `wasm32-unknown-unknown`, which has no filesystem.
+
+use std::process::Command;
+
+fn check_no_std(features: &str) {
+    let status = Command::new(env!("CARGO"))
+        .args([
+            "check",
+            "--lib",
+            "--no-default-features",
+            "--features",
+            features,
+        ])
+        .args(["--target-dir", "target/no_std_check"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .status()
+        .unwrap();
+    assert!(
+        status.success(),
+        "no_std build failed with features `{features}`"
+    );
+}
+
+#[test]
+fn no_std_light() {
+    check_no_std("extension-light");
+}
+
+#[test]
+fn no_std_light_and_texture() {
+    check_no_std("extension-light,texture");
+}
+
+#[test]
+fn no_std_dataurl() {
+    check_no_std("dataurl");
+}